@@ -1,24 +1,66 @@
+import contextlib
 import json
 import logging
 import os
 import shutil
 import sys
 import time
+from dataclasses import asdict
 from pathlib import Path
 from typing import Any
 
 import click
+from click.core import ParameterSource
 from rich.console import Console
 from rich.logging import RichHandler
 from rich.traceback import install
 
+from tpi_redes import __version__
 from tpi_redes.config import (
+    ACK_TIMEOUT,
+    AUDIT_LOG_PATH,
+    BUNDLE_THRESHOLD,
     CHUNK_SIZE,
-    DEFAULT_SAVE_DIR,
     DEFAULT_HOST,
     DEFAULT_PROXY_PORT,
     DEFAULT_SERVER_PORT,
+    ENABLED_PROTOCOLS,
+    FSYNC_POLICY,
+    KNOCK_SECRET,
+    PING_TIMEOUT,
+    POST_VALIDATORS,
+    PRESETS_PATH,
+    RECEIVED_INDEX_PATH,
+    SETTINGS_PATH,
+    SPEED_ALERT_SECONDS,
+    TUNING_PROFILES,
+    UDP_PACING_RATE,
+    UDP_SESSION_TIMEOUT,
+    get_default_output_dir,
 )
+from tpi_redes.core.exit_codes import ExitCode, exit_code_for
+from tpi_redes.observability.audit_log import AuditLog
+from tpi_redes.observability.peer_stats import PeerStats
+from tpi_redes.observability.receive_report import (
+    build_receive_report,
+    parse_window,
+    render_receive_report,
+)
+from tpi_redes.services.knock import KnockGate, send_knock
+from tpi_redes.services.listeners import parse_listener
+from tpi_redes.services.mirror import Mirror
+from tpi_redes.services.pause_control import PauseControl
+from tpi_redes.services.presets import Preset, PresetStore
+from tpi_redes.services.sleep_inhibitor import SleepInhibitor
+from tpi_redes.transfer.checksum_cache import ChecksumCache
+from tpi_redes.transfer.file_validator import FileValidator
+from tpi_redes.transfer.post_validation import parse_validators
+from tpi_redes.transfer.received_index import ReceivedIndex
+from tpi_redes.transfer.routing import load_routes, parse_route
+from tpi_redes.transfer.speed_alert import SpeedMonitor
+from tpi_redes.transfer.summary import TransferSummary
+from tpi_redes.transfer.write_policy import parse_fsync_policy
+from tpi_redes.transport.ping import ProbeResult, probe_bandwidth
 
 console = Console(stderr=True)
 logger = logging.getLogger("tpi-redes")
@@ -85,13 +127,10 @@ def handle_exception(exc_type: Any, exc_value: Any, exc_traceback: Any):
 
     if debug_mode:
         console.print_exception(show_locals=True)
-        sys.exit(1)
-
-    sys.exit(1)
-
-    console.print(f"[bold red]Error:[/bold red] {exc_value}")
+    else:
+        console.print(f"[bold red]Error:[/bold red] {exc_value}")
 
-    sys.exit(1)
+    sys.exit(exit_code_for(exc_value))
 
 
 install(show_locals=True)
@@ -143,12 +182,154 @@ def sniffer_service(port: int, interface: str | None):
 @click.option("--port", default=DEFAULT_SERVER_PORT, help="Port to listen on")
 @click.option(
     "--protocol",
-    type=click.Choice(["tcp", "udp"]),
+    type=click.Choice(ENABLED_PROTOCOLS),
     default="tcp",
     help="Protocol to use",
 )
 @click.option(
-    "--save-dir", default=DEFAULT_SAVE_DIR, help="Directory to save received files"
+    "--save-dir",
+    default=None,
+    help="Directory to save received files (default: TPI_REDES_SAVE_DIR)",
+)
+@click.option(
+    "--listen",
+    "listen_specs",
+    multiple=True,
+    metavar="PROTOCOL:PORT[=DIR]",
+    help=(
+        "Also listen on this port, storing files in DIR if given (repeatable); "
+        "every other setting is shared with the main listener"
+    ),
+)
+@click.option(
+    "--per-peer-dirs",
+    is_flag=True,
+    help="Store each sender's files in a subdirectory named after its IP",
+)
+@click.option(
+    "--route",
+    "route_specs",
+    multiple=True,
+    metavar="MATCH=DIR",
+    help="Store files from a sender IP or network in DIR (repeatable)",
+)
+@click.option(
+    "--routes-file",
+    default=None,
+    type=click.Path(exists=True, dir_okay=False, path_type=Path),
+    help='JSON list of routing rules, e.g. [{"match": "10.0.0.0/24", "dir": "lab"}]',
+)
+@click.option(
+    "--audit",
+    is_flag=True,
+    help="Verify incoming files without writing anything to disk",
+)
+@click.option(
+    "--inhibit-sleep",
+    is_flag=True,
+    help="Keep the system awake while transfers are active",
+)
+@click.option(
+    "--audit-log",
+    default=AUDIT_LOG_PATH or None,
+    type=click.Path(dir_okay=False, path_type=Path),
+    help="Append a hash-chained record of every file to this journal",
+)
+@click.option(
+    "--index",
+    "index_path",
+    default=RECEIVED_INDEX_PATH or None,
+    type=click.Path(dir_okay=False, path_type=Path),
+    help="Record where each verified file was stored, for find-received",
+)
+@click.option(
+    "--mirror-dir",
+    default=None,
+    type=click.Path(file_okay=False, path_type=Path),
+    help="Also copy every verified file to this directory, in the background",
+)
+@click.option(
+    "--atomic",
+    is_flag=True,
+    help="Stage each connection's files and keep them only if all verify (TCP)",
+)
+@click.option(
+    "--dedup",
+    is_flag=True,
+    help="Hard-link files whose content was already received instead of storing it",
+)
+@click.option(
+    "--fsync",
+    "fsync_spec",
+    default=FSYNC_POLICY,
+    metavar="POLICY",
+    help="When to sync received files: never, on-complete or every:N MiB (TCP)",
+)
+@click.option(
+    "--validate",
+    "validate_spec",
+    default=POST_VALIDATORS,
+    metavar="NAMES",
+    help="Check verified files' content: comma-separated zip, tar, json, csv, "
+    "image or all",
+)
+@click.option(
+    "--knock",
+    is_flag=True,
+    help="Only accept senders that knock with TPI_REDES_KNOCK_SECRET first",
+)
+@click.option(
+    "--raw",
+    is_flag=True,
+    help="Accept unframed TCP streams (e.g. from nc) and store each until EOF",
+)
+@click.option(
+    "--raw-name",
+    default="raw_stream.bin",
+    help="File name for streams received with --raw",
+)
+@click.option(
+    "--until-complete",
+    is_flag=True,
+    help="Exit after the first file is received and verified",
+)
+@click.option(
+    "--count",
+    type=click.IntRange(min=1),
+    default=None,
+    help="Exit after this many files are received and verified",
+)
+@click.option(
+    "--forever",
+    is_flag=True,
+    help="Keep receiving until interrupted (default)",
+)
+@click.option(
+    "--window",
+    "window_spec",
+    default=None,
+    metavar="DURATION",
+    help="Accept transfers for this long (e.g. 90s, 10m, 2h), then report and exit",
+)
+@click.option(
+    "--session-timeout",
+    type=click.FloatRange(min=0, min_open=True),
+    default=UDP_SESSION_TIMEOUT,
+    help="Abort a UDP transfer as stalled after this many silent seconds",
+)
+@click.option(
+    "--max-rate",
+    type=click.IntRange(min=1),
+    default=None,
+    metavar="BYTES",
+    help="Limit each TCP connection's ingress to this many bytes/s",
+)
+@click.option(
+    "--report",
+    "report_path",
+    default=None,
+    type=click.Path(dir_okay=False, path_type=Path),
+    help="Also write the --window report to this JSON file",
 )
 @click.option(
     "--sniff",
@@ -157,13 +338,101 @@ def sniffer_service(port: int, interface: str | None):
 )
 @click.option("--interface", default=None, help="Network interface to sniff")
 def start_server(
-    port: int, protocol: str, save_dir: str, sniff: bool, interface: str | None
+    port: int,
+    protocol: str,
+    save_dir: str | None,
+    listen_specs: tuple[str, ...],
+    per_peer_dirs: bool,
+    route_specs: tuple[str, ...],
+    routes_file: Path | None,
+    audit: bool,
+    inhibit_sleep: bool,
+    audit_log: Path | None,
+    index_path: Path | None,
+    mirror_dir: Path | None,
+    atomic: bool,
+    dedup: bool,
+    fsync_spec: str,
+    validate_spec: str,
+    knock: bool,
+    raw: bool,
+    raw_name: str,
+    until_complete: bool,
+    count: int | None,
+    forever: bool,
+    window_spec: str | None,
+    session_timeout: float,
+    max_rate: int | None,
+    report_path: Path | None,
+    sniff: bool,
+    interface: str | None,
 ):
     """Start the file receiver server.
 
     Optionally spawns a privileged subprocess for packet sniffing if --sniff is used.
     Also starts the DiscoveryService listener to announce presence on the network.
+    With --until-complete or --count it exits once that many files verified,
+    and with --window once the time is up; on exit it prints a summary of
+    every file handled, plus a per-sender report with --window. Each --listen
+    adds a listener served in its own thread; only its directory can differ,
+    every other setting is shared with the main listener.
     """
+    if raw and protocol != "tcp":
+        raise click.UsageError("--raw is only supported with --protocol tcp.")
+    if max_rate and protocol != "tcp":
+        raise click.UsageError("--max-rate is only supported with --protocol tcp.")
+    if atomic and protocol != "tcp":
+        raise click.UsageError("--atomic is only supported with --protocol tcp.")
+    if dedup and (protocol != "tcp" or raw):
+        raise click.UsageError("--dedup is only supported with --protocol tcp.")
+    if knock and not KNOCK_SECRET:
+        raise click.UsageError("--knock requires TPI_REDES_KNOCK_SECRET to be set.")
+    if sum([until_complete, count is not None, forever]) > 1:
+        raise click.UsageError(
+            "--until-complete, --count and --forever are mutually exclusive."
+        )
+    if forever and window_spec:
+        raise click.UsageError("--window cannot be used with --forever.")
+    if report_path and not window_spec:
+        raise click.UsageError("--report requires --window.")
+    if mirror_dir and (audit or raw or atomic):
+        raise click.UsageError(
+            "--mirror-dir cannot be used with --audit, --raw or --atomic."
+        )
+    if listen_specs and (raw or until_complete or count is not None):
+        raise click.UsageError(
+            "--listen cannot be used with --raw, --until-complete or --count."
+        )
+    max_files = 1 if until_complete else count
+    journal = AuditLog(audit_log) if audit_log else None
+    try:
+        routes = load_routes(routes_file) if routes_file else []
+        routes += [parse_route(spec) for spec in route_specs]
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="--route/--routes-file") from e
+    try:
+        write_policy = parse_fsync_policy(fsync_spec)
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="--fsync") from e
+    try:
+        validators = parse_validators(validate_spec)
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="--validate") from e
+    try:
+        window = parse_window(window_spec) if window_spec else None
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="--window") from e
+    try:
+        listeners = [parse_listener(s, ENABLED_PROTOCOLS) for s in listen_specs]
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="--listen") from e
+    bound = [(protocol, port)] + [(li.protocol, li.port) for li in listeners]
+    if len(set(bound)) < len(bound):
+        raise click.BadParameter(
+            "each protocol and port may only be listened on once",
+            param_hint="--listen",
+        )
+
     sniffer_process = None
     discovery = None
 
@@ -171,7 +440,10 @@ def start_server(
     import threading
 
     try:
-        save_dir = str(Path(save_dir).expanduser().resolve())
+        if save_dir:
+            save_dir = str(Path(save_dir).expanduser().resolve())
+        else:
+            save_dir = str(get_default_output_dir(create=not audit))
 
         if sniff:
             if shutil.which("pkexec") is None:
@@ -252,31 +524,129 @@ def start_server(
                     ),
                     flush=True,
                 )
+
+        knock_gate = KnockGate(KNOCK_SECRET.encode("utf-8")) if knock else None
+        if knock_gate:
+            knock_gate.listen()
+
+        def make_server(kind: str, listen_port: int, directory: str) -> Any:
+            common = {
+                "host": "0.0.0.0",
+                "port": listen_port,
+                "save_dir": directory,
+                "per_peer_dirs": per_peer_dirs,
+                "audit": audit,
+                "sleep_inhibitor": SleepInhibitor() if inhibit_sleep else None,
+                "audit_log": journal,
+                "routes": routes,
+                "knock_gate": knock_gate,
+            }
+            if kind == "raw":
+                from tpi_redes.transport.raw_tcp_server import RawTCPServer
+
+                return RawTCPServer(raw_name=raw_name, **common)
+            if kind == "tcp":
+                from tpi_redes.transport.tcp_server import TCPServer
+
+                return TCPServer(
+                    atomic=atomic, dedup=dedup, write_policy=write_policy, **common
+                )
+            from tpi_redes.transport.udp_server import UDPServer
+
+            udp_server = UDPServer(**common)
+            udp_server.session_timeout = session_timeout
+            return udp_server
+
+        server = make_server("raw" if raw else protocol, port, save_dir)
+        extra_servers = [
+            make_server(
+                li.protocol,
+                li.port,
+                str(li.save_dir.resolve()) if li.save_dir else save_dir,
+            )
+            for li in listeners
+        ]
+
         from tpi_redes.services.discovery import DiscoveryService
 
+        # Protocol -> port senders should use; the main listener wins a tie.
+        advertised: dict[str, int] = {}
+        for each_protocol, each_port in bound:
+            advertised.setdefault(each_protocol, each_port)
         discovery = DiscoveryService()
         try:
-            discovery.listen(port)
+            discovery.listen(
+                port,
+                lambda: {
+                    "protocols": list(advertised),
+                    "listeners": advertised,
+                    **server.capacity(),
+                },
+            )
         except OSError:
             logger.warning("Discovery service could not bind (port in use?). Skipping.")
 
         logger.info(f"Starting {protocol.upper()} server on port {port}...")
-        logger.info(f"Saving files to: {save_dir}")
+        if audit:
+            logger.info(f"Audit mode: nothing will be written to {save_dir}")
+        else:
+            logger.info(f"Saving files to: {save_dir}")
         print(
             json.dumps({"type": "SERVER_READY", "protocol": protocol, "port": port}),
             flush=True,
         )
 
-        if protocol == "tcp":
-            from tpi_redes.transport.tcp_server import TCPServer
-
-            server = TCPServer(host="0.0.0.0", port=port, save_dir=save_dir)
-            server.start()
-        else:
-            from tpi_redes.transport.udp_server import UDPServer
-
-            server = UDPServer(host="0.0.0.0", port=port, save_dir=save_dir)
+        servers = [server, *extra_servers]
+        received_index = ReceivedIndex(index_path) if index_path else None
+        mirror = Mirror(mirror_dir) if mirror_dir else None
+        pause = PauseControl()
+        peer_stats = PeerStats()
+        started = time.time()
+        window_ends = time.monotonic() + window if window else None
+        for each in servers:
+            each.max_files = max_files
+            each.validators = validators
+            each.received_index = received_index
+            each.window_ends = window_ends
+            each.mirror = mirror
+            each.max_rate = max_rate
+            each.pause = pause
+            each.peer_stats = peer_stats
+
+        threads = []
+        for li, extra in zip(listeners, extra_servers, strict=True):
+            logger.info(f"Also listening with {li.protocol.upper()} on {li.port}")
+            thread = threading.Thread(
+                target=_run_listener, args=(extra, li.protocol), daemon=True
+            )
+            thread.start()
+            threads.append(thread)
+            print(
+                json.dumps(
+                    {"type": "SERVER_READY", "protocol": li.protocol, "port": li.port}
+                ),
+                flush=True,
+            )
+        with pause:
             server.start()
+            if window:
+                # Extra listeners close with the same window; wait for them.
+                for thread in threads:
+                    while thread.is_alive():
+                        thread.join(0.5)
+        outcomes = [o for each in servers for o in each.outcomes]
+        _print_receive_summary(
+            outcomes,
+            sum(each.files_completed for each in servers),
+            peer_stats.snapshot(),
+        )
+        if mirror:
+            logger.info("Finishing queued mirror copies...")
+            stats = mirror.close()
+            print(json.dumps({"type": "MIRROR_SUMMARY", **stats}), flush=True)
+        if window:
+            report = build_receive_report(outcomes, started, time.time())
+            _print_receive_report(report, report_path)
 
     except OSError as e:
         if e.errno == 98:
@@ -293,13 +663,287 @@ def start_server(
             discovery.stop()
 
 
+def _run_listener(server: Any, protocol: str):
+    """Serve an additional --listen listener until it stops.
+
+    Runs in a daemon thread; a listener that cannot bind is reported and
+    leaves the others running.
+
+    Args:
+        server: The listener's server.
+        protocol: Its protocol, for the log.
+
+    Returns:
+        None: No return value.
+    """
+    try:
+        server.start()
+    except OSError as e:
+        logger.error(f"{protocol.upper()} listener on port {server.port} failed: {e}")
+
+
+def _print_receive_summary(
+    outcomes: list[dict[str, Any]],
+    completed: int,
+    peers: dict[str, dict[str, int]],
+):
+    """Print a `RECEIVE_SUMMARY` JSON event (for IPC) and a table of files.
+
+    Args:
+        outcomes: Every file the server handled, as recorded by the server.
+        completed: How many of them were received and verified.
+        peers: Connection counters per sender IP, from `PeerStats`.
+
+    Returns:
+        None: No return value.
+    """
+    from rich.table import Table
+
+    from tpi_redes.observability.packet_logger import PacketLogger
+
+    print(
+        json.dumps(
+            {
+                "type": "RECEIVE_SUMMARY",
+                "completed": completed,
+                "files": outcomes,
+                "errors": PacketLogger.error_stats(),
+                "warnings": PacketLogger.warning_stats(),
+                "peers": peers,
+            }
+        ),
+        flush=True,
+    )
+    if not outcomes:
+        return
+
+    table = Table(title=f"Received {completed}/{len(outcomes)} file(s)")
+    table.add_column("File", style="cyan")
+    table.add_column("Sender", style="magenta")
+    table.add_column("Bytes", justify="right")
+    table.add_column("Result")
+    for o in outcomes:
+        style = "green" if o["result"] in ("verified", "raw") else "red"
+        table.add_row(
+            o["filename"],
+            o["peer"],
+            str(o["size"]),
+            f"[{style}]{o['result']}[/{style}]",
+        )
+    console.print(table)
+
+
+def _print_receive_report(report: dict[str, Any], path: Path | None):
+    """Print the `--window` report as a `RECEIVE_REPORT` JSON event and text.
+
+    Args:
+        report: The report from `build_receive_report`.
+        path: Optional JSON file to also write the report to.
+
+    Returns:
+        None: No return value.
+    """
+    print(json.dumps({"type": "RECEIVE_REPORT", **report}), flush=True)
+    console.print(render_receive_report(report), markup=False, highlight=False)
+    if path:
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(json.dumps(report, indent=2), encoding="utf-8")
+        logger.info(f"Report written to {path}")
+
+
+def _load_preset(
+    ctx: click.Context, _param: click.Parameter, name: str | None
+) -> str | None:
+    """Use a saved preset's options as defaults for `send-file`.
+
+    Runs eagerly, so options given explicitly on the command line still win.
+
+    Args:
+        ctx: The click context of the command being invoked.
+        _param: The `--preset` parameter.
+        name: Preset name, if one was given.
+
+    Returns:
+        str | None: The preset name, unchanged.
+    """
+    if not name:
+        return name
+    try:
+        saved = PresetStore().get(name)
+    except (KeyError, ValueError) as e:
+        raise click.BadParameter(f"Cannot load preset '{name}': {e}") from e
+
+    defaults = {
+        key: value
+        for key, value in asdict(saved).items()
+        if key != "name" and value is not None and value is not False
+    }
+    ctx.default_map = {**(ctx.default_map or {}), **defaults}
+    return name
+
+
+def _load_pairing(
+    ctx: click.Context, _param: click.Parameter, payload: str | None
+) -> str | None:
+    """Use the receiver reachable through a pairing code as `send-file` defaults.
+
+    Args:
+        ctx: The click context of the command being invoked.
+        _param: The `--pair` parameter.
+        payload: Pairing code printed by `pair-code`, if one was given.
+
+    Returns:
+        str | None: The pairing code, unchanged.
+    """
+    if not payload:
+        return payload
+    from tpi_redes.services.pairing import connect_from_pairing
+
+    try:
+        ip, info = connect_from_pairing(payload, KNOCK_SECRET or None)
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="--pair") from e
+
+    defaults: dict[str, Any] = {"ip": ip, "port": info.port, "protocol": info.protocol}
+    if info.knock:
+        defaults["knock"] = True
+    if info.secret:
+        ctx.meta["knock_secret"] = info.secret
+    ctx.default_map = {**(ctx.default_map or {}), **defaults}
+    return payload
+
+
+def _probe_receiver(ip: str, port: int, total_bytes: int) -> ProbeResult | None:
+    """Probe the receiver's throughput and announce the estimated ETA.
+
+    Emits a `PROBE_RESULT` JSON event. A failed probe is logged and ignored
+    so the transfer proceeds with the configured settings.
+
+    Args:
+        ip: The receiver's IP address.
+        port: The receiver's port.
+        total_bytes: Bytes the transfer will send.
+
+    Returns:
+        ProbeResult | None: The measurement, or None if the probe failed.
+    """
+    try:
+        result = probe_bandwidth((ip, port))
+    except (OSError, ValueError) as e:
+        logger.warning(f"Bandwidth probe failed, using configured settings: {e}")
+        return None
+    eta = result.eta(total_bytes)
+    print(
+        json.dumps(
+            {
+                "type": "PROBE_RESULT",
+                **result.to_dict(),
+                "total_bytes": total_bytes,
+                "eta_seconds": round(eta, 3),
+            }
+        ),
+        flush=True,
+    )
+    console.print(
+        f"[cyan]Probe:[/cyan] {result.throughput / 1e6:.1f} MB/s, "
+        f"RTT {result.rtt_ms:.1f} ms, ETA {eta:.1f} s"
+    )
+    return result
+
+
+def _print_probe_summary(result: ProbeResult, total_bytes: int, elapsed: float):
+    """Compare the probe's estimate with the throughput actually achieved.
+
+    Emits a `PROBE_SUMMARY` JSON event.
+
+    Args:
+        result: The probe measurement taken before the transfer.
+        total_bytes: Bytes the transfer sent.
+        elapsed: Seconds the transfer took.
+
+    Returns:
+        None: No return value.
+    """
+    achieved = total_bytes / elapsed if elapsed > 0 else 0.0
+    print(
+        json.dumps(
+            {
+                "type": "PROBE_SUMMARY",
+                "estimated_throughput": result.throughput,
+                "achieved_throughput": round(achieved, 1),
+                "estimated_seconds": round(result.eta(total_bytes), 3),
+                "elapsed_seconds": round(elapsed, 3),
+            }
+        ),
+        flush=True,
+    )
+    console.print(
+        f"[cyan]Probe estimate:[/cyan] {result.throughput / 1e6:.1f} MB/s, "
+        f"achieved {achieved / 1e6:.1f} MB/s"
+    )
+
+
+def _print_transfer_summary(summary: TransferSummary):
+    """Report how the finished transfer went.
+
+    Emits a `TRANSFER_SUMMARY` JSON event and prints the summary to the
+    console.
+
+    Args:
+        summary: The summary returned by the client.
+
+    Returns:
+        None: No return value.
+    """
+    print(json.dumps({"type": "TRANSFER_SUMMARY", **summary.to_dict()}), flush=True)
+    console.print(str(summary), markup=False, highlight=False)
+
+
+def _resolve_tuning(profile: str | None, protocol: str) -> dict[str, int | float]:
+    """Return a tuning profile's values for options left at their defaults.
+
+    Options given on the command line or by a preset keep their values.
+
+    Args:
+        profile: Name of the profile, if one was chosen.
+        protocol: Protocol whose values are used.
+
+    Returns:
+        dict[str, int | float]: Option values to apply, by parameter name.
+    """
+    if not profile:
+        return {}
+    ctx = click.get_current_context()
+    return {
+        key: value
+        for key, value in TUNING_PROFILES[profile][protocol].items()
+        if ctx.get_parameter_source(key) is ParameterSource.DEFAULT
+    }
+
+
 @cli.command()
 @click.argument("files", nargs=-1, type=click.Path(exists=True, dir_okay=False))
+@click.option(
+    "--preset",
+    default=None,
+    is_eager=True,
+    expose_value=False,
+    callback=_load_preset,
+    help="Use the options saved under this name (see `preset save`)",
+)
+@click.option(
+    "--pair",
+    default=None,
+    is_eager=True,
+    expose_value=False,
+    callback=_load_pairing,
+    help="Send to the receiver in this pairing code (see `pair-code`)",
+)
 @click.option("--ip", prompt="Receiver IP", help="IP address of the receiver")
 @click.option("--port", default=DEFAULT_SERVER_PORT, help="Port to connect to")
 @click.option(
     "--protocol",
-    type=click.Choice(["tcp", "udp"]),
+    type=click.Choice(ENABLED_PROTOCOLS),
     default="tcp",
     help="Protocol to use",
 )
@@ -311,6 +955,136 @@ def start_server(
 @click.option("--interface", default=None, help="Network interface to sniff")
 @click.option("--delay", default=0.0, help="Delay between chunks in seconds")
 @click.option("--chunk-size", default=CHUNK_SIZE, help="Buffer size in bytes")
+@click.option(
+    "--pacing-rate",
+    type=click.IntRange(min=1),
+    default=UDP_PACING_RATE,
+    help="UDP target send rate in bytes per second",
+)
+@click.option(
+    "--no-pacing",
+    is_flag=True,
+    help="Disable UDP pacing and send at line rate",
+)
+@click.option(
+    "--max-read-bytes",
+    type=click.IntRange(min=0),
+    default=None,
+    help=(
+        "Send at most this many bytes of each file (e.g. growing logs); "
+        "required to send FIFOs and devices"
+    ),
+)
+@click.option(
+    "--emit-manifest",
+    is_flag=True,
+    help="Send a SHA256SUMS manifest of the batch for the receiver to verify",
+)
+@click.option(
+    "--inhibit-sleep",
+    is_flag=True,
+    help="Keep the system awake while transfers are active",
+)
+@click.option(
+    "--audit-log",
+    default=AUDIT_LOG_PATH or None,
+    type=click.Path(dir_okay=False, path_type=Path),
+    help="Append a hash-chained record of every file to this journal",
+)
+@click.option(
+    "--sla",
+    type=click.FloatRange(min=0, min_open=True),
+    default=None,
+    help="Expected duration in seconds; warn if the transfer will miss it",
+)
+@click.option(
+    "--knock",
+    is_flag=True,
+    help="Knock with TPI_REDES_KNOCK_SECRET before sending (gated receivers)",
+)
+@click.option(
+    "--no-checksum-cache",
+    is_flag=True,
+    help="Always re-hash source files instead of reusing cached checksums",
+)
+@click.option(
+    "--confirm",
+    is_flag=True,
+    help="Wait for the receiver to confirm each file's checksum (TCP only)",
+)
+@click.option(
+    "--restart-on-change",
+    type=click.IntRange(min=0),
+    default=0,
+    help="Resend a file from scratch up to N times if it changes mid-send (TCP)",
+)
+@click.option(
+    "--tune",
+    type=click.Choice(sorted(TUNING_PROFILES)),
+    default=None,
+    help="Apply a tuning profile to options not given explicitly",
+)
+@click.option(
+    "--socket-buffer",
+    type=int,
+    default=None,
+    help="Socket send buffer in bytes (bounds the data in flight)",
+)
+@click.option(
+    "--ack-timeout",
+    default=ACK_TIMEOUT,
+    help="Seconds to wait for each --confirm acknowledgement",
+)
+@click.option(
+    "--fec",
+    type=click.IntRange(min=2, max=255),
+    default=None,
+    metavar="N",
+    help="Send a parity datagram per N datagrams to repair losses (UDP)",
+)
+@click.option(
+    "--probe",
+    is_flag=True,
+    help="Measure throughput first to size chunks and buffers and estimate ETA "
+    "(TCP)",
+)
+@click.option(
+    "--min-speed",
+    type=click.FloatRange(min=0, min_open=True),
+    default=None,
+    help="Warn when the speed stays below this many bytes/s",
+)
+@click.option(
+    "--max-speed",
+    type=click.FloatRange(min=0, min_open=True),
+    default=None,
+    help="Warn when the speed stays at this cap in bytes/s (e.g. a shaped link)",
+)
+@click.option(
+    "--speed-window",
+    type=click.FloatRange(min=0, min_open=True),
+    default=SPEED_ALERT_SECONDS,
+    help="Seconds the speed must stay out of range before alerting",
+)
+@click.option(
+    "--on-slow",
+    type=click.Choice(["warn", "cancel"]),
+    default="warn",
+    help="What to do when --min-speed is not met",
+)
+@click.option(
+    "--bundle-below",
+    type=click.IntRange(min=0),
+    default=BUNDLE_THRESHOLD,
+    metavar="BYTES",
+    help="Send files up to this size together in bundles; 0 disables (TCP)",
+)
+@click.option(
+    "--trailing-hash",
+    is_flag=True,
+    help="Hash files while sending them instead of beforehand, reading each "
+    "once (TCP)",
+)
 def send_file(
     files: tuple[str],
     ip: str,
@@ -320,6 +1094,28 @@ def send_file(
     interface: str | None,
     delay: float,
     chunk_size: int,
+    pacing_rate: int,
+    no_pacing: bool,
+    max_read_bytes: int | None,
+    emit_manifest: bool,
+    inhibit_sleep: bool,
+    audit_log: Path | None,
+    sla: float | None,
+    knock: bool,
+    no_checksum_cache: bool,
+    confirm: bool,
+    restart_on_change: int,
+    tune: str | None,
+    socket_buffer: int | None,
+    ack_timeout: float,
+    fec: int | None,
+    probe: bool,
+    min_speed: float | None,
+    max_speed: float | None,
+    speed_window: float,
+    on_slow: str,
+    bundle_below: int,
+    trailing_hash: bool,
 ):
     """Send one or more files to a remote server.
 
@@ -329,12 +1125,66 @@ def send_file(
     if not files:
         console.print("[bold red]Error:[/bold red] No files provided.")
         return
+    tuning = _resolve_tuning(tune, protocol)
+    if emit_manifest:
+        tuning.pop("restart_on_change", None)
+    chunk_size = int(tuning.get("chunk_size", chunk_size))
+    pacing_rate = int(tuning.get("pacing_rate", pacing_rate))
+    socket_buffer = int(tuning.get("socket_buffer", socket_buffer or 0)) or None
+    ack_timeout = float(tuning.get("ack_timeout", ack_timeout))
+    restart_on_change = int(tuning.get("restart_on_change", restart_on_change))
+    fec = int(tuning.get("fec", fec or 0)) or None
+    if emit_manifest and protocol != "tcp":
+        raise click.UsageError("--emit-manifest is only supported with TCP.")
+    if confirm and protocol != "tcp":
+        raise click.UsageError("--confirm is only supported with TCP.")
+    if fec and protocol != "udp":
+        raise click.UsageError("--fec is only supported with UDP.")
+    if probe and protocol != "tcp":
+        raise click.UsageError("--probe is only supported with TCP.")
+    bundle_source = click.get_current_context().get_parameter_source("bundle_below")
+    if bundle_below and bundle_source is not ParameterSource.DEFAULT:
+        # A TPI_REDES_BUNDLE_THRESHOLD default is silently ignored instead.
+        if protocol != "tcp":
+            raise click.UsageError("--bundle-below is only supported with TCP.")
+        if confirm:
+            raise click.UsageError("--bundle-below cannot be combined with --confirm.")
+    if trailing_hash and (protocol != "tcp" or confirm or emit_manifest):
+        raise click.UsageError(
+            "--trailing-hash requires TCP and cannot be used with --confirm or "
+            "--emit-manifest."
+        )
+    if on_slow == "cancel" and min_speed is None:
+        raise click.UsageError("--on-slow cancel requires --min-speed.")
+    if restart_on_change and (protocol != "tcp" or emit_manifest):
+        raise click.UsageError(
+            "--restart-on-change requires TCP and cannot be used with --emit-manifest."
+        )
+    knock_secret = click.get_current_context().meta.get("knock_secret", KNOCK_SECRET)
+    if knock and not knock_secret:
+        raise click.UsageError("--knock requires TPI_REDES_KNOCK_SECRET to be set.")
+    checksum_cache = None if no_checksum_cache else ChecksumCache()
+    speed_monitor = (
+        SpeedMonitor(min_speed, max_speed, speed_window)
+        if min_speed is not None or max_speed is not None
+        else None
+    )
 
     import subprocess
     import threading
     from pathlib import Path
 
     file_paths = [Path(f) for f in files]
+    if max_read_bytes is not None and any(
+        p.is_fifo() or p.is_char_device() or p.is_block_device() for p in file_paths
+    ):
+        if protocol != "tcp" or confirm or emit_manifest:
+            raise click.UsageError(
+                "FIFOs and devices can only be sent over TCP, without --confirm "
+                "or --emit-manifest."
+            )
+        # They are read once, so their checksum can only follow the content.
+        trailing_hash = True
 
     sniffer_process = None
 
@@ -412,24 +1262,125 @@ def send_file(
                     flush=True,
                 )
 
-        if protocol == "tcp":
-            from tpi_redes.transport.tcp_client import TCPClient
-
-            client = TCPClient()
-            client.send_files(file_paths, ip, port, delay, chunk_size)
-        else:
-            from tpi_redes.transport.udp_client import UDPClient
-
-            client = UDPClient()
-            client.send_files(file_paths, ip, port, delay, chunk_size)
+        inhibitor = SleepInhibitor() if inhibit_sleep else contextlib.nullcontext()
+        with inhibitor, PauseControl() as pause:
+            if knock:
+                send_knock(ip, knock_secret.encode("utf-8"))
+            total_bytes = sum(
+                FileValidator(p).transfer_size(max_read_bytes) for p in file_paths
+            )
+            probe_result = _probe_receiver(ip, port, total_bytes) if probe else None
+            if probe_result:
+                ctx = click.get_current_context()
+                unset = {
+                    key
+                    for key in ("chunk_size", "socket_buffer")
+                    if key not in tuning
+                    and ctx.get_parameter_source(key) is ParameterSource.DEFAULT
+                }
+                if "chunk_size" in unset:
+                    chunk_size = probe_result.chunk_size
+                if "socket_buffer" in unset:
+                    socket_buffer = probe_result.socket_buffer
+            if protocol == "tcp":
+                from tpi_redes.transport.tcp_client import TCPClient
+
+                client = TCPClient()
+                started = time.monotonic()
+                summary = client.send_files(
+                    file_paths,
+                    ip,
+                    port,
+                    delay,
+                    chunk_size,
+                    max_read_bytes=max_read_bytes,
+                    emit_manifest=emit_manifest,
+                    audit_log=AuditLog(audit_log) if audit_log else None,
+                    sla=sla,
+                    checksum_cache=checksum_cache,
+                    confirm=confirm,
+                    restarts=restart_on_change,
+                    ack_timeout=ack_timeout,
+                    socket_buffer=socket_buffer,
+                    speed_monitor=speed_monitor,
+                    cancel_when_slow=on_slow == "cancel",
+                    pause=pause,
+                    bundle_threshold=bundle_below,
+                    trailing_hash=trailing_hash,
+                )
+                if probe_result:
+                    _print_probe_summary(
+                        probe_result, total_bytes, time.monotonic() - started
+                    )
+            else:
+                from tpi_redes.transport.udp_client import UDPClient
+
+                client = UDPClient()
+                summary = client.send_files(
+                    file_paths,
+                    ip,
+                    port,
+                    delay,
+                    chunk_size,
+                    pacing_rate=None if no_pacing else pacing_rate,
+                    max_read_bytes=max_read_bytes,
+                    audit_log=AuditLog(audit_log) if audit_log else None,
+                    sla=sla,
+                    checksum_cache=checksum_cache,
+                    socket_buffer=socket_buffer,
+                    fec_group=fec,
+                    speed_monitor=speed_monitor,
+                    cancel_when_slow=on_slow == "cancel",
+                    pause=pause,
+                )
+            _print_transfer_summary(summary)
 
     except KeyboardInterrupt:
         console.print("\n[yellow]Transfer cancelled by user.[/yellow]")
+        sys.exit(ExitCode.CANCELLED)
     except Exception as e:
         raise e
     finally:
         if sniffer_process:
             sniffer_process.terminate()
+        if checksum_cache:
+            checksum_cache.save()
+            stats = checksum_cache.stats()
+            logger.debug(f"Checksum cache: {stats}")
+            print(json.dumps({"type": "CHECKSUM_CACHE", **stats}), flush=True)
+
+
+@cli.command()
+@click.option("--ip", prompt="Receiver IP", help="IP address of the receiver")
+@click.option("--port", default=DEFAULT_SERVER_PORT, help="Port to connect to")
+@click.option("--name", required=True, help="File name the receiver stores it under")
+@click.option("--chunk-size", default=CHUNK_SIZE, help="Buffer size in bytes")
+@click.option(
+    "--audit-log",
+    default=AUDIT_LOG_PATH or None,
+    type=click.Path(dir_okay=False, path_type=Path),
+    help="Append a hash-chained record of the stream to this journal",
+)
+def send_stream(ip: str, port: int, name: str, chunk_size: int, audit_log: Path | None):
+    """Send stdin to a TCP receiver as a single file of unknown size.
+
+    The size and checksum travel in a trailer once stdin reaches EOF, e.g.
+    `tar c dir | python -m tpi_redes.cli.main send-stream --ip IP --name dir.tar`.
+    """
+    from tpi_redes.transport.tcp_client import TCPClient
+
+    try:
+        TCPClient().send_stream(
+            sys.stdin.buffer,
+            Path(name).name,
+            ip,
+            port,
+            chunk_size,
+            audit_log=AuditLog(audit_log) if audit_log else None,
+        )
+    except KeyboardInterrupt:
+        console.print("\n[yellow]Transfer cancelled by user.[/yellow]")
+        sys.exit(ExitCode.CANCELLED)
 
 
 @cli.command()
@@ -447,7 +1398,7 @@ def send_file(
 @click.option(
     "--protocol",
     default="tcp",
-    type=click.Choice(["tcp", "udp"], case_sensitive=False),
+    type=click.Choice(ENABLED_PROTOCOLS, case_sensitive=False),
     help="Protocol to proxy (TCP or UDP)",
 )
 def start_proxy(
@@ -533,7 +1484,7 @@ def start_proxy(
         import traceback
 
         traceback.print_exc()
-        sys.exit(1)
+        sys.exit(exit_code_for(e))
     finally:
         if discovery:
             discovery.stop()
@@ -571,6 +1522,484 @@ def scan_network():
         console.print(table)
 
 
+@cli.command()
+@click.option("--ip", prompt="Receiver IP", help="IP address of the receiver")
+@click.option("--port", default=DEFAULT_SERVER_PORT, help="Port of the receiver")
+@click.option(
+    "--protocol",
+    type=click.Choice(ENABLED_PROTOCOLS),
+    default="tcp",
+    help="Protocol the receiver is listening on",
+)
+@click.option("--timeout", default=PING_TIMEOUT, help="Seconds to wait for a reply")
+def ping_peer(ip: str, port: int, protocol: str, timeout: float):
+    """Check that a receiver is reachable before starting a transfer.
+
+    Prints the `PingResult` as a JSON object (for IPC) and a summary line.
+    """
+    from tpi_redes.transport.ping import ping_peer as probe
+
+    result = probe((ip, port), protocol, timeout)
+    print(json.dumps({"type": "PING_RESULT", **result.to_dict()}), flush=True)
+
+    if result.reachable:
+        console.print(
+            f"[green]{ip}:{port} reachable[/green] "
+            f"({result.rtt_ms} ms, version {result.version})"
+        )
+    else:
+        console.print(f"[red]{ip}:{port} unreachable:[/red] {result.error}")
+
+
+@cli.command()
+@click.option("--port", default=DEFAULT_SERVER_PORT, help="Port of the receiver")
+@click.option(
+    "--protocol",
+    type=click.Choice(ENABLED_PROTOCOLS),
+    default="tcp",
+    help="Protocol the receiver listens on",
+)
+@click.option(
+    "--address",
+    "addresses",
+    multiple=True,
+    help="Address to advertise instead of the detected ones (repeatable)",
+)
+@click.option("--knock", is_flag=True, help="The receiver runs with --knock")
+@click.option(
+    "--with-secret",
+    is_flag=True,
+    help="Include TPI_REDES_KNOCK_SECRET in the code so senders need no setup",
+)
+def pair_code(
+    port: int,
+    protocol: str,
+    addresses: tuple[str, ...],
+    knock: bool,
+    with_secret: bool,
+):
+    """Print a pairing code for `send-file --pair` or a QR code in the UI.
+
+    Prints a `PAIRING` JSON event (for IPC) with the code and its fields.
+    """
+    import socket
+
+    from tpi_redes.services.pairing import PairingInfo, encode_pairing, local_addresses
+
+    if (knock or with_secret) and not KNOCK_SECRET:
+        raise click.UsageError("--knock requires TPI_REDES_KNOCK_SECRET to be set.")
+    info = PairingInfo(
+        addresses=list(addresses) or local_addresses(),
+        port=port,
+        protocol=protocol,
+        hostname=socket.gethostname(),
+        knock=knock or with_secret,
+        secret=KNOCK_SECRET if with_secret else None,
+    )
+    payload = encode_pairing(info)
+    fields = {k: v for k, v in info.to_dict().items() if k != "secret"}
+    print(json.dumps({"type": "PAIRING", "payload": payload, **fields}), flush=True)
+    console.print(f"[green]Pairing code:[/green] {payload}")
+    if with_secret:
+        console.print("[yellow]The code contains the knock secret.[/yellow]")
+
+
+@cli.command()
+@click.argument(
+    "directory", type=click.Path(exists=True, file_okay=False, path_type=Path)
+)
+@click.option(
+    "--manifest",
+    type=click.Path(exists=True, dir_okay=False, path_type=Path),
+    default=None,
+    help="Manifest to check against (default: DIRECTORY/SHA256SUMS)",
+)
+def verify_manifest(directory: Path, manifest: Path | None):
+    """Re-verify a received directory against its SHA256SUMS manifest.
+
+    Prints the report as JSON (for IPC) and exits with status 4 on any failure.
+    """
+    from tpi_redes.transfer.manifest import verify_tree
+
+    report = verify_tree(directory, manifest)
+    print(
+        json.dumps(
+            {
+                "valid": report.valid,
+                "ok": report.ok,
+                "mismatched": report.mismatched,
+                "missing": report.missing,
+            }
+        )
+    )
+
+    for name in report.mismatched:
+        console.print(f"[red]MISMATCH[/red] {name}")
+    for name in report.missing:
+        console.print(f"[yellow]MISSING[/yellow] {name}")
+    console.print(f"{len(report.ok)} file(s) OK.")
+
+    if not report.valid:
+        sys.exit(ExitCode.INTEGRITY)
+
+
+@cli.command()
+@click.argument(
+    "path",
+    required=False,
+    default=AUDIT_LOG_PATH or None,
+    type=click.Path(dir_okay=False, path_type=Path),
+)
+def audit_verify(path: Path | None):
+    """Check that an audit journal's hash chain has not been tampered with.
+
+    PATH defaults to TPI_REDES_AUDIT_LOG. Prints the result as JSON (for IPC)
+    and exits with status 4 if the chain is broken.
+    """
+    if path is None:
+        raise click.UsageError("No journal given and TPI_REDES_AUDIT_LOG is unset.")
+
+    result = AuditLog(path).verify()
+    print(json.dumps(asdict(result)))
+
+    if result.valid:
+        console.print(f"[green]Audit log intact[/green] ({result.entries} entries).")
+    else:
+        console.print(
+            f"[bold red]Audit log broken at line {result.error_line}:[/bold red] "
+            f"{result.error}"
+        )
+        sys.exit(ExitCode.INTEGRITY)
+
+
+@cli.command()
+@click.argument("query")
+@click.option(
+    "--index",
+    "index_path",
+    default=RECEIVED_INDEX_PATH or None,
+    type=click.Path(dir_okay=False, path_type=Path),
+    help="Index written by start-server --index (default: TPI_REDES_RECEIVED_INDEX)",
+)
+def find_received(query: str, index_path: Path | None):
+    """Find received files by checksum (or prefix) or by name pattern.
+
+    QUERY is 8 to 64 hex digits to match checksums, otherwise a glob on the
+    file name (e.g. "*.iso"). Prints a `RECEIVED_MATCHES` JSON event (for IPC)
+    and a table.
+    """
+    from rich.table import Table
+
+    if index_path is None:
+        raise click.UsageError("No index given and TPI_REDES_RECEIVED_INDEX is unset.")
+
+    matches = ReceivedIndex(index_path).find(query)
+    print(
+        json.dumps({"type": "RECEIVED_MATCHES", "query": query, "matches": matches}),
+        flush=True,
+    )
+
+    table = Table(title=f"Received files matching '{query}'")
+    table.add_column("File", style="cyan")
+    table.add_column("Path")
+    table.add_column("SHA-256")
+    table.add_column("Peer")
+    table.add_column("Transfer")
+    for m in matches:
+        path = m["path"] if m["exists"] else f"[red]{m['path']} (missing)[/red]"
+        table.add_row(
+            m["filename"], path, m["sha256"][:16], m["peer"], m["transfer_id"][:8]
+        )
+    console.print(table)
+
+
+@cli.command()
+@click.argument(
+    "files", nargs=-1, required=True, type=click.Path(exists=True, dir_okay=False)
+)
+def inspect_file(files: tuple[str, ...]):
+    """Print preview metadata (MIME type, dimensions, duration, encoding).
+
+    Emits one `FILE_INFO` JSON event per file (for IPC).
+    """
+    from tpi_redes.transfer.file_inspector import inspect_file as inspect
+
+    for file in files:
+        try:
+            info: dict[str, Any] = inspect(Path(file)).to_dict()
+        except OSError as e:
+            info = {"error": str(e)}
+        print(json.dumps({"type": "FILE_INFO", "path": file, **info}), flush=True)
+
+
+@cli.group()
+def preset():
+    """Manage named send-file presets (stored in TPI_REDES_PRESETS)."""
+
+
+@preset.command("save")
+@click.argument("name")
+@click.option("--ip", required=True, help="IP address of the receiver")
+@click.option("--port", default=DEFAULT_SERVER_PORT, help="Port of the receiver")
+@click.option(
+    "--protocol",
+    type=click.Choice(ENABLED_PROTOCOLS),
+    default="tcp",
+    help="Protocol to use",
+)
+@click.option("--chunk-size", type=int, default=None, help="Buffer size in bytes")
+@click.option("--delay", type=float, default=None, help="Delay between chunks")
+@click.option(
+    "--pacing-rate",
+    type=click.IntRange(min=1),
+    default=None,
+    help="UDP target send rate in bytes per second",
+)
+@click.option("--no-pacing", is_flag=True, help="Disable UDP pacing")
+@click.option("--emit-manifest", is_flag=True, help="Send a SHA256SUMS manifest")
+@click.option("--sla", type=float, default=None, help="Expected duration (s)")
+@click.option("--knock", is_flag=True, help="Knock before sending")
+def preset_save(name: str, **options: Any):
+    """Create or replace the preset NAME."""
+    PresetStore().save(Preset(name=name, **options))
+    console.print(f"[green]Saved preset '{name}'.[/green]")
+
+
+@preset.command("list")
+def preset_list():
+    """List saved presets.
+
+    Prints a `PRESETS` JSON event (for IPC) and a table.
+    """
+    from rich.table import Table
+
+    presets = PresetStore().load()
+    print(
+        json.dumps({"type": "PRESETS", "presets": [asdict(p) for p in presets]}),
+        flush=True,
+    )
+
+    table = Table(title="Presets")
+    table.add_column("Name", style="cyan")
+    table.add_column("Destination", style="green")
+    table.add_column("Protocol", style="magenta")
+    for p in presets:
+        table.add_row(p.name, f"{p.ip}:{p.port}", p.protocol.upper())
+    console.print(table)
+
+
+@preset.command("delete")
+@click.argument("name")
+def preset_delete(name: str):
+    """Delete the preset NAME."""
+    try:
+        PresetStore().delete(name)
+    except KeyError as e:
+        raise click.ClickException(f"No preset named '{name}'.") from e
+    console.print(f"[green]Deleted preset '{name}'.[/green]")
+
+
+@cli.group("output-dir")
+def output_dir():
+    """Check or change where received files are stored (TPI_REDES_SETTINGS)."""
+
+
+@output_dir.command("show")
+def output_dir_show():
+    """Validate the current output directory.
+
+    Prints an `OUTPUT_DIR` JSON event (for IPC) with `ok`, `free_bytes` and
+    structured `errors`.
+    """
+    from tpi_redes.services.output_dir import validate_output_dir
+
+    check = validate_output_dir(get_default_output_dir(create=False))
+    _print_output_dir(check.to_dict())
+
+
+@output_dir.command("set")
+@click.argument("path", type=click.Path(path_type=Path))
+@click.option("--check-only", is_flag=True, help="Validate PATH without saving it")
+def output_dir_set(path: Path, check_only: bool):
+    """Validate PATH and save it as the default output directory.
+
+    The directory must exist, be writable and have free space. The result is
+    printed as an `OUTPUT_DIR` JSON event (for IPC); PATH is saved only if it
+    passes. `TPI_REDES_SAVE_DIR` still takes precedence over the saved value.
+    """
+    from tpi_redes.services.output_dir import save_output_dir, validate_output_dir
+
+    check = validate_output_dir(path)
+    if check.ok and not check_only:
+        save_output_dir(Path(check.path))
+    _print_output_dir({**check.to_dict(), "saved": check.ok and not check_only})
+
+
+def _print_output_dir(result: dict[str, Any]):
+    """Emit an `OUTPUT_DIR` event and a summary line.
+
+    Args:
+        result: The validation result fields.
+
+    Returns:
+        None: No return value.
+    """
+    print(json.dumps({"type": "OUTPUT_DIR", **result}), flush=True)
+    if result["ok"]:
+        console.print(f"[green]{result['path']} is usable.[/green]")
+    for error in result["errors"]:
+        console.print(f"[red]{error['code']}:[/red] {error['message']}")
+
+
+@cli.group()
+def backup():
+    """Back up or restore settings, presets and the received index."""
+
+
+def _backup_items(index: Path | None) -> dict[str, Path]:
+    """Return where each backed-up item lives on this machine.
+
+    Args:
+        index: The received index, if one is used.
+
+    Returns:
+        dict[str, Path]: Location per item name.
+    """
+    items = {"settings": SETTINGS_PATH, "presets": PRESETS_PATH}
+    if index:
+        items["received_index"] = index
+    return items
+
+
+_backup_index_option = click.option(
+    "--index",
+    default=RECEIVED_INDEX_PATH or None,
+    type=click.Path(dir_okay=False, path_type=Path),
+    help="Received index to include (default: TPI_REDES_RECEIVED_INDEX)",
+)
+
+
+@backup.command("create")
+@click.argument("archive", type=click.Path(dir_okay=False, path_type=Path))
+@_backup_index_option
+def backup_create(archive: Path, index: Path | None):
+    """Write the receiver's state into the zip ARCHIVE.
+
+    Prints a `BACKUP` JSON event (for IPC) listing the items included.
+    """
+    from tpi_redes.services.backup import create_backup
+
+    result = create_backup(archive, _backup_items(index))
+    print(json.dumps({"type": "BACKUP", "status": "created", **result}), flush=True)
+    console.print(
+        f"[green]Backed up {', '.join(result['items']) or 'nothing'} "
+        f"to {archive}.[/green]"
+    )
+
+
+@backup.command("restore")
+@click.argument("archive", type=click.Path(exists=True, dir_okay=False, path_type=Path))
+@_backup_index_option
+@click.option(
+    "--overwrite",
+    is_flag=True,
+    help="Replace local files instead of merging (newer local entries are lost)",
+)
+def backup_restore(archive: Path, index: Path | None, overwrite: bool):
+    """Restore the state saved in ARCHIVE.
+
+    By default the backup is merged: local settings and presets win over the
+    backed-up ones and only unknown received index entries are added. Prints
+    a `BACKUP` JSON event (for IPC) with the action taken per item.
+    """
+    from tpi_redes.services.backup import restore_backup
+
+    try:
+        result = restore_backup(archive, _backup_items(index), overwrite)
+    except ValueError as e:
+        raise click.ClickException(str(e)) from e
+    print(json.dumps({"type": "BACKUP", "status": "restored", **result}), flush=True)
+    if result["app_version"] != __version__:
+        console.print(
+            f"[yellow]Backup written by tpi-redes {result['app_version']}, "
+            f"restored with {__version__}.[/yellow]"
+        )
+    for name, item in result["items"].items():
+        console.print(f"{name}: {item['action']} ({item['added']} entries added)")
+
+
+@cli.group("config")
+def config_group():
+    """Inspect the configuration resolved from defaults and environment."""
+
+
+@config_group.command("show")
+@click.option(
+    "--tune",
+    type=click.Choice(sorted(TUNING_PROFILES)),
+    default=None,
+    help="Show the send-file values this tuning profile resolves to",
+)
+@click.option(
+    "--protocol",
+    type=click.Choice(ENABLED_PROTOCOLS),
+    default="tcp",
+    help="Protocol whose send-file values are shown",
+)
+def config_show(tune: str | None, protocol: str):
+    """Print the effective settings.
+
+    Prints a `CONFIG` JSON event (for IPC) with the global settings and the
+    send-file values for PROTOCOL, then a table.
+    """
+    from rich.table import Table
+
+    from tpi_redes import config
+
+    send: dict[str, Any] = {"chunk_size": CHUNK_SIZE, "socket_buffer": None}
+    if protocol == "tcp":
+        send |= {"ack_timeout": ACK_TIMEOUT, "restart_on_change": 0}
+    else:
+        send |= {"pacing_rate": UDP_PACING_RATE, "fec": None}
+    if tune:
+        send |= TUNING_PROFILES[tune][protocol]
+    settings = {
+        "host": config.DEFAULT_HOST,
+        "port": config.DEFAULT_SERVER_PORT,
+        "save_dir": str(get_default_output_dir(create=False)),
+        "protocols": ENABLED_PROTOCOLS,
+        "readahead_bytes": config.READAHEAD_BUDGET,
+        "buffer_pool_buffers": config.BUFFER_POOL_BUFFERS,
+        "bundle_threshold": config.BUNDLE_THRESHOLD,
+        "write_buffer_bytes": config.WRITE_BUFFER_BYTES,
+        "fsync": config.FSYNC_POLICY,
+        "validators": config.POST_VALIDATORS,
+        "received_index": config.RECEIVED_INDEX_PATH or None,
+        "udp_session_timeout": config.UDP_SESSION_TIMEOUT,
+        "ping_timeout": PING_TIMEOUT,
+    }
+    print(
+        json.dumps(
+            {
+                "type": "CONFIG",
+                "settings": settings,
+                "send": {"protocol": protocol, "tune": tune, **send},
+                "tuning_profiles": sorted(TUNING_PROFILES),
+            }
+        ),
+        flush=True,
+    )
+
+    table = Table(title=f"Configuration ({protocol}, tune: {tune or 'none'})")
+    table.add_column("Setting", style="cyan")
+    table.add_column("Value", style="green")
+    for key, value in settings.items():
+        table.add_row(key, str(value))
+    for key, value in send.items():
+        table.add_row(f"send-file {key}", str(value))
+    console.print(table)
+
+
 @cli.command()
 def list_interfaces():
     """List available network interfaces using Scapy."""
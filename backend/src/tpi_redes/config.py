@@ -1,5 +1,6 @@
 """Centralized configuration constants for the application."""
 
+import json
 import os
 from pathlib import Path
 
@@ -10,13 +11,180 @@ DEFAULT_PROXY_PORT = int(os.getenv("TPI_REDES_PROXY_PORT", "8081"))
 _DEFAULT_DATA_DIR = Path(
     os.getenv("TPI_REDES_HOME", str(Path.home() / ".tpi-redes"))
 )
-DEFAULT_SAVE_DIR = os.getenv(
-    "TPI_REDES_SAVE_DIR", str(_DEFAULT_DATA_DIR / "received_files")
+SETTINGS_PATH = Path(
+    os.getenv("TPI_REDES_SETTINGS", str(_DEFAULT_DATA_DIR / "settings.json"))
 )
 
+
+def _saved_setting(key: str) -> str | None:
+    """Return a value saved in the settings file, if present and valid.
+
+    Args:
+        key: Setting name, e.g. `output_dir`.
+
+    Returns:
+        str | None: The saved value, or None if unset or unreadable.
+    """
+    try:
+        data = json.loads(SETTINGS_PATH.read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return None
+    value = data.get(key) if isinstance(data, dict) else None
+    return value if isinstance(value, str) and value else None
+
+
+DEFAULT_SAVE_DIR = (
+    os.getenv("TPI_REDES_SAVE_DIR")
+    or _saved_setting("output_dir")
+    or str(_DEFAULT_DATA_DIR / "received_files")
+)
+PRESETS_PATH = Path(
+    os.getenv("TPI_REDES_PRESETS", str(_DEFAULT_DATA_DIR / "presets.json"))
+)
+CHECKSUM_CACHE_PATH = Path(
+    os.getenv(
+        "TPI_REDES_CHECKSUM_CACHE", str(_DEFAULT_DATA_DIR / "checksum_cache.json")
+    )
+)
+CHECKSUM_CACHE_MAX_ENTRIES = 1000
+OUTPUT_DIR_MODE = 0o700
+OUTPUT_DIR_MIN_FREE_BYTES = 100 * 1024 * 1024
+STAGING_PREFIX = ".tpi-staging-"
+
 CHUNK_SIZE = 4096
+READAHEAD_BUDGET = int(os.getenv("TPI_REDES_READAHEAD_BYTES", str(16 * 1024 * 1024)))
+# Chunk buffers kept for reuse per transfer; 0 sizes the pool to the read-ahead.
+BUFFER_POOL_BUFFERS = int(os.getenv("TPI_REDES_BUFFER_POOL_BUFFERS", "0"))
+WRITE_BUFFER_BYTES = int(os.getenv("TPI_REDES_WRITE_BUFFER_BYTES", str(1024 * 1024)))
+# TCP files up to this size are sent together in bundles; 0 disables bundling.
+BUNDLE_THRESHOLD = int(os.getenv("TPI_REDES_BUNDLE_THRESHOLD", "0"))
+BUNDLE_MAX_BYTES = 4 * 1024 * 1024
+FSYNC_POLICY = os.getenv("TPI_REDES_FSYNC", "never")
+POST_VALIDATORS = os.getenv("TPI_REDES_VALIDATORS", "")
 DISCOVERY_BUFFER_SIZE = 1024
 UDP_PAYLOAD_SIZE = 4096
 MAX_UDP_PACKET_SIZE = 65535
 
 PROGRESS_REPORT_INTERVAL_BYTES = 1024 * 100
+# Files smaller than this are too quick to tell a slow disk from noise.
+SLOW_DISK_MIN_BYTES = 1024 * 1024
+
+RECEIVER_STATUS_TIMEOUT = 0.5
+ABORT_DRAIN_TIMEOUT = 2.0
+PEER_PREVIEW_BYTES = 64
+PING_TIMEOUT = 2.0
+PROBE_BYTES = 256 * 1024
+PROBE_MAX_BYTES = 4 * 1024 * 1024
+BATCH_PREVIEW_MAX_BYTES = 1024 * 1024
+ACK_TIMEOUT = 30.0
+SLA_MIN_SAMPLE_SECONDS = 1.0
+SPEED_SAMPLE_SECONDS = 0.25
+SPEED_SMOOTHING = 0.3
+SPEED_ALERT_SECONDS = 5.0
+SOURCE_CHECK_INTERVAL = 0.5
+ERROR_EVENT_INTERVAL = 1.0
+MIRROR_RETRIES = 3
+MIRROR_RETRY_DELAY = 2.0
+
+UDP_DISABLED = os.getenv("TPI_REDES_DISABLE_UDP", "").lower() in ("1", "true", "yes")
+ENABLED_PROTOCOLS = ["tcp"] if UDP_DISABLED else ["tcp", "udp"]
+
+AUDIT_LOG_PATH = os.getenv("TPI_REDES_AUDIT_LOG", "")
+RECEIVED_INDEX_PATH = os.getenv("TPI_REDES_RECEIVED_INDEX", "")
+
+UDP_PACING_RATE = int(os.getenv("TPI_REDES_UDP_PACING_RATE", str(10 * 1024 * 1024)))
+UDP_SESSION_TIMEOUT = float(os.getenv("TPI_REDES_UDP_SESSION_TIMEOUT", "30"))
+UDP_REAP_INTERVAL = 1.0
+
+# Built-in `send-file --tune` profiles; keys are send-file options, per protocol.
+TUNING_PROFILES: dict[str, dict[str, dict[str, int | float]]] = {
+    "lan-fast": {
+        "tcp": {
+            "chunk_size": 65536,
+            "socket_buffer": 4 * 1024 * 1024,
+            "ack_timeout": 10.0,
+        },
+        "udp": {
+            "chunk_size": 8192,
+            "socket_buffer": 4 * 1024 * 1024,
+            "pacing_rate": 100 * 1024 * 1024,
+        },
+    },
+    "wan-high-latency": {
+        "tcp": {
+            "chunk_size": 65536,
+            "socket_buffer": 16 * 1024 * 1024,
+            "ack_timeout": 120.0,
+            "restart_on_change": 2,
+        },
+        "udp": {
+            "chunk_size": 1200,
+            "socket_buffer": 4 * 1024 * 1024,
+            "pacing_rate": 2 * 1024 * 1024,
+        },
+    },
+    "lossy-wifi": {
+        "tcp": {
+            "chunk_size": 16384,
+            "socket_buffer": 1024 * 1024,
+            "ack_timeout": 60.0,
+            "restart_on_change": 3,
+        },
+        "udp": {
+            "chunk_size": 1024,
+            "socket_buffer": 1024 * 1024,
+            "pacing_rate": 1024 * 1024,
+            "fec": 8,
+        },
+    },
+}
+
+# Hidden fault-injection knobs for reproducing bug reports (ignored when frozen).
+DEBUG_DELAY_PER_CHUNK_MS = int(os.getenv("TPI_REDES_DEBUG_DELAY_PER_CHUNK_MS", "0"))
+DEBUG_DROP_EVERY_NTH_PACKET = int(
+    os.getenv("TPI_REDES_DEBUG_DROP_EVERY_NTH_PACKET", "0")
+)
+DEBUG_CORRUPT_EVERY_NTH_CHUNK = int(
+    os.getenv("TPI_REDES_DEBUG_CORRUPT_EVERY_NTH_CHUNK", "0")
+)
+
+KNOCK_SECRET = os.getenv("TPI_REDES_KNOCK_SECRET", "")
+KNOCK_PORT = int(os.getenv("TPI_REDES_KNOCK_PORT", "37021"))
+KNOCK_WINDOW = 30.0
+KNOCK_MAX_SKEW = 30.0
+KNOCK_SETTLE_DELAY = 0.2
+
+
+def get_default_output_dir(peer_ip: str | None = None, create: bool = True) -> Path:
+    """Resolve the directory where received files are stored, creating it.
+
+    Honors `TPI_REDES_SAVE_DIR`, then the directory saved with `output-dir set`,
+    falling back to `received_files` inside the data directory. New
+    directories are created readable only by the owner.
+
+    Args:
+        peer_ip: Optional sender address; when given, a per-peer subdirectory
+            of the default directory is returned.
+        create: Whether to create the directory if it does not exist.
+
+    Returns:
+        Path: Absolute path of the output directory.
+    """
+    output_dir = Path(DEFAULT_SAVE_DIR).expanduser().resolve()
+    if peer_ip:
+        output_dir = output_dir / peer_dir_name(peer_ip)
+    if create:
+        output_dir.mkdir(mode=OUTPUT_DIR_MODE, parents=True, exist_ok=True)
+    return output_dir
+
+
+def peer_dir_name(peer_ip: str) -> str:
+    """Return a filesystem-safe subdirectory name for a peer address.
+
+    Args:
+        peer_ip: IPv4 or IPv6 address of the peer.
+
+    Returns:
+        str: The address with characters invalid in paths replaced.
+    """
+    return peer_ip.replace(":", "_")
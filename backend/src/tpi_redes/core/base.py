@@ -1,4 +1,24 @@
+import contextlib
+import logging
+import shutil
+import time
 from abc import ABC, abstractmethod
+from collections.abc import Iterator
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import OUTPUT_DIR_MODE, peer_dir_name
+from tpi_redes.observability.audit_log import AuditLog
+from tpi_redes.observability.peer_stats import PeerStats
+from tpi_redes.services.knock import KnockGate
+from tpi_redes.services.mirror import Mirror
+from tpi_redes.services.pause_control import PauseControl
+from tpi_redes.services.sleep_inhibitor import SleepInhibitor
+from tpi_redes.transfer.post_validation import validate_file
+from tpi_redes.transfer.received_index import ReceivedIndex
+from tpi_redes.transfer.routing import OutputRouter, RouteRule
+
+logger = logging.getLogger("tpi-redes")
 
 
 class BaseServer(ABC):
@@ -8,15 +28,58 @@ class BaseServer(ABC):
         host (str): IP address to bind the server to (e.g., "0.0.0.0").
         port (int): Port number to listen on.
         save_dir (str): Directory path where received files will be stored.
+        per_peer_dirs (bool): Store each sender's files in its own subdirectory.
+        audit (bool): Verify incoming files but discard their content.
+        sleep_inhibitor (SleepInhibitor | None): Keeps the host awake while
+            transfers are in progress.
+        audit_log (AuditLog | None): Hash-chained journal of received files.
+        router (OutputRouter | None): Picks the output directory by sender.
+        knock_gate (KnockGate | None): Admits only senders that knocked first.
+        active_transfers (int): Connections or sessions currently receiving.
+        max_files (int | None): Stop once this many files completed; None
+            keeps the server running until interrupted.
+        window_ends (float | None): `time.monotonic()` value at which the
+            server stops accepting transfers; None for no time limit.
+        outcomes (list[dict[str, Any]]): Every file this server handled, in
+            order, with its size, result and validation results.
+        validators (tuple[str, ...]): Post-transfer validators run on every
+            verified file, see `tpi_redes.transfer.post_validation`.
+        received_index (ReceivedIndex | None): Records where each verified
+            file was stored, for later lookup by checksum or name.
+        pause (PauseControl | None): Stops reading content while paused; only
+            the TCP server honors it, UDP datagrams cannot be held back.
+        mirror (Mirror | None): Copies every verified file to a second
+            directory in the background.
+        max_rate (int | None): Ingress limit in bytes per second for each
+            connection; only the TCP servers honor it, since slowing reads
+            only slows a sender down when flow control pushes back.
+        peer_stats (PeerStats): Connection and byte counters per sender IP.
     """
 
-    def __init__(self, host: str, port: int, save_dir: str):
+    def __init__(
+        self,
+        host: str,
+        port: int,
+        save_dir: str,
+        per_peer_dirs: bool = False,
+        audit: bool = False,
+        sleep_inhibitor: SleepInhibitor | None = None,
+        audit_log: AuditLog | None = None,
+        routes: list[RouteRule] | None = None,
+        knock_gate: KnockGate | None = None,
+    ):
         """Initialize the server configuration.
 
         Args:
             host: IP address to bind to.
             port: Port number to use.
             save_dir: Path to storage directory.
+            per_peer_dirs: Whether to group received files by sender IP.
+            audit: Whether to run in read-only audit mode.
+            sleep_inhibitor: Optional inhibitor held during active transfers.
+            audit_log: Optional journal recording every received file.
+            routes: Optional rules mapping sender addresses to directories.
+            knock_gate: Optional authorization gate for incoming senders.
 
         Returns:
             None
@@ -24,6 +87,215 @@ class BaseServer(ABC):
         self.host = host
         self.port = port
         self.save_dir = save_dir
+        self.per_peer_dirs = per_peer_dirs
+        self.audit = audit
+        self.sleep_inhibitor = sleep_inhibitor
+        self.audit_log = audit_log
+        self.router = OutputRouter(routes, Path(save_dir)) if routes else None
+        self.knock_gate = knock_gate
+        self.active_transfers = 0
+        self.max_files: int | None = None
+        self.window_ends: float | None = None
+        self.outcomes: list[dict[str, Any]] = []
+        self.validators: tuple[str, ...] = ()
+        self.received_index: ReceivedIndex | None = None
+        self.pause: PauseControl | None = None
+        self.mirror: Mirror | None = None
+        self.max_rate: int | None = None
+        self.peer_stats = PeerStats()
+        # Verified files of an atomic batch still in staging, indexed and
+        # mirrored only once the batch commits; None outside atomic batches.
+        self._staged: list[tuple[tuple[str, int], str, int, str]] | None = None
+
+    def output_dir_for(self, addr: tuple[str, int]) -> Path:
+        """Return the directory where files from a given sender are stored.
+
+        Routing rules, if any, are applied first. The directory is created
+        (owner-only permissions) if it does not exist, except in audit mode,
+        which never touches the disk.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+
+        Returns:
+            Path: Directory for the sender's files.
+        """
+        output_dir = Path(self.save_dir)
+        if self.router:
+            output_dir, _rule = self.router.resolve(addr[0])
+        if self.per_peer_dirs:
+            output_dir = output_dir / peer_dir_name(addr[0])
+        if not self.audit:
+            output_dir.mkdir(mode=OUTPUT_DIR_MODE, parents=True, exist_ok=True)
+        return output_dir
+
+    def is_authorized(self, addr: tuple[str, int]) -> bool:
+        """Return whether a sender may transfer, given the knock gate.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+
+        Returns:
+            bool: True if no gate is configured or the sender has knocked.
+        """
+        return self.knock_gate is None or self.knock_gate.is_allowed(addr[0])
+
+    def matched_route(self, addr: tuple[str, int]) -> str | None:
+        """Return the routing rule that applies to a sender, for events.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+
+        Returns:
+            str | None: The matching rule's pattern, or None if no rule matched.
+        """
+        if not self.router:
+            return None
+        _output_dir, rule = self.router.resolve(addr[0])
+        return rule.match if rule else None
+
+    def capacity(self) -> dict[str, Any]:
+        """Describe what this receiver can currently accept, for discovery.
+
+        Returns:
+            dict[str, Any]: Free disk space in the save directory (None if
+            unknown), whether senders must knock first, active transfers and
+            whether the receiver is in read-only audit mode.
+        """
+        try:
+            free_bytes: int | None = shutil.disk_usage(self.save_dir).free
+        except OSError:
+            free_bytes = None
+        return {
+            "free_bytes": free_bytes,
+            "auth_required": self.knock_gate is not None,
+            "active_transfers": self.active_transfers,
+            "audit": self.audit,
+        }
+
+    @contextlib.contextmanager
+    def inhibit_sleep(self) -> Iterator[None]:
+        """Hold the sleep inhibitor, if configured, for the enclosed transfer.
+
+        Returns:
+            Iterator[None]: Context manager yielding once.
+        """
+        if not self.sleep_inhibitor:
+            yield
+            return
+        with self.sleep_inhibitor:
+            yield
+
+    @property
+    def files_completed(self) -> int:
+        """Number of files received and verified (or stored raw) so far."""
+        return sum(o["result"] in ("verified", "raw") for o in self.outcomes)
+
+    @property
+    def limit_reached(self) -> bool:
+        """Whether the server has received the files it was asked to wait for."""
+        return self.max_files is not None and self.files_completed >= self.max_files
+
+    def window_remaining(self) -> float | None:
+        """Return the seconds left in the receive window.
+
+        Returns:
+            float | None: Seconds until `window_ends` (zero or negative once
+            it has passed), or None if the server has no time limit.
+        """
+        if self.window_ends is None:
+            return None
+        return self.window_ends - time.monotonic()
+
+    def validate(self, path: Path, mime: str | None = None) -> list[dict[str, Any]]:
+        """Run the configured validators on a stored, verified file.
+
+        Args:
+            path: Where the file was stored.
+            mime: Content type announced by the sender; sniffed if None.
+
+        Returns:
+            list[dict[str, Any]]: One result per validator that applied.
+        """
+        results = [r.to_dict() for r in validate_file(path, self.validators, mime)]
+        for r in results:
+            if not r["ok"]:
+                logger.warning(f"'{path.name}' failed {r['validator']}: {r['error']}")
+        return results
+
+    def record_audit(
+        self,
+        addr: tuple[str, int],
+        filename: str,
+        size: int,
+        sha256: str,
+        result: str,
+        validation: list[dict[str, Any]] | None = None,
+    ):
+        """Record a file's outcome in the audit log, index and mirror.
+
+        Verified files of an atomic batch reach the index and mirror only
+        when the batch commits, see `store_verified`.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+            filename: Name of the file.
+            size: Bytes received.
+            sha256: Checksum of the received content.
+            result: Outcome, e.g. "verified", "mismatch" or "aborted:<reason>".
+            validation: Results of post-transfer validators, if any ran.
+
+        Returns:
+            None: No return value.
+        """
+        peer = f"{addr[0]}:{addr[1]}"
+        self.peer_stats.received(addr[0], size)
+        self.outcomes.append(
+            {
+                "filename": filename,
+                "peer": peer,
+                "size": size,
+                "result": result,
+                "validation": validation or [],
+            }
+        )
+        if self.audit_log:
+            self.audit_log.record(
+                "received", peer, filename, size, sha256, result, validation
+            )
+        if result != "verified" or self.audit:
+            return
+        if self._staged is not None:
+            self._staged.append((addr, filename, size, sha256))
+            return
+        self.store_verified(addr, filename, size, sha256)
+
+    def store_verified(
+        self, addr: tuple[str, int], filename: str, size: int, sha256: str
+    ):
+        """Add a verified file, at its final location, to the index and mirror.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+            filename: Name of the file.
+            size: Bytes received.
+            sha256: Checksum of the received content.
+
+        Returns:
+            None: No return value.
+        """
+        peer = f"{addr[0]}:{addr[1]}"
+        stored_path = self.output_dir_for(addr) / filename
+        if self.received_index:
+            self.received_index.add(stored_path, filename, size, sha256, peer)
+        if self.mirror:
+            # Routed senders may store outside save_dir; mirror those flat.
+            relative = (
+                stored_path.relative_to(self.save_dir)
+                if stored_path.is_relative_to(self.save_dir)
+                else Path(filename)
+            )
+            self.mirror.submit(stored_path, relative)
 
     @abstractmethod
     def start(self):
@@ -0,0 +1,82 @@
+from pathlib import Path
+
+from tpi_redes.core.protocol import AbortReason
+
+
+class TransferAbortedError(Exception):
+    """Raised when a transfer is aborted, locally or by the remote peer.
+
+    Attributes:
+        reason (str): An `AbortReason` value describing the cause.
+        initiator (str): `"local"` or `"peer"`, whichever side aborted.
+        detail (str): Optional human-readable detail.
+    """
+
+    def __init__(self, reason: str, initiator: str, detail: str = ""):
+        self.reason = reason
+        self.initiator = initiator
+        self.detail = detail
+        message = f"Transfer aborted by {initiator}: {reason}"
+        super().__init__(f"{message} ({detail})" if detail else message)
+
+
+class SourceModifiedError(TransferAbortedError):
+    """Raised when a source file changes while it is being sent.
+
+    Attributes:
+        path (Path): The file that changed.
+    """
+
+    def __init__(self, path: Path):
+        self.path = path
+        super().__init__(
+            AbortReason.SOURCE_MODIFIED, "local", f"{path.name} changed during send"
+        )
+
+
+class NotAFileTransferPeerError(TransferAbortedError):
+    """Raised when the remote end answers with something other than this protocol.
+
+    Typically the address points at another service, e.g. an HTTP server.
+
+    Attributes:
+        preview (bytes): The first bytes received, for diagnostics.
+    """
+
+    def __init__(self, preview: bytes):
+        self.preview = preview
+        super().__init__(
+            AbortReason.PROTOCOL_ERROR,
+            "peer",
+            f"not a tpi-redes receiver; it sent {preview!r}",
+        )
+
+
+class ChecksumMismatchError(Exception):
+    """Raised when the receiver confirms files that differ from the source.
+
+    Attributes:
+        filenames (list[str]): Names of the files that failed verification.
+    """
+
+    def __init__(self, filenames: list[str]):
+        self.filenames = filenames
+        super().__init__(
+            f"Receiver stored {len(filenames)} file(s) with a different checksum: "
+            + ", ".join(filenames)
+        )
+
+
+class ProtocolDisabledError(ValueError):
+    """Raised when a transport disabled by configuration is requested.
+
+    Attributes:
+        protocol (str): The disabled protocol, e.g. `"udp"`.
+    """
+
+    def __init__(self, protocol: str):
+        self.protocol = protocol
+        super().__init__(
+            f"{protocol.upper()} transport is disabled in this deployment "
+            "(TPI_REDES_DISABLE_UDP); use TCP instead."
+        )
@@ -0,0 +1,54 @@
+import socket
+from enum import IntEnum
+
+from tpi_redes.core.errors import (
+    ChecksumMismatchError,
+    NotAFileTransferPeerError,
+    ProtocolDisabledError,
+    TransferAbortedError,
+)
+from tpi_redes.core.protocol import AbortReason
+
+
+class ExitCode(IntEnum):
+    """Process exit status of the CLI, so scripts can branch on failures.
+
+    Values are part of the CLI's public interface and must not be reused.
+    """
+
+    SUCCESS = 0
+    FAILURE = 1
+    USAGE = 2
+    CONNECTION = 3
+    INTEGRITY = 4
+    CANCELLED = 5
+    TIMEOUT = 6
+    ABORTED = 7
+
+
+def exit_code_for(exc: BaseException) -> ExitCode:
+    """Map an exception that ended a command to its exit status.
+
+    Args:
+        exc: The exception raised by the command.
+
+    Returns:
+        ExitCode: The status the process should exit with.
+    """
+    if isinstance(exc, NotAFileTransferPeerError):
+        return ExitCode.CONNECTION
+    if isinstance(exc, TransferAbortedError):
+        if exc.reason == AbortReason.CANCELLED:
+            return ExitCode.CANCELLED
+        return ExitCode.ABORTED
+    if isinstance(exc, ChecksumMismatchError):
+        return ExitCode.INTEGRITY
+    if isinstance(exc, KeyboardInterrupt):
+        return ExitCode.CANCELLED
+    if isinstance(exc, TimeoutError):
+        return ExitCode.TIMEOUT
+    if isinstance(exc, ConnectionError | socket.gaierror):
+        return ExitCode.CONNECTION
+    if isinstance(exc, ProtocolDisabledError | FileNotFoundError | ValueError):
+        return ExitCode.USAGE
+    return ExitCode.FAILURE
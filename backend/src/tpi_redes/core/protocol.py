@@ -1,8 +1,24 @@
+import hashlib
 import struct
 from dataclasses import dataclass
+from enum import StrEnum
 from typing import ClassVar
 
 
+class AbortReason(StrEnum):
+    """Why a transfer was aborted, shared by both peers."""
+
+    CANCELLED = "cancelled"
+    SOURCE_ERROR = "source_error"
+    SOURCE_MODIFIED = "source_modified"
+    DISK_FULL = "disk_full"
+    WRITE_ERROR = "write_error"
+    PROTOCOL_ERROR = "protocol_error"
+    CONNECTION_LOST = "connection_lost"
+    STALLED = "stalled"
+    INTERNAL_ERROR = "internal_error"
+
+
 @dataclass
 class Header:
     """Represents the fixed-size protocol header.
@@ -20,6 +36,92 @@ class Header:
     hash_len: int
 
 
+@dataclass
+class ReceiverStatus:
+    """Progress report sent by a UDP receiver back to the sender.
+
+    Attributes:
+        bytes_written (int): Bytes of the current file written to disk so far.
+        datagrams_received (int): Content datagrams accepted for the current file.
+        repaired (int): Lost datagrams rebuilt from FEC parity so far.
+    """
+
+    bytes_written: int
+    datagrams_received: int
+    repaired: int = 0
+
+
+@dataclass
+class FecFrame:
+    """One content datagram of a UDP file sent with forward error correction.
+
+    Attributes:
+        op_code (bytes): b'G' for a data frame, b'X' for a parity frame.
+        group (int): Parity group the frame belongs to.
+        index (int): Position in the group for data frames; number of data
+            frames in the group for parity frames.
+        body (bytes): A data block, or the XOR of the group's data blocks.
+    """
+
+    op_code: bytes
+    group: int
+    index: int
+    body: bytes
+
+
+@dataclass
+class StreamChunk:
+    """Framing of one content chunk of a stream of unknown length.
+
+    Attributes:
+        offset (int): Bytes of the stream sent before this chunk.
+        length (int): Number of content bytes following the frame.
+    """
+
+    offset: int
+    length: int
+
+
+@dataclass
+class FileAck:
+    """A receiver's confirmation that a file was stored, sent over TCP.
+
+    Attributes:
+        bytes_received (int): Bytes of the file the receiver stored.
+        sha256 (str): SHA-256 hex digest of the stored content.
+    """
+
+    bytes_received: int
+    sha256: str
+
+
+@dataclass
+class Preamble:
+    """The banner each peer sends first on a TCP connection.
+
+    Attributes:
+        protocol_version (int): Version of the wire protocol the peer speaks.
+        version (str): The peer's tpi-redes version, possibly truncated.
+    """
+
+    protocol_version: int
+    version: str
+
+
+@dataclass
+class AbortMessage:
+    """An abort notice received from the remote peer.
+
+    Attributes:
+        reason (str): An `AbortReason` value, or an unknown reason string
+            sent by a newer peer.
+        message (str): Optional human-readable detail.
+    """
+
+    reason: str
+    message: str
+
+
 class ProtocolHandler:
     """Handles packing and unpacking of the custom binary protocol.
 
@@ -30,11 +132,171 @@ class ProtocolHandler:
         Q       : File Size (unsigned long long, 8 bytes)
         H       : Hash Length (unsigned short, 2 bytes)
         3s      : Reserved Padding (3 bytes)
+
+    Abort messages reuse the header with OpCode b'A': the name field carries
+    the `AbortReason` and the hash field an optional detail message. They may
+    be sent by either peer at a message boundary before closing.
+
+    A batch may start with a manifest (OpCode b'M'), framed like a file whose
+    content is the `SHA256SUMS` listing of the files that follow.
+
+    A batch of several files may also start with a preview (OpCode b'L'): a
+    header with empty name and hash whose size field holds the length of the
+    listing that follows, one `<size> <name>` line per file in sending order.
+    It lets the receiver check free space for the whole batch and report
+    aggregate progress.
+
+    Each file may be preceded by its content type (OpCode b'Y'): a header whose
+    name field carries the MIME type the sender detected, with size and hash
+    left empty. It applies to the next file only.
+
+    Small files may travel together in a bundle (OpCode b'U'): a header with
+    an empty name whose size field holds the length of the payload and whose
+    hash field holds its SHA-256. The payload is the files' own messages back
+    to back, content types included; the receiver verifies it as a whole and
+    then handles those messages as if they had arrived one by one.
+
+    Liveness probes also reuse the header: a ping is a bare header with OpCode
+    b'P', answered by a pong with OpCode b'O' whose name field carries the
+    receiver's version string.
+
+    A bandwidth probe (OpCode b'B') carries throwaway bytes, as many as its
+    size field says; the receiver discards them and replies with an
+    acknowledgement (OpCode b'K') holding the count and an empty hash.
+
+    A file sent with OpCode b'C' instead of b'F' asks the receiver to confirm
+    it: once the content is stored the receiver replies with an acknowledgement
+    (OpCode b'K') whose size field holds the bytes received and whose hash
+    field holds the SHA-256 it computed. The sender may then start the next
+    file on the same connection.
+
+    A file sent with OpCode b'H' has an empty hash field: the sender hashes
+    the content while sending it and follows it with a trailer (OpCode b'T',
+    as for streams) holding the size and the SHA-256, so huge files are read
+    only once.
+
+    Sources of unknown length (e.g. stdin) are sent as a stream: a header with
+    OpCode b'S' carries the name with size and hash left empty, then each
+    chunk is framed with its running offset (see below), and a trailer with
+    OpCode b'T' closes the stream with the final size in the size field and
+    the SHA-256 in the hash field.
+
+    Stream Chunk Format (13 bytes, Big-endian), followed by the chunk bytes:
+        c       : OpCode b'D' (1 byte)
+        Q       : Offset (unsigned long long, 8 bytes)
+        I       : Chunk Length (unsigned int, 4 bytes)
+
+    From protocol version 5 the content of b'F', b'C' and b'H' files, in
+    bundles too, is framed the same way, so the sender can abort mid-file:
+    an abort message then takes the place of the next chunk.
+
+    Receivers only ever send b'K', b'A', b'O' and b'R' messages, so a sender
+    reading anything else is connected to some other service.
+
+    A TCP sender opens the connection with a preamble and the receiver answers
+    with its own, or with an abort if the sender's protocol version is older
+    than `MIN_PROTOCOL_VERSION`. The receiver's answer carries the lower of
+    both versions, which the connection then speaks: messages added in a
+    later version (see `PROTOCOL_FEATURES`) are not sent to an older peer.
+    Receivers still accept senders that start directly with a message, and
+    close connections that start with anything else.
+
+    Preamble Format (16 bytes, Big-endian), sent in both directions:
+        4s      : Magic b'\\x89TPR' (4 bytes)
+        B       : Protocol Version (unsigned char, 1 byte)
+        11s     : tpi-redes Version, UTF-8, NUL-padded (11 bytes)
+
+    Receiver Status Format (13 or 17 bytes, Big-endian), UDP receiver -> sender:
+        c       : OpCode b'R' (1 byte)
+        Q       : Bytes Written (unsigned long long, 8 bytes)
+        I       : Datagrams Received (unsigned int, 4 bytes)
+        I       : Repaired Datagrams (unsigned int, 4 bytes; FEC files only)
+
+    A UDP file announced with OpCode b'E' instead of b'F' is sent with
+    forward error correction: its content datagrams are FEC frames, and after
+    every group of data frames comes a parity frame that lets the receiver
+    rebuild one lost data frame of that group (see `tpi_redes.transfer.fec`).
+
+    FEC Frame Format (6 bytes, Big-endian), followed by the body:
+        c       : OpCode b'G' (data) or b'X' (parity) (1 byte)
+        I       : Group (unsigned int, 4 bytes)
+        B       : Index in Group, or Data Frames in Group for parity (1 byte)
+
+    FEC Data Block Format (12 bytes, Big-endian), followed by the content:
+        Q       : Offset (unsigned long long, 8 bytes)
+        I       : Content Length (unsigned int, 4 bytes)
     """
 
     HEADER_FORMAT: ClassVar[str] = "!cHQH3s"
     HEADER_SIZE: ClassVar[int] = struct.calcsize(HEADER_FORMAT)
 
+    FILE_OP_CODE: ClassVar[bytes] = b"F"
+    CONFIRMED_FILE_OP_CODE: ClassVar[bytes] = b"C"
+    TRAILED_FILE_OP_CODE: ClassVar[bytes] = b"H"
+    ACK_OP_CODE: ClassVar[bytes] = b"K"
+    STREAM_OP_CODE: ClassVar[bytes] = b"S"
+    TRAILER_OP_CODE: ClassVar[bytes] = b"T"
+
+    CHUNK_OP_CODE: ClassVar[bytes] = b"D"
+    CHUNK_FORMAT: ClassVar[str] = "!cQI"
+    CHUNK_HEADER_SIZE: ClassVar[int] = struct.calcsize(CHUNK_FORMAT)
+    MANIFEST_OP_CODE: ClassVar[bytes] = b"M"
+    ABORT_OP_CODE: ClassVar[bytes] = b"A"
+    PING_OP_CODE: ClassVar[bytes] = b"P"
+    PONG_OP_CODE: ClassVar[bytes] = b"O"
+    PROBE_OP_CODE: ClassVar[bytes] = b"B"
+    BATCH_OP_CODE: ClassVar[bytes] = b"L"
+    CONTENT_TYPE_OP_CODE: ClassVar[bytes] = b"Y"
+    BUNDLE_OP_CODE: ClassVar[bytes] = b"U"
+
+    STATUS_OP_CODE: ClassVar[bytes] = b"R"
+    STATUS_FORMAT: ClassVar[str] = "!cQI"
+    STATUS_SIZE: ClassVar[int] = struct.calcsize(STATUS_FORMAT)
+    FEC_STATUS_FORMAT: ClassVar[str] = "!cQII"
+    FEC_STATUS_SIZE: ClassVar[int] = struct.calcsize(FEC_STATUS_FORMAT)
+
+    FEC_FILE_OP_CODE: ClassVar[bytes] = b"E"
+    FEC_DATA_OP_CODE: ClassVar[bytes] = b"G"
+    FEC_PARITY_OP_CODE: ClassVar[bytes] = b"X"
+    FEC_FORMAT: ClassVar[str] = "!cIB"
+    FEC_HEADER_SIZE: ClassVar[int] = struct.calcsize(FEC_FORMAT)
+    FEC_BLOCK_FORMAT: ClassVar[str] = "!QI"
+    FEC_BLOCK_HEADER_SIZE: ClassVar[int] = struct.calcsize(FEC_BLOCK_FORMAT)
+
+    PREAMBLE_MAGIC: ClassVar[bytes] = b"\x89TPR"
+    PREAMBLE_FORMAT: ClassVar[str] = "!4sB11s"
+    PREAMBLE_SIZE: ClassVar[int] = struct.calcsize(PREAMBLE_FORMAT)
+    PROTOCOL_VERSION: ClassVar[int] = 5
+    MIN_PROTOCOL_VERSION: ClassVar[int] = 1
+    # Protocol version that introduced each optional message.
+    PROTOCOL_FEATURES: ClassVar[dict[str, int]] = {
+        "batch_preview": 2,
+        "content_type": 2,
+        "bundle": 3,
+        "trailing_hash": 4,
+        "framed_content": 5,
+    }
+
+    SENDER_OP_CODES: ClassVar[tuple[bytes, ...]] = (
+        FILE_OP_CODE,
+        CONFIRMED_FILE_OP_CODE,
+        TRAILED_FILE_OP_CODE,
+        MANIFEST_OP_CODE,
+        STREAM_OP_CODE,
+        ABORT_OP_CODE,
+        PING_OP_CODE,
+        PROBE_OP_CODE,
+        BATCH_OP_CODE,
+        CONTENT_TYPE_OP_CODE,
+        BUNDLE_OP_CODE,
+    )
+    RECEIVER_OP_CODES: ClassVar[tuple[bytes, ...]] = (
+        ACK_OP_CODE,
+        ABORT_OP_CODE,
+        PONG_OP_CODE,
+        STATUS_OP_CODE,
+    )
+
     @staticmethod
     def pack_header(
         op_code: bytes, filename: str, file_size: int, file_hash: str
@@ -90,3 +352,403 @@ class ProtocolHandler:
             file_size=unpacked[2],
             hash_len=unpacked[3],
         )
+
+    @staticmethod
+    def pack_abort(reason: AbortReason, message: str = "") -> bytes:
+        """Pack a complete abort message (header and payload).
+
+        Args:
+            reason: Why the transfer is being aborted.
+            message: Optional human-readable detail.
+
+        Returns:
+            bytes: The header followed by the reason and message bytes.
+        """
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.ABORT_OP_CODE, reason.value, 0, message
+        )
+        return header + reason.value.encode("utf-8") + message.encode("utf-8")
+
+    @staticmethod
+    def unpack_abort(reason_bytes: bytes, message_bytes: bytes) -> AbortMessage:
+        """Parse the payload of an abort message.
+
+        Args:
+            reason_bytes: The `name_len` bytes following the header.
+            message_bytes: The `hash_len` bytes following the reason.
+
+        Returns:
+            AbortMessage: The parsed abort notice.
+        """
+        return AbortMessage(
+            reason=reason_bytes.decode("utf-8", errors="replace"),
+            message=message_bytes.decode("utf-8", errors="replace"),
+        )
+
+    @staticmethod
+    def pack_preamble(version: str, protocol_version: int | None = None) -> bytes:
+        """Pack the banner that opens a TCP connection.
+
+        Args:
+            version: tpi-redes version of the local peer.
+            protocol_version: Protocol version to announce; defaults to
+                `PROTOCOL_VERSION`.
+
+        Returns:
+            bytes: The packed 16-byte preamble.
+        """
+        if protocol_version is None:
+            protocol_version = ProtocolHandler.PROTOCOL_VERSION
+        return struct.pack(
+            ProtocolHandler.PREAMBLE_FORMAT,
+            ProtocolHandler.PREAMBLE_MAGIC,
+            protocol_version,
+            version.encode("utf-8")[:11],
+        )
+
+    @staticmethod
+    def disabled_features(protocol_version: int) -> list[str]:
+        """List the optional messages a connection cannot use.
+
+        Args:
+            protocol_version: The version the connection speaks.
+
+        Returns:
+            list[str]: Names from `PROTOCOL_FEATURES` introduced later.
+        """
+        return [
+            name
+            for name, since in ProtocolHandler.PROTOCOL_FEATURES.items()
+            if protocol_version < since
+        ]
+
+    @staticmethod
+    def unpack_preamble(data: bytes) -> Preamble:
+        """Unpack the banner that opens a TCP connection.
+
+        Args:
+            data: The first 16 bytes received on the connection.
+
+        Returns:
+            Preamble: The peer's protocol and tpi-redes versions.
+
+        Raises:
+            ValueError: If the data is not a preamble.
+        """
+        if len(data) != ProtocolHandler.PREAMBLE_SIZE or not data.startswith(
+            ProtocolHandler.PREAMBLE_MAGIC
+        ):
+            raise ValueError(f"Not a tpi-redes preamble: {data!r}")
+
+        _magic, protocol_version, version = struct.unpack(
+            ProtocolHandler.PREAMBLE_FORMAT, data
+        )
+        return Preamble(
+            protocol_version=protocol_version,
+            version=version.rstrip(b"\x00").decode("utf-8", errors="replace"),
+        )
+
+    @staticmethod
+    def pack_ping() -> bytes:
+        """Pack a liveness probe.
+
+        Returns:
+            bytes: A header with the ping OpCode and no payload.
+        """
+        return ProtocolHandler.pack_header(ProtocolHandler.PING_OP_CODE, "", 0, "")
+
+    @staticmethod
+    def pack_probe(size: int) -> bytes:
+        """Pack the header of a bandwidth probe.
+
+        Args:
+            size: Number of throwaway bytes that follow the header.
+
+        Returns:
+            bytes: A header with the probe OpCode and the size.
+        """
+        return ProtocolHandler.pack_header(
+            ProtocolHandler.PROBE_OP_CODE, "", size, ""
+        )
+
+    @staticmethod
+    def pack_batch_preview(listing: bytes) -> bytes:
+        """Pack a batch preview (header and listing).
+
+        Args:
+            listing: The encoded `build_batch_preview` listing.
+
+        Returns:
+            bytes: A header with the batch OpCode, followed by the listing.
+        """
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.BATCH_OP_CODE, "", len(listing), ""
+        )
+        return header + listing
+
+    @staticmethod
+    def pack_content_type(mime: str) -> bytes:
+        """Pack the content type of the next file.
+
+        Args:
+            mime: MIME type detected by the sender, e.g. "image/png".
+
+        Returns:
+            bytes: A header with the content type OpCode, followed by the type.
+        """
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.CONTENT_TYPE_OP_CODE, mime, 0, ""
+        )
+        return header + mime.encode("utf-8")
+
+    @staticmethod
+    def pack_bundle(payload: bytes) -> bytes:
+        """Pack the header and hash announcing a bundle.
+
+        Args:
+            payload: The bundled file messages, sent right after.
+
+        Returns:
+            bytes: The packed header followed by the payload's SHA-256.
+        """
+        digest = hashlib.sha256(payload).hexdigest()
+        return (
+            ProtocolHandler.pack_header(
+                ProtocolHandler.BUNDLE_OP_CODE, "", len(payload), digest
+            )
+            + digest.encode("utf-8")
+        )
+
+    @staticmethod
+    def pack_pong(version: str) -> bytes:
+        """Pack the reply to a liveness probe.
+
+        Args:
+            version: Version string of the responding receiver.
+
+        Returns:
+            bytes: The header followed by the version bytes.
+        """
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.PONG_OP_CODE, version, 0, ""
+        )
+        return header + version.encode("utf-8")
+
+    @staticmethod
+    def pack_stream_start(filename: str) -> bytes:
+        """Pack the opening of a stream of unknown length.
+
+        Args:
+            filename: Name the receiver stores the stream under.
+
+        Returns:
+            bytes: The header followed by the filename bytes.
+        """
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.STREAM_OP_CODE, filename, 0, ""
+        )
+        return header + filename.encode("utf-8")
+
+    @staticmethod
+    def pack_chunk(offset: int, data: bytes) -> bytes:
+        """Frame one chunk of a stream.
+
+        Args:
+            offset: Bytes of the stream sent before this chunk.
+            data: The chunk's content.
+
+        Returns:
+            bytes: The 13-byte chunk frame followed by the content.
+        """
+        frame = struct.pack(
+            ProtocolHandler.CHUNK_FORMAT,
+            ProtocolHandler.CHUNK_OP_CODE,
+            offset,
+            len(data),
+        )
+        return frame + data
+
+    @staticmethod
+    def unpack_chunk(data: bytes) -> StreamChunk:
+        """Unpack a stream chunk frame.
+
+        Args:
+            data: The 13 frame bytes preceding the chunk's content.
+
+        Returns:
+            StreamChunk: The chunk's offset and length.
+
+        Raises:
+            ValueError: If the data is not a well-formed chunk frame.
+        """
+        if len(data) != ProtocolHandler.CHUNK_HEADER_SIZE:
+            raise ValueError(
+                f"Invalid chunk frame size: expected "
+                f"{ProtocolHandler.CHUNK_HEADER_SIZE}, got {len(data)}"
+            )
+
+        op_code, offset, length = struct.unpack(ProtocolHandler.CHUNK_FORMAT, data)
+        if op_code != ProtocolHandler.CHUNK_OP_CODE:
+            raise ValueError(f"Unexpected chunk op code: {op_code!r}")
+
+        return StreamChunk(offset=offset, length=length)
+
+    @staticmethod
+    def pack_trailer(total_size: int, sha256: str) -> bytes:
+        """Pack the trailer that closes a stream or a b'H' file.
+
+        Args:
+            total_size: Total bytes of the stream or file.
+            sha256: SHA-256 hex digest of the whole content.
+
+        Returns:
+            bytes: The header followed by the digest bytes.
+        """
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.TRAILER_OP_CODE, "", total_size, sha256
+        )
+        return header + sha256.encode("utf-8")
+
+    @staticmethod
+    def pack_ack(bytes_received: int, sha256: str) -> bytes:
+        """Pack a receiver's confirmation of a stored file.
+
+        Args:
+            bytes_received: Bytes of the file that were stored.
+            sha256: SHA-256 hex digest of the stored content.
+
+        Returns:
+            bytes: The header followed by the digest bytes.
+        """
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.ACK_OP_CODE, "", bytes_received, sha256
+        )
+        return header + sha256.encode("utf-8")
+
+    @staticmethod
+    def unpack_ack(header: Header, hash_bytes: bytes) -> FileAck:
+        """Parse a file acknowledgement.
+
+        Args:
+            header: The acknowledgement's header.
+            hash_bytes: The `hash_len` bytes following the header.
+
+        Returns:
+            FileAck: The parsed acknowledgement.
+        """
+        return FileAck(
+            bytes_received=header.file_size,
+            sha256=hash_bytes.decode("utf-8", errors="replace"),
+        )
+
+    @staticmethod
+    def pack_status(
+        bytes_written: int, datagrams_received: int, repaired: int | None = None
+    ) -> bytes:
+        """Pack a receiver progress report.
+
+        Args:
+            bytes_written: Bytes of the current file written to disk.
+            datagrams_received: Content datagrams accepted so far.
+            repaired: Datagrams rebuilt from FEC parity, for FEC files only.
+
+        Returns:
+            bytes: The packed status message (13 bytes, 17 with `repaired`).
+        """
+        if repaired is not None:
+            return struct.pack(
+                ProtocolHandler.FEC_STATUS_FORMAT,
+                ProtocolHandler.STATUS_OP_CODE,
+                bytes_written,
+                datagrams_received,
+                repaired,
+            )
+        return struct.pack(
+            ProtocolHandler.STATUS_FORMAT,
+            ProtocolHandler.STATUS_OP_CODE,
+            bytes_written,
+            datagrams_received,
+        )
+
+    @staticmethod
+    def unpack_status(data: bytes) -> ReceiverStatus:
+        """Unpack a receiver progress report.
+
+        Args:
+            data: Raw bytes received from the network.
+
+        Returns:
+            ReceiverStatus: The parsed status report.
+
+        Raises:
+            ValueError: If the data is not a well-formed status message.
+        """
+        repaired = 0
+        if len(data) == ProtocolHandler.FEC_STATUS_SIZE:
+            op_code, bytes_written, datagrams, repaired = struct.unpack(
+                ProtocolHandler.FEC_STATUS_FORMAT, data
+            )
+        elif len(data) == ProtocolHandler.STATUS_SIZE:
+            op_code, bytes_written, datagrams = struct.unpack(
+                ProtocolHandler.STATUS_FORMAT, data
+            )
+        else:
+            raise ValueError(
+                f"Invalid status size: expected {ProtocolHandler.STATUS_SIZE} or "
+                f"{ProtocolHandler.FEC_STATUS_SIZE}, got {len(data)}"
+            )
+        if op_code != ProtocolHandler.STATUS_OP_CODE:
+            raise ValueError(f"Unexpected status op code: {op_code!r}")
+
+        return ReceiverStatus(
+            bytes_written=bytes_written,
+            datagrams_received=datagrams,
+            repaired=repaired,
+        )
+
+    @staticmethod
+    def pack_fec_frame(op_code: bytes, group: int, index: int, body: bytes) -> bytes:
+        """Frame a data block or parity block of an FEC-protected UDP file.
+
+        Args:
+            op_code: `FEC_DATA_OP_CODE` or `FEC_PARITY_OP_CODE`.
+            group: Parity group of the frame.
+            index: Position in the group, or data frames in it for parity.
+            body: The block.
+
+        Returns:
+            bytes: The 6-byte frame header followed by the body.
+        """
+        return struct.pack(ProtocolHandler.FEC_FORMAT, op_code, group, index) + body
+
+    @staticmethod
+    def unpack_fec_frame(data: bytes) -> FecFrame:
+        """Unpack a content datagram of an FEC-protected UDP file.
+
+        Args:
+            data: The whole datagram.
+
+        Returns:
+            FecFrame: The parsed frame.
+
+        Raises:
+            ValueError: If the datagram is not an FEC frame.
+        """
+        if len(data) < ProtocolHandler.FEC_HEADER_SIZE:
+            raise ValueError(f"FEC frame too short: {len(data)} bytes")
+
+        op_code, group, index = struct.unpack(
+            ProtocolHandler.FEC_FORMAT, data[: ProtocolHandler.FEC_HEADER_SIZE]
+        )
+        if op_code not in (
+            ProtocolHandler.FEC_DATA_OP_CODE,
+            ProtocolHandler.FEC_PARITY_OP_CODE,
+        ):
+            raise ValueError(f"Unexpected FEC op code: {op_code!r}")
+
+        return FecFrame(
+            op_code=op_code,
+            group=group,
+            index=index,
+            body=data[ProtocolHandler.FEC_HEADER_SIZE :],
+        )
@@ -0,0 +1,187 @@
+import contextlib
+import hashlib
+import json
+import os
+import threading
+import time
+from collections.abc import Iterator
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+GENESIS_HASH = "0" * 64
+
+
+@dataclass
+class AuditVerification:
+    """Outcome of checking an audit log's hash chain.
+
+    Attributes:
+        valid (bool): Whether every entry links to the previous one.
+        entries (int): Number of entries checked.
+        error_line (int | None): 1-based line of the first broken entry.
+        error (str | None): Description of the problem found.
+    """
+
+    valid: bool
+    entries: int
+    error_line: int | None = None
+    error: str | None = None
+
+
+class AuditLog:
+    """Append-only, hash-chained journal of sent and received files.
+
+    Each line is a JSON object whose `hash` is the SHA-256 of the previous
+    entry's hash followed by the entry's own canonical JSON (without `hash`).
+    Editing, removing or reordering any entry breaks the chain from that point
+    on, which `verify` reports. This is kept apart from the application logs.
+    """
+
+    def __init__(self, path: Path):
+        """Initialize the journal; the file is created on first append.
+
+        Args:
+            path: Location of the journal file.
+        """
+        self.path = path
+        self._lock = threading.Lock()
+
+    def record(
+        self,
+        direction: str,
+        peer: str,
+        filename: str,
+        size: int,
+        sha256: str,
+        result: str,
+        validation: list[dict[str, Any]] | None = None,
+    ) -> dict[str, Any]:
+        """Append an entry for a file transfer outcome.
+
+        Args:
+            direction: "sent" or "received".
+            peer: Remote "ip:port".
+            filename: Name of the file.
+            size: Bytes transferred.
+            sha256: Checksum of the content (empty if unknown).
+            result: Outcome, e.g. "verified", "mismatch", "sent" or
+                "aborted:<reason>".
+            validation: Results of post-transfer validators, if any ran.
+
+        Returns:
+            dict[str, Any]: The entry as written, including its chain hash.
+        """
+        with self._lock, self._locked_file() as f:
+            last = _last_line(f)
+            prev = json.loads(last) if last else None
+            entry: dict[str, Any] = {
+                "seq": prev["seq"] + 1 if prev else 1,
+                "timestamp": time.time(),
+                "direction": direction,
+                "peer": peer,
+                "filename": filename,
+                "size": size,
+                "sha256": sha256,
+                "result": result,
+                "prev": prev["hash"] if prev else GENESIS_HASH,
+            }
+            if validation:
+                entry["validation"] = validation
+            entry["hash"] = _chain_hash(entry)
+            f.seek(0, os.SEEK_END)
+            f.write(json.dumps(entry).encode("utf-8") + b"\n")
+            f.flush()
+            os.fsync(f.fileno())
+        return entry
+
+    def verify(self) -> AuditVerification:
+        """Check that the journal's hash chain is intact.
+
+        Returns:
+            AuditVerification: The result; a missing file is a valid, empty log.
+        """
+        if not self.path.exists():
+            return AuditVerification(valid=True, entries=0)
+
+        prev_hash, expected_seq = GENESIS_HASH, 1
+        with open(self.path, encoding="utf-8") as f:
+            for line_no, line in enumerate(f, start=1):
+                try:
+                    entry: dict[str, Any] = json.loads(line)
+                    if entry["seq"] != expected_seq:
+                        error = f"expected seq {expected_seq}, found {entry['seq']}"
+                    elif entry["prev"] != prev_hash:
+                        error = "previous hash does not match"
+                    elif entry["hash"] != _chain_hash(entry):
+                        error = "entry hash does not match its contents"
+                    else:
+                        error = None
+                except (ValueError, KeyError, TypeError) as e:
+                    error = f"unreadable entry: {e}"
+                    entry = {}
+
+                if error:
+                    return AuditVerification(False, line_no - 1, line_no, error)
+                prev_hash, expected_seq = entry["hash"], expected_seq + 1
+
+        return AuditVerification(valid=True, entries=expected_seq - 1)
+
+    @contextlib.contextmanager
+    def _locked_file(self) -> Iterator[Any]:
+        """Open the journal for appending, holding an exclusive file lock.
+
+        The lock keeps the chain consistent when a sender and a receiver on
+        the same host share one journal. It is skipped where `fcntl` is
+        unavailable (Windows).
+
+        Returns:
+            Iterator[Any]: Context manager yielding the binary file object.
+        """
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        with open(self.path, "a+b") as f:
+            try:
+                import fcntl
+
+                fcntl.flock(f.fileno(), fcntl.LOCK_EX)
+            except ImportError:
+                pass
+            yield f
+
+
+def _chain_hash(entry: dict[str, Any]) -> str:
+    """Compute an entry's chain hash from its fields other than `hash`.
+
+    Args:
+        entry: The journal entry.
+
+    Returns:
+        str: Hex SHA-256 of the previous hash and the canonical entry JSON.
+    """
+    body = {k: v for k, v in entry.items() if k != "hash"}
+    canonical = json.dumps(body, sort_keys=True, separators=(",", ":"))
+    return hashlib.sha256((entry["prev"] + canonical).encode("utf-8")).hexdigest()
+
+
+def _last_line(f: Any, block_size: int = 4096) -> str:
+    """Return the last non-empty line of a binary file without reading it all.
+
+    Args:
+        f: File object opened for binary reading.
+        block_size: Bytes read per step, backwards from the end.
+
+    Returns:
+        str: The last line, or an empty string for an empty file.
+    """
+    end = f.seek(0, os.SEEK_END)
+    data = b""
+    pos = end
+    while pos > 0:
+        step = min(block_size, pos)
+        pos -= step
+        f.seek(pos)
+        data = f.read(step) + data
+        if data.rstrip(b"\n").count(b"\n") >= 1:
+            break
+    lines = data.rstrip(b"\n").split(b"\n")
+    return lines[-1].decode("utf-8") if lines[-1] else ""
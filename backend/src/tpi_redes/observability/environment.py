@@ -0,0 +1,68 @@
+import platform
+from typing import Any
+
+from tpi_redes import __version__
+from tpi_redes.core.protocol import ProtocolHandler
+
+
+def environment_snapshot(
+    protocol: str,
+    local: str,
+    remote: str,
+    peer_version: str | None,
+    settings: dict[str, Any],
+    protocol_version: int | None = None,
+) -> dict[str, Any]:
+    """Describe the conditions a transfer ran under, for bug reports.
+
+    Attached to the sender's `complete` events so the UI can keep it with
+    each history entry.
+
+    Args:
+        protocol: "tcp" or "udp".
+        local: Local "ip:port" of the connection.
+        remote: Receiver "ip:port".
+        peer_version: tpi-redes version the receiver announced, if known.
+        settings: Tuned parameters and negotiated options, e.g. chunk size.
+        protocol_version: Protocol version negotiated with the peer, if any.
+
+    Returns:
+        dict[str, Any]: The snapshot.
+    """
+    return {
+        "app_version": __version__,
+        "os": platform.platform(),
+        "python": platform.python_version(),
+        "protocol": protocol,
+        "local": local,
+        "remote": remote,
+        "peer_version": peer_version,
+        "protocol_version": protocol_version,
+        "settings": settings,
+    }
+
+
+def compatibility_warning(
+    peer: str, peer_version: str, protocol_version: int
+) -> dict[str, Any] | None:
+    """Describe what an older peer cannot do, as a `PEER_COMPATIBILITY` event.
+
+    Args:
+        peer: The peer as "ip:port".
+        peer_version: tpi-redes version the peer announced.
+        protocol_version: Protocol version negotiated with the peer.
+
+    Returns:
+        dict[str, Any] | None: The event, or None if nothing is disabled.
+    """
+    disabled = ProtocolHandler.disabled_features(protocol_version)
+    if not disabled:
+        return None
+    return {
+        "type": "PEER_COMPATIBILITY",
+        "peer": peer,
+        "peer_version": peer_version,
+        "protocol_version": protocol_version,
+        "local_protocol_version": ProtocolHandler.PROTOCOL_VERSION,
+        "disabled": disabled,
+    }
@@ -0,0 +1,182 @@
+import threading
+import time
+from collections.abc import Callable
+from dataclasses import dataclass
+from typing import Any
+
+from tpi_redes.config import ERROR_EVENT_INTERVAL
+
+# Non-fatal occurrences: rate limited like errors but counted apart.
+WARNING_EVENT_TYPES = (
+    "TRANSFER_WARNING",
+    "SLA_WARNING",
+    "SLOW_TRANSFER",
+    "SPEED_SATURATED",
+    "PEER_COMPATIBILITY",
+)
+WARNING_STATUSES = ("restarting",)
+ERROR_EVENT_TYPES = ("SNIFFER_ERROR", *WARNING_EVENT_TYPES)
+ERROR_STATUSES = ("aborted", *WARNING_STATUSES)
+
+
+def is_error_event(event: dict[str, Any]) -> bool:
+    """Whether an event reports an error, retry or warning.
+
+    Args:
+        event: The event.
+
+    Returns:
+        bool: True for error-like events, which are rate limited.
+    """
+    return (
+        event.get("type") in ERROR_EVENT_TYPES
+        or event.get("status") in ERROR_STATUSES
+    )
+
+
+def is_warning_event(event: dict[str, Any]) -> bool:
+    """Whether an event reports something non-fatal.
+
+    Args:
+        event: The event.
+
+    Returns:
+        bool: True for warnings, which the summaries list apart from errors.
+    """
+    return (
+        event.get("type") in WARNING_EVENT_TYPES
+        or event.get("status") in WARNING_STATUSES
+    )
+
+
+def _kind(event: dict[str, Any]) -> str:
+    """Name the kind of an error event for the aggregate counts.
+
+    Args:
+        event: The event.
+
+    Returns:
+        str: e.g. "TRANSFER_UPDATE/aborted/stalled".
+    """
+    parts = (
+        event.get("type"),
+        event.get("status"),
+        event.get("reason"),
+        event.get("code"),
+    )
+    return "/".join(str(p) for p in parts if p)
+
+
+@dataclass
+class _Run:
+    """Occurrences of one error event for one transfer."""
+
+    last_emitted: float
+    suppressed: int = 0
+    first_ts: float | None = None
+    last_ts: float | None = None
+
+
+class ErrorEventLimiter:
+    """Deduplicates error events so a flapping network cannot flood the UI.
+
+    Identical error events (same type, status, reason and file) are emitted
+    at most once per `interval` seconds. Repeats in between are dropped and
+    counted; the next one emitted carries `repeated` with their count and
+    `first_ts`/`last_ts` of the dropped run. Other events pass untouched.
+    Safe to share between threads, e.g. the listeners of `--listen`.
+    """
+
+    def __init__(
+        self,
+        interval: float = ERROR_EVENT_INTERVAL,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        """Start with no history.
+
+        Args:
+            interval: Minimum seconds between identical error events.
+            clock: Monotonic time source.
+        """
+        self.interval = interval
+        self._clock = clock
+        self._runs: dict[tuple[Any, ...], _Run] = {}
+        self._stats: dict[str, dict[str, Any]] = {}
+        self._warning_kinds: set[str] = set()
+        self._lock = threading.Lock()
+
+    def admit(self, event: dict[str, Any]) -> dict[str, Any] | None:
+        """Decide whether a stamped event is emitted.
+
+        Args:
+            event: The event, stamped with `ts`.
+
+        Returns:
+            dict[str, Any] | None: The event to emit (annotated if it ends a
+            run of dropped repeats), or None to drop it.
+        """
+        if not is_error_event(event):
+            return event
+        with self._lock:
+            return self._admit_error(event)
+
+    def _admit_error(self, event: dict[str, Any]) -> dict[str, Any] | None:
+        """Count an error event and decide whether it is emitted.
+
+        Must be called with the lock held.
+
+        Args:
+            event: The error event, stamped with `ts`.
+
+        Returns:
+            dict[str, Any] | None: The event to emit, or None to drop it.
+        """
+        kind = _kind(event)
+        if is_warning_event(event):
+            self._warning_kinds.add(kind)
+        stats = self._stats.setdefault(
+            kind,
+            {"count": 0, "suppressed": 0, "first_ts": event.get("ts"), "last_ts": None},
+        )
+        stats["count"] += 1
+        stats["last_ts"] = event.get("ts")
+
+        now = self._clock()
+        key = (kind, event.get("filename"))
+        run = self._runs.get(key)
+        if run is None:
+            self._runs[key] = _Run(now)
+            return event
+        if now - run.last_emitted < self.interval:
+            run.suppressed += 1
+            run.first_ts = run.first_ts or event.get("ts")
+            run.last_ts = event.get("ts")
+            stats["suppressed"] += 1
+            return None
+        if run.suppressed:
+            event = {
+                **event,
+                "repeated": run.suppressed,
+                "first_ts": run.first_ts,
+                "last_ts": run.last_ts,
+            }
+        self._runs[key] = _Run(now)
+        return event
+
+    def stats(self, warnings: bool = False) -> dict[str, dict[str, Any]]:
+        """Return aggregate counts per kind of error event.
+
+        Args:
+            warnings: Count the warnings instead of the errors.
+
+        Returns:
+            dict[str, dict[str, Any]]: Per kind, e.g.
+            "TRANSFER_UPDATE/aborted/stalled": `count` seen, how many were
+            `suppressed`, and `first_ts`/`last_ts`.
+        """
+        with self._lock:
+            return {
+                kind: dict(s)
+                for kind, s in self._stats.items()
+                if (kind in self._warning_kinds) == warnings
+            }
@@ -3,8 +3,32 @@ import logging
 import time
 from typing import Any, ClassVar
 
+from .event_limiter import ErrorEventLimiter
+
 logger = logging.getLogger("tpi-redes")
 
+_CLOCK_START = time.monotonic()
+
+
+def stamp_event(event: dict[str, Any]) -> dict[str, Any]:
+    """Add a wall-clock and a monotonic timestamp to an event.
+
+    `ts` is Unix time, for display and correlation with logs; `mono` is
+    seconds since the backend started on a monotonic clock, so consumers can
+    order events and measure phases even if the wall clock is adjusted.
+
+    Args:
+        event: The event to stamp.
+
+    Returns:
+        dict[str, Any]: A copy of the event with `ts` and `mono`.
+    """
+    return {
+        **event,
+        "ts": round(time.time(), 6),
+        "mono": round(time.monotonic() - _CLOCK_START, 6),
+    }
+
 
 class PacketLogger:
     """Handles buffering and flushing of packet capture events to stdout.
@@ -13,12 +37,16 @@ class PacketLogger:
     which can be expensive in high-throughput scenarios. Buffer is flushed
     when it reaches size limit or time interval.
 
+    Repeated error events go through an `ErrorEventLimiter`, so a flapping
+    link reports one event per second per error instead of one per retry.
+
     Attributes:
         BUFFER_SIZE_LIMIT (int): Maximum number of events before forced flush.
         FLUSH_INTERVAL (float): Maximum time in seconds to hold events.
     """
 
     _buffer: ClassVar[list[dict[str, Any]]] = []
+    _limiter: ClassVar[ErrorEventLimiter] = ErrorEventLimiter()
     _last_flush_time = 0.0
     BUFFER_SIZE_LIMIT = 100
     FLUSH_INTERVAL = 0.05
@@ -61,7 +89,7 @@ class PacketLogger:
             "ack": ack,
             "window": window,
         }
-        PacketLogger._buffer.append(packet_data)
+        PacketLogger._buffer.append(stamp_event(packet_data))
         PacketLogger._check_flush()
 
     @staticmethod
@@ -71,9 +99,45 @@ class PacketLogger:
         Args:
             data: Dictionary containing event data.
         """
-        PacketLogger._buffer.append(data)
+        event = PacketLogger._limiter.admit(stamp_event(data))
+        if event is not None:
+            PacketLogger._buffer.append(event)
         PacketLogger._check_flush()
 
+    @staticmethod
+    def emit_event(event: dict[str, Any]):
+        """Print a timestamped event immediately, outside the buffer.
+
+        Args:
+            event: Dictionary containing event data.
+        """
+        admitted = PacketLogger._limiter.admit(stamp_event(event))
+        if admitted is not None:
+            print(json.dumps(admitted), flush=True)
+
+    @staticmethod
+    def reset_errors():
+        """Forget previous error events, so a new transfer starts clean."""
+        PacketLogger._limiter = ErrorEventLimiter()
+
+    @staticmethod
+    def error_stats() -> dict[str, dict[str, Any]]:
+        """Return how many error events of each kind were seen and dropped.
+
+        Returns:
+            dict[str, dict[str, Any]]: See `ErrorEventLimiter.stats`.
+        """
+        return PacketLogger._limiter.stats()
+
+    @staticmethod
+    def warning_stats() -> dict[str, dict[str, Any]]:
+        """Return how many warning events of each kind were seen and dropped.
+
+        Returns:
+            dict[str, dict[str, Any]]: See `ErrorEventLimiter.stats`.
+        """
+        return PacketLogger._limiter.stats(warnings=True)
+
     @staticmethod
     def _check_flush():
         """Check if buffer conditions are met and trigger flush."""
@@ -0,0 +1,160 @@
+import threading
+import time
+from collections import deque
+from dataclasses import dataclass, field
+from typing import Any
+
+# Received bytes are kept in per-minute buckets for the last hour.
+RECENT_BYTES_WINDOW = 3600.0
+BUCKET_SECONDS = 60.0
+
+
+@dataclass
+class _Peer:
+    connections: int = 0
+    current_connections: int = 0
+    handshakes_failed: int = 0
+    auth_failures: int = 0
+    bytes_total: int = 0
+    buckets: deque[tuple[float, int]] = field(default_factory=deque)
+
+
+class PeerStats:
+    """Connection counters per sender IP, to spot misbehaving clients.
+
+    Receivers report connections, failed handshakes, rejected senders and
+    received bytes; `snapshot` summarizes them per IP, including the bytes
+    received during the last hour. Safe to share between listener threads.
+    """
+
+    def __init__(self, clock: Any = time.monotonic):
+        """Start with no peers.
+
+        Args:
+            clock: Monotonic time source, replaceable in tests.
+        """
+        self._clock = clock
+        self._peers: dict[str, _Peer] = {}
+        self._lock = threading.Lock()
+
+    def connected(self, ip: str):
+        """Count a connection that was accepted.
+
+        Args:
+            ip: The sender's IP address.
+
+        Returns:
+            None: No return value.
+        """
+        with self._lock:
+            peer = self._peer(ip)
+            peer.connections += 1
+            peer.current_connections += 1
+
+    def disconnected(self, ip: str):
+        """Count the end of a connection counted by `connected`.
+
+        Args:
+            ip: The sender's IP address.
+
+        Returns:
+            None: No return value.
+        """
+        with self._lock:
+            peer = self._peer(ip)
+            peer.current_connections = max(0, peer.current_connections - 1)
+
+    def handshake_failed(self, ip: str):
+        """Count a connection closed before any transfer started.
+
+        Args:
+            ip: The sender's IP address.
+
+        Returns:
+            None: No return value.
+        """
+        with self._lock:
+            self._peer(ip).handshakes_failed += 1
+
+    def auth_failed(self, ip: str):
+        """Count a sender rejected by the knock gate.
+
+        Args:
+            ip: The sender's IP address.
+
+        Returns:
+            None: No return value.
+        """
+        with self._lock:
+            self._peer(ip).auth_failures += 1
+
+    def received(self, ip: str, nbytes: int):
+        """Count bytes received from a sender.
+
+        Args:
+            ip: The sender's IP address.
+            nbytes: Bytes received.
+
+        Returns:
+            None: No return value.
+        """
+        if nbytes <= 0:
+            return
+        now = self._clock()
+        bucket = now - now % BUCKET_SECONDS
+        with self._lock:
+            peer = self._peer(ip)
+            peer.bytes_total += nbytes
+            if peer.buckets and peer.buckets[-1][0] == bucket:
+                peer.buckets[-1] = (bucket, peer.buckets[-1][1] + nbytes)
+            else:
+                peer.buckets.append((bucket, nbytes))
+            self._expire(peer, now)
+
+    def snapshot(self) -> dict[str, dict[str, int]]:
+        """Return the counters of every peer seen so far.
+
+        Returns:
+            dict[str, dict[str, int]]: Per IP, its `connections`,
+            `current_connections`, `handshakes_failed`, `auth_failures`,
+            `bytes_total` and `bytes_last_hour`.
+        """
+        now = self._clock()
+        with self._lock:
+            result = {}
+            for ip, peer in sorted(self._peers.items()):
+                self._expire(peer, now)
+                result[ip] = {
+                    "connections": peer.connections,
+                    "current_connections": peer.current_connections,
+                    "handshakes_failed": peer.handshakes_failed,
+                    "auth_failures": peer.auth_failures,
+                    "bytes_total": peer.bytes_total,
+                    "bytes_last_hour": sum(n for _, n in peer.buckets),
+                }
+            return result
+
+    def _peer(self, ip: str) -> _Peer:
+        """Return a peer's counters, creating them on first use.
+
+        Args:
+            ip: The sender's IP address.
+
+        Returns:
+            _Peer: The counters.
+        """
+        return self._peers.setdefault(ip, _Peer())
+
+    @staticmethod
+    def _expire(peer: _Peer, now: float):
+        """Drop buckets that fell out of the last hour.
+
+        Args:
+            peer: The peer's counters.
+            now: Current clock value.
+
+        Returns:
+            None: No return value.
+        """
+        while peer.buckets and peer.buckets[0][0] <= now - RECENT_BYTES_WINDOW:
+            peer.buckets.popleft()
@@ -0,0 +1,95 @@
+import re
+from typing import Any
+
+_WINDOW = re.compile(r"(\d+(?:\.\d+)?)\s*([smh]?)")
+_UNIT_SECONDS = {"": 1, "s": 1, "m": 60, "h": 3600}
+_SUCCESS = ("verified", "raw")
+
+
+def parse_window(spec: str) -> float:
+    """Parse a receive window such as `90`, `45s`, `10m` or `2h`.
+
+    Args:
+        spec: A positive number with an optional unit (seconds by default).
+
+    Returns:
+        float: The window in seconds.
+
+    Raises:
+        ValueError: If the window is not recognized or not positive.
+    """
+    match = _WINDOW.fullmatch(spec.strip().lower())
+    seconds = float(match[1]) * _UNIT_SECONDS[match[2]] if match else 0.0
+    if seconds <= 0:
+        raise ValueError(
+            f"Invalid window '{spec}'; use a duration such as 90, 45s, 10m or 2h"
+        )
+    return seconds
+
+
+def build_receive_report(
+    outcomes: list[dict[str, Any]], started: float, ended: float
+) -> dict[str, Any]:
+    """Consolidate what a receiver handled during a session.
+
+    Args:
+        outcomes: Every file the server handled, as recorded by the server.
+        started: Unix time the receiver started accepting transfers.
+        ended: Unix time it stopped.
+
+    Returns:
+        dict[str, Any]: Totals, the failed files and a breakdown per sender
+        IP (senders use a new port per connection, so ports are dropped).
+    """
+    peers: dict[str, dict[str, int]] = {}
+    failures: list[dict[str, Any]] = []
+    for o in outcomes:
+        ok = o["result"] in _SUCCESS
+        ip = o["peer"].rsplit(":", 1)[0]
+        peer = peers.setdefault(ip, {"files": 0, "received": 0, "failed": 0})
+        peer["files"] += 1
+        if ok:
+            peer["received"] += 1
+        else:
+            peer["failed"] += 1
+            failures.append(
+                {"filename": o["filename"], "peer": o["peer"], "result": o["result"]}
+            )
+    return {
+        "started": started,
+        "ended": ended,
+        "duration": round(ended - started, 3),
+        "files": len(outcomes),
+        "received": len(outcomes) - len(failures),
+        "failed": len(failures),
+        "total_bytes": sum(o["size"] for o in outcomes if o["result"] in _SUCCESS),
+        "failures": failures,
+        "peers": peers,
+    }
+
+
+def render_receive_report(report: dict[str, Any]) -> str:
+    """Render a report from `build_receive_report` as plain text.
+
+    Args:
+        report: The report.
+
+    Returns:
+        str: A few lines: totals, one line per sender and the failed files.
+    """
+    lines = [
+        f"Receive window of {report['duration']:.0f} s: "
+        f"{report['received']}/{report['files']} file(s) received, "
+        f"{report['total_bytes']} bytes, {report['failed']} failed",
+    ]
+    lines += [
+        f"  {ip}: {p['received']} received, {p['failed']} failed"
+        for ip, p in sorted(report["peers"].items())
+    ]
+    if report["failures"]:
+        lines.append("  Failed:")
+        lines += [
+            f"    {f['filename']} from {f['peer']}: {f['result']}"
+            for f in report["failures"]
+        ]
+    return "\n".join(lines)
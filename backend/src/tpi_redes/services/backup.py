@@ -0,0 +1,182 @@
+import json
+import time
+import zipfile
+from pathlib import Path
+from typing import Any
+
+from tpi_redes import __version__
+from tpi_redes.transfer.write_policy import write_atomic
+
+BACKUP_FORMAT = 1
+BACKUP_MANIFEST = "backup.json"
+# Archive member of each item; JSON objects are merged by key, the index by
+# transfer id.
+BACKUP_MEMBERS = {
+    "settings": "settings.json",
+    "presets": "presets.json",
+    "received_index": "received_index.jsonl",
+}
+
+
+def create_backup(archive: Path, items: dict[str, Path]) -> dict[str, Any]:
+    """Write the receiver's state files into a single zip archive.
+
+    Args:
+        archive: Path of the archive to create; replaced if it exists.
+        items: Location of each item of `BACKUP_MEMBERS` to include; missing
+            files are left out.
+
+    Returns:
+        dict[str, Any]: The `archive`, its `format`, the `app_version` that
+        wrote it and the `items` included.
+    """
+    included = [name for name, path in items.items() if path.is_file()]
+    manifest = {
+        "format": BACKUP_FORMAT,
+        "app_version": __version__,
+        "created": time.time(),
+        "items": included,
+    }
+    archive.parent.mkdir(parents=True, exist_ok=True)
+    with zipfile.ZipFile(archive, "w", zipfile.ZIP_DEFLATED) as zf:
+        zf.writestr(BACKUP_MANIFEST, json.dumps(manifest, indent=2))
+        for name in included:
+            zf.write(items[name], BACKUP_MEMBERS[name])
+    return {
+        "archive": str(archive),
+        "format": BACKUP_FORMAT,
+        "app_version": __version__,
+        "items": included,
+    }
+
+
+def restore_backup(
+    archive: Path, items: dict[str, Path], overwrite: bool = False
+) -> dict[str, Any]:
+    """Restore a backup, merging it into the current state by default.
+
+    Settings and presets keep local values for keys present on both sides,
+    and received index entries are added only if their transfer id is new,
+    so restoring an old backup never loses newer history. With `overwrite`,
+    each restored item replaces the local file instead.
+
+    Args:
+        archive: Archive written by `create_backup`.
+        items: Where each item is restored; items without a location are
+            skipped.
+        overwrite: Replace local files instead of merging.
+
+    Raises:
+        ValueError: If the archive is not a backup, or was written in a newer
+            format than this version reads.
+        OSError: If the archive or a local file cannot be read or written.
+
+    Returns:
+        dict[str, Any]: The `archive`, the `app_version` that wrote it and,
+        per item, the `action` taken (`created`, `merged`, `replaced` or
+        `skipped`) and how many entries were `added`.
+    """
+    try:
+        zf = zipfile.ZipFile(archive)
+    except zipfile.BadZipFile as e:
+        raise ValueError(f"{archive} is not a tpi-redes backup") from e
+    with zf:
+        try:
+            manifest = json.loads(zf.read(BACKUP_MANIFEST))
+        except (KeyError, ValueError) as e:
+            raise ValueError(f"{archive} is not a tpi-redes backup") from e
+        version = manifest.get("format")
+        if not isinstance(version, int) or version > BACKUP_FORMAT:
+            raise ValueError(
+                f"{archive} has backup format {version}, written by tpi-redes "
+                f"{manifest.get('app_version')}; this version reads up to "
+                f"{BACKUP_FORMAT}"
+            )
+
+        results: dict[str, dict[str, Any]] = {}
+        for name in manifest.get("items", []):
+            if name not in BACKUP_MEMBERS or name not in items:
+                results[name] = {"action": "skipped", "added": 0}
+                continue
+            content = zf.read(BACKUP_MEMBERS[name]).decode("utf-8")
+            results[name] = _restore_item(name, content, items[name], overwrite)
+
+    return {
+        "archive": str(archive),
+        "app_version": manifest.get("app_version"),
+        "items": results,
+    }
+
+
+def _restore_item(
+    name: str, content: str, path: Path, overwrite: bool
+) -> dict[str, Any]:
+    """Restore one item into its local file.
+
+    Args:
+        name: Item name from `BACKUP_MEMBERS`.
+        content: The item's content in the archive.
+        path: The local file.
+        overwrite: Replace the local file instead of merging.
+
+    Raises:
+        ValueError: If the backed-up or local content is malformed.
+
+    Returns:
+        dict[str, Any]: The `action` taken and how many entries were `added`.
+    """
+    is_index = name == "received_index"
+    restored = _parse_index(content) if is_index else _parse_object(content, name)
+    if overwrite or not path.exists():
+        action = "replaced" if path.exists() else "created"
+        write_atomic(path, content)
+        return {"action": action, "added": len(restored)}
+
+    local_text = path.read_text(encoding="utf-8")
+    if is_index:
+        local = _parse_index(local_text)
+        known = {e.get("transfer_id") for e in local}
+        new = [e for e in restored if e.get("transfer_id") not in known]
+        merged = sorted(local + new, key=lambda e: e.get("timestamp", 0))
+        text = "".join(json.dumps(e) + "\n" for e in merged)
+    else:
+        local_object = _parse_object(local_text, name)
+        new = [key for key in restored if key not in local_object]
+        text = json.dumps({**restored, **local_object}, indent=2)
+    if new:
+        write_atomic(path, text)
+    return {"action": "merged", "added": len(new)}
+
+
+def _parse_object(content: str, name: str) -> dict[str, Any]:
+    """Parse a JSON object item.
+
+    Args:
+        content: File content.
+        name: Item name, for errors.
+
+    Raises:
+        ValueError: If the content is not a JSON object.
+
+    Returns:
+        dict[str, Any]: The object.
+    """
+    data = json.loads(content)
+    if not isinstance(data, dict):
+        raise ValueError(f"{name}: expected a JSON object")
+    return data
+
+
+def _parse_index(content: str) -> list[dict[str, Any]]:
+    """Parse a received index, one JSON entry per line.
+
+    Args:
+        content: File content.
+
+    Raises:
+        ValueError: If a line is not valid JSON.
+
+    Returns:
+        list[dict[str, Any]]: The entries.
+    """
+    return [json.loads(line) for line in content.splitlines() if line.strip()]
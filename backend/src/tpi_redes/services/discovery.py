@@ -4,6 +4,7 @@ import logging
 import socket
 import threading
 import time
+from collections.abc import Callable
 from typing import Any
 
 from tpi_redes.config import DEFAULT_SERVER_PORT, DISCOVERY_BUFFER_SIZE
@@ -12,6 +13,14 @@ logger = logging.getLogger("tpi-redes")
 
 DISCOVERY_PORT = 37020
 BROADCAST_IP = "255.255.255.255"
+CAPACITY_FIELDS = (
+    "protocols",
+    "listeners",
+    "free_bytes",
+    "auth_required",
+    "active_transfers",
+    "audit",
+)
 
 
 class DiscoveryService:
@@ -63,6 +72,11 @@ class DiscoveryService:
 
                         if response.get("type") == "PONG":
                             peer: dict[str, Any] = {
+                                **{
+                                    k: response[k]
+                                    for k in CAPACITY_FIELDS
+                                    if k in response
+                                },
                                 "hostname": response.get("hostname"),
                                 "ip": addr[0],
                                 "port": response.get("port", DEFAULT_SERVER_PORT),
@@ -86,7 +100,7 @@ class DiscoveryService:
 
         return discovered_peers
 
-    def listen(self, port: int):
+    def listen(self, port: int, capacity: Callable[[], dict[str, Any]] | None = None):
         """Start a background thread listening for PING broadcasts.
 
         Responds with PONG packets containing this node's hostname and service port.
@@ -94,6 +108,8 @@ class DiscoveryService:
 
         Args:
             port: The TCP service port to announce in PONG responses.
+            capacity: Optional callback returning the receiver's current state
+                (protocols, free space, ...), merged into each PONG.
         """
         self.running = True
 
@@ -118,6 +134,7 @@ class DiscoveryService:
                             )
 
                             response = {
+                                **(capacity() if capacity else {}),
                                 "type": "PONG",
                                 "hostname": self.hostname,
                                 "port": port,
@@ -0,0 +1,173 @@
+import hashlib
+import hmac
+import logging
+import math
+import os
+import socket
+import struct
+import threading
+import time
+from collections.abc import Callable
+
+from tpi_redes.config import (
+    KNOCK_MAX_SKEW,
+    KNOCK_PORT,
+    KNOCK_SETTLE_DELAY,
+    KNOCK_WINDOW,
+)
+
+logger = logging.getLogger("tpi-redes")
+
+# Timestamp, random nonce and HMAC-SHA256 over both.
+KNOCK_PACKET = struct.Struct("!d16s32s")
+
+
+def build_knock(secret: bytes, now: float | None = None) -> bytes:
+    """Build a signed single-packet authorization (SPA) datagram.
+
+    Args:
+        secret: Shared secret configured on both sender and receiver.
+        now: Timestamp to sign; defaults to the current time.
+
+    Returns:
+        bytes: The knock datagram.
+    """
+    timestamp = time.time() if now is None else now
+    nonce = os.urandom(16)
+    signed = struct.pack("!d16s", timestamp, nonce)
+    return KNOCK_PACKET.pack(
+        timestamp, nonce, hmac.new(secret, signed, hashlib.sha256).digest()
+    )
+
+
+def send_knock(ip: str, secret: bytes, port: int = KNOCK_PORT):
+    """Ask a gated receiver to admit this host for a short window.
+
+    Sleeps briefly afterwards so the receiver can process the knock before
+    the transfer connection arrives.
+
+    Args:
+        ip: Receiver IP address.
+        secret: Shared secret configured on the receiver.
+        port: The receiver's knock port.
+
+    Returns:
+        None: No return value.
+    """
+    with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as s:
+        s.sendto(build_knock(secret), (ip, port))
+    logger.info(f"Sent authorization knock to {ip}:{port}")
+    time.sleep(KNOCK_SETTLE_DELAY)
+
+
+class KnockGate:
+    """Single-packet authorization gate for receivers exposed beyond the LAN.
+
+    Senders must first send a datagram signed with the shared secret to the
+    knock port; their IP is then admitted for `window` seconds. Knocks are
+    rejected if the signature is wrong, the timestamp is outside
+    `KNOCK_MAX_SKEW`, or the nonce was already used.
+
+    Security caveats: this is an application-level filter. The listening
+    port still completes TCP handshakes, so a port scan shows it open; the
+    gate only closes unauthorized connections before reading from them and
+    drops their datagrams. Admission is by source IP, so every host behind
+    the same NAT is admitted together, and knocks are not encrypted. Use a
+    firewall for real port filtering, and a long random secret.
+    """
+
+    def __init__(
+        self,
+        secret: bytes,
+        port: int = KNOCK_PORT,
+        window: float = KNOCK_WINDOW,
+        clock: Callable[[], float] = time.time,
+    ):
+        """Initialize the gate; call `listen` to start accepting knocks.
+
+        Args:
+            secret: Shared secret senders sign their knocks with.
+            port: UDP port to listen for knocks on.
+            window: Seconds an authorized IP stays admitted.
+            clock: Wall-clock time source, compared with knock timestamps.
+        """
+        self.secret = secret
+        self.port = port
+        self.window = window
+        self._clock = clock
+        self._allowed: dict[str, float] = {}
+        # Each nonce is kept until its timestamp leaves the skew window.
+        self._seen_nonces: dict[bytes, float] = {}
+        self._lock = threading.Lock()
+        self.running = False
+
+    def verify(self, data: bytes, ip: str) -> bool:
+        """Check a knock and admit its sender if it is valid.
+
+        Args:
+            data: The received datagram.
+            ip: The sender's IP address.
+
+        Returns:
+            bool: True if the knock was accepted.
+        """
+        if len(data) != KNOCK_PACKET.size:
+            return False
+        timestamp, nonce, mac = KNOCK_PACKET.unpack(data)
+        expected = hmac.new(self.secret, data[: -len(mac)], hashlib.sha256).digest()
+        now = self._clock()
+
+        with self._lock:
+            self._seen_nonces = {n: t for n, t in self._seen_nonces.items() if t >= now}
+            if (
+                not hmac.compare_digest(mac, expected)
+                or not math.isfinite(timestamp)
+                or abs(now - timestamp) > KNOCK_MAX_SKEW
+                or nonce in self._seen_nonces
+            ):
+                return False
+            self._seen_nonces[nonce] = timestamp + KNOCK_MAX_SKEW
+            self._allowed[ip] = now + self.window
+        return True
+
+    def is_allowed(self, ip: str) -> bool:
+        """Return whether an IP has a current authorization.
+
+        Args:
+            ip: The peer's IP address.
+
+        Returns:
+            bool: True if the IP knocked within the last `window` seconds.
+        """
+        with self._lock:
+            return self._allowed.get(ip, 0.0) >= self._clock()
+
+    def listen(self):
+        """Start a background thread accepting knocks on the knock port.
+
+        Returns:
+            None: No return value.
+        """
+        self.running = True
+
+        def _listen_loop():
+            with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as s:
+                s.bind(("", self.port))
+                logger.info(f"Knock gate listening on UDP {self.port}")
+
+                while self.running:
+                    try:
+                        data, addr = s.recvfrom(KNOCK_PACKET.size + 1)
+                        if self.verify(data, addr[0]):
+                            logger.info(f"Authorized {addr[0]} for {self.window}s")
+                        else:
+                            logger.warning(f"Rejected invalid knock from {addr[0]}")
+                    except Exception as e:
+                        if self.running:
+                            logger.error(f"Knock gate error: {e}")
+
+        threading.Thread(target=_listen_loop, daemon=True).start()
+
+    def stop(self):
+        """Stop the listening thread."""
+        self.running = False
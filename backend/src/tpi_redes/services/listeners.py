@@ -0,0 +1,49 @@
+from dataclasses import dataclass
+from pathlib import Path
+
+
+@dataclass(frozen=True)
+class ListenerSpec:
+    """An additional port the receiver listens on.
+
+    Attributes:
+        protocol (str): Transport protocol, `tcp` or `udp`.
+        port (int): Port to bind.
+        save_dir (Path | None): Where this listener stores files, or None to
+            share the receiver's main directory.
+    """
+
+    protocol: str
+    port: int
+    save_dir: Path | None = None
+
+
+def parse_listener(spec: str, protocols: list[str]) -> ListenerSpec:
+    """Parse a `PROTOCOL:PORT[=DIR]` listener given on the command line.
+
+    Args:
+        spec: Listener specification, e.g. `udp:8081=/srv/udp-drop`.
+        protocols: Protocols enabled in this build.
+
+    Returns:
+        ListenerSpec: The parsed listener.
+
+    Raises:
+        ValueError: If the listener is malformed, its protocol is not enabled
+            or its port is out of range.
+    """
+    address, _, directory = spec.partition("=")
+    protocol, sep, port_text = address.partition(":")
+    if not sep or not port_text.isdigit():
+        raise ValueError(f"Invalid listener '{spec}', expected PROTOCOL:PORT[=DIR]")
+    if protocol not in protocols:
+        raise ValueError(
+            f"Invalid listener '{spec}', protocol must be one of "
+            f"{', '.join(protocols)}"
+        )
+    port = int(port_text)
+    if not 0 < port < 65536:
+        raise ValueError(f"Invalid listener '{spec}', port out of range")
+    return ListenerSpec(
+        protocol, port, Path(directory).expanduser() if directory else None
+    )
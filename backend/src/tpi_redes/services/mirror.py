@@ -0,0 +1,145 @@
+import contextlib
+import logging
+import os
+import queue
+import shutil
+import threading
+import time
+from collections.abc import Callable
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import MIRROR_RETRIES, MIRROR_RETRY_DELAY
+from tpi_redes.observability.packet_logger import PacketLogger
+
+logger = logging.getLogger("tpi-redes")
+
+
+class Mirror:
+    """Copies verified received files to a second directory in the background.
+
+    Files are queued as soon as they are verified and copied by a worker
+    thread, so a slow or temporarily unavailable destination (e.g. a network
+    mount) never holds up reception. A failed copy is retried `retries`
+    times, `retry_delay` seconds apart, before the file is reported as
+    failed. Copies land under a temporary name and are renamed into place,
+    so the mirror never shows a partial file.
+    """
+
+    def __init__(
+        self,
+        target: Path,
+        retries: int = MIRROR_RETRIES,
+        retry_delay: float = MIRROR_RETRY_DELAY,
+        sleep: Callable[[float], None] = time.sleep,
+    ):
+        """Start the worker.
+
+        Args:
+            target: Destination directory, created if missing.
+            retries: Extra attempts for a copy that failed.
+            retry_delay: Seconds between attempts.
+            sleep: Function used to wait between attempts.
+        """
+        self.target = target
+        self.retries = retries
+        self.retry_delay = retry_delay
+        self.mirrored = 0
+        self.failed = 0
+        self._sleep = sleep
+        self._queue: queue.Queue[tuple[Path, Path] | None] = queue.Queue()
+        self._thread = threading.Thread(target=self._run, daemon=True)
+        self._thread.start()
+
+    def submit(self, source: Path, relative: Path):
+        """Queue a stored file for mirroring.
+
+        Args:
+            source: Where the receiver stored the file.
+            relative: Path of the copy inside the target directory.
+
+        Returns:
+            None: No return value.
+        """
+        self._queue.put((source, relative))
+
+    def close(self) -> dict[str, Any]:
+        """Finish the queued copies and stop the worker.
+
+        Returns:
+            dict[str, Any]: The `target` and how many files were `mirrored`
+            and `failed`.
+        """
+        self._queue.put(None)
+        self._thread.join()
+        return {
+            "target": str(self.target),
+            "mirrored": self.mirrored,
+            "failed": self.failed,
+        }
+
+    def _run(self):
+        """Copy queued files until `close`.
+
+        Returns:
+            None: No return value.
+        """
+        while (job := self._queue.get()) is not None:
+            self._mirror(*job)
+
+    def _mirror(self, source: Path, relative: Path):
+        """Copy one file, retrying, and report the outcome.
+
+        Args:
+            source: The stored file.
+            relative: Path of the copy inside the target directory.
+
+        Returns:
+            None: No return value.
+        """
+        destination = self.target / relative
+        event = {"type": "MIRROR_UPDATE", "filename": relative.name}
+        error = ""
+        for attempt in range(self.retries + 1):
+            if attempt:
+                self._sleep(self.retry_delay)
+            try:
+                self._copy(source, destination)
+            except OSError as e:
+                logger.warning(
+                    f"Mirroring '{relative}' failed (attempt {attempt + 1}): {e}"
+                )
+                error = str(e)
+                continue
+            self.mirrored += 1
+            PacketLogger.emit_event(
+                {**event, "status": "mirrored", "path": str(destination)}
+            )
+            return
+        self.failed += 1
+        PacketLogger.emit_event(
+            {**event, "status": "failed", "attempts": self.retries + 1, "error": error}
+        )
+
+    def _copy(self, source: Path, destination: Path):
+        """Copy a file atomically into place.
+
+        Args:
+            source: The stored file.
+            destination: Final path of the copy.
+
+        Raises:
+            OSError: If the copy fails.
+
+        Returns:
+            None: No return value.
+        """
+        destination.parent.mkdir(parents=True, exist_ok=True)
+        partial = destination.with_name(f".{destination.name}.mirror")
+        try:
+            shutil.copy2(source, partial)
+            os.replace(partial, destination)
+        except OSError:
+            with contextlib.suppress(OSError):
+                partial.unlink(missing_ok=True)
+            raise
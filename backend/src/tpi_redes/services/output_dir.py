@@ -0,0 +1,109 @@
+import json
+import shutil
+import tempfile
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import OUTPUT_DIR_MIN_FREE_BYTES, SETTINGS_PATH
+from tpi_redes.transfer.write_policy import write_atomic
+
+
+@dataclass
+class OutputDirCheck:
+    """Result of validating a candidate output directory.
+
+    Attributes:
+        path (str): The directory, expanded and made absolute.
+        ok (bool): True if received files can be stored there.
+        free_bytes (int | None): Free space on its filesystem, if known.
+        errors (list[dict[str, str]]): Problems found, each with a `code`
+            (`NOT_FOUND`, `NOT_A_DIRECTORY`, `NOT_WRITABLE` or `LOW_SPACE`)
+            and a human-readable `message`.
+    """
+
+    path: str
+    ok: bool = True
+    free_bytes: int | None = None
+    errors: list[dict[str, str]] = field(default_factory=list)
+
+    def add_error(self, code: str, message: str):
+        """Record a problem and mark the directory as unusable.
+
+        Args:
+            code: Machine-readable error code.
+            message: Explanation for the user.
+
+        Returns:
+            None: No return value.
+        """
+        self.ok = False
+        self.errors.append({"code": code, "message": message})
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the result as a JSON-serializable dictionary."""
+        return asdict(self)
+
+
+def validate_output_dir(
+    path: Path, min_free_bytes: int = OUTPUT_DIR_MIN_FREE_BYTES
+) -> OutputDirCheck:
+    """Check that a directory exists, is writable and has free space.
+
+    Writability is tested by creating a temporary file, which also catches
+    read-only mounts and ACLs that permission bits do not show.
+
+    Args:
+        path: The candidate directory.
+        min_free_bytes: Free space below which the directory is rejected.
+
+    Returns:
+        OutputDirCheck: The outcome, with every problem found.
+    """
+    path = path.expanduser().resolve()
+    check = OutputDirCheck(str(path))
+    if not path.exists():
+        check.add_error("NOT_FOUND", f"{path} does not exist")
+        return check
+    if not path.is_dir():
+        check.add_error("NOT_A_DIRECTORY", f"{path} is not a directory")
+        return check
+
+    try:
+        with tempfile.TemporaryFile(dir=path):
+            pass
+    except OSError as e:
+        check.add_error("NOT_WRITABLE", f"Cannot write to {path}: {e.strerror}")
+
+    check.free_bytes = shutil.disk_usage(path).free
+    if check.free_bytes < min_free_bytes:
+        check.add_error(
+            "LOW_SPACE",
+            f"Only {check.free_bytes} bytes free (need {min_free_bytes})",
+        )
+    return check
+
+
+def save_output_dir(path: Path, settings_path: Path = SETTINGS_PATH):
+    """Remember a directory as the default for received files.
+
+    Other settings in the file are preserved, and the file is replaced
+    atomically.
+
+    Args:
+        path: The validated output directory.
+        settings_path: Location of the settings file.
+
+    Returns:
+        None: No return value.
+    """
+    settings: dict[str, Any] = {}
+    if settings_path.exists():
+        try:
+            data = json.loads(settings_path.read_text(encoding="utf-8"))
+            settings = data if isinstance(data, dict) else {}
+        except ValueError:
+            pass
+    settings["output_dir"] = str(path)
+
+    write_atomic(settings_path, json.dumps(settings, indent=2))
@@ -0,0 +1,159 @@
+import base64
+import json
+import logging
+import socket
+from dataclasses import asdict, dataclass, field
+from typing import Any
+
+from tpi_redes.config import PING_TIMEOUT
+from tpi_redes.services.knock import send_knock
+from tpi_redes.transport.ping import ping_peer
+
+logger = logging.getLogger("tpi-redes")
+
+PAIRING_PREFIX = "tpir1:"
+
+
+@dataclass
+class PairingInfo:
+    """What a sender needs to reach a receiver, as shared in a pairing code.
+
+    Attributes:
+        addresses (list[str]): Candidate IPv4 addresses of the receiver, most
+            likely first.
+        port (int): Receiver port.
+        protocol (str): `tcp` or `udp`.
+        hostname (str): Receiver host name, for display.
+        knock (bool): Whether the receiver requires an authorization knock.
+        secret (str | None): Knock secret, only if the receiver chose to
+            share it in the code.
+    """
+
+    addresses: list[str]
+    port: int
+    protocol: str = "tcp"
+    hostname: str = ""
+    knock: bool = False
+    secret: str | None = field(default=None, repr=False)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the info as a JSON-serializable dictionary."""
+        return asdict(self)
+
+
+def local_addresses() -> list[str]:
+    """Return this host's non-loopback IPv4 addresses, most likely first.
+
+    The address of the default route comes first; nothing is sent to find it.
+
+    Returns:
+        list[str]: Candidate addresses, or `["127.0.0.1"]` if none is found.
+    """
+    candidates: list[str] = []
+    try:
+        with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as s:
+            s.connect(("192.0.2.1", 9))
+            candidates.append(s.getsockname()[0])
+    except OSError:
+        pass
+    try:
+        infos = socket.getaddrinfo(socket.gethostname(), None, socket.AF_INET)
+        candidates += [str(info[4][0]) for info in infos]
+    except OSError:
+        pass
+
+    addresses = [ip for ip in dict.fromkeys(candidates) if not ip.startswith("127.")]
+    return addresses or ["127.0.0.1"]
+
+
+def encode_pairing(info: PairingInfo) -> str:
+    """Pack pairing info into a compact string, suitable for a QR code.
+
+    Args:
+        info: The receiver's connection info.
+
+    Returns:
+        str: `tpir1:` followed by base64url-encoded JSON with short keys.
+    """
+    data: dict[str, Any] = {
+        "a": info.addresses,
+        "p": info.port,
+        "t": info.protocol,
+        "h": info.hostname,
+    }
+    if info.knock:
+        data["k"] = 1
+    if info.secret:
+        data["s"] = info.secret
+    raw = json.dumps(data, separators=(",", ":")).encode("utf-8")
+    return PAIRING_PREFIX + base64.urlsafe_b64encode(raw).decode("ascii").rstrip("=")
+
+
+def decode_pairing(payload: str) -> PairingInfo:
+    """Unpack a pairing code produced by `encode_pairing`.
+
+    Args:
+        payload: The pairing code.
+
+    Returns:
+        PairingInfo: The receiver's connection info.
+
+    Raises:
+        ValueError: If the code is malformed or from an unknown version.
+    """
+    payload = payload.strip()
+    if not payload.startswith(PAIRING_PREFIX):
+        raise ValueError("Not a tpi-redes pairing code")
+    encoded = payload[len(PAIRING_PREFIX) :]
+    try:
+        raw = base64.urlsafe_b64decode(encoded + "=" * (-len(encoded) % 4))
+        data = json.loads(raw)
+        info = PairingInfo(
+            addresses=[str(ip) for ip in data["a"]],
+            port=int(data["p"]),
+            protocol=str(data.get("t", "tcp")),
+            hostname=str(data.get("h", "")),
+            knock=bool(data.get("k")),
+            secret=data.get("s"),
+        )
+    except (ValueError, KeyError, TypeError) as e:
+        raise ValueError(f"Invalid pairing code: {e}") from e
+    if not info.addresses or info.protocol not in ("tcp", "udp"):
+        raise ValueError("Invalid pairing code: no address or unknown protocol")
+    return info
+
+
+def connect_from_pairing(
+    payload: str, secret: str | None = None, timeout: float = PING_TIMEOUT
+) -> tuple[str, PairingInfo]:
+    """Find which of a pairing code's addresses reaches the receiver.
+
+    Each candidate is pinged in order; for receivers that require a knock,
+    the candidate is knocked first with the code's secret or `secret`.
+
+    Args:
+        payload: The pairing code.
+        secret: Knock secret to use if the code does not carry one.
+        timeout: Seconds to wait for each candidate.
+
+    Returns:
+        tuple[str, PairingInfo]: The reachable address and the decoded info.
+
+    Raises:
+        ValueError: If the code is malformed.
+        ConnectionError: If no candidate answers.
+    """
+    info = decode_pairing(payload)
+    knock_secret = info.secret or secret
+    for ip in info.addresses:
+        if info.knock and knock_secret:
+            send_knock(ip, knock_secret.encode("utf-8"))
+        result = ping_peer((ip, info.port), info.protocol, timeout)
+        if result.reachable:
+            logger.info(f"Paired with {info.hostname or ip} at {ip}:{info.port}")
+            return ip, info
+        logger.debug(f"Pairing candidate {ip} unreachable: {result.error}")
+    raise ConnectionError(
+        f"No address in the pairing code reached port {info.port} "
+        f"({', '.join(info.addresses)})"
+    )
@@ -0,0 +1,87 @@
+import logging
+import signal
+import threading
+import time
+from types import FrameType, TracebackType
+from typing import Any
+
+logger = logging.getLogger("tpi-redes")
+
+
+class PauseControl:
+    """Pauses and resumes every transfer of the process at chunk boundaries.
+
+    Senders and receivers check `paused` between chunks and block in
+    `wait_resumed` while it is set; TCP connections stay open, so a paused
+    transfer continues where it left off. Used as a context manager on Unix,
+    SIGUSR1 pauses and SIGUSR2 resumes, letting operators throttle the tool
+    (e.g. `kill -USR1 <pid>` during business hours) without killing it.
+    """
+
+    def __init__(self):
+        """Start unpaused, without signal handlers."""
+        self._running = threading.Event()
+        self._running.set()
+        self._previous: dict[int, Any] = {}
+
+    @property
+    def paused(self) -> bool:
+        """Whether transfers are paused."""
+        return not self._running.is_set()
+
+    def pause(self):
+        """Pause all transfers at their next chunk.
+
+        Returns:
+            None: No return value.
+        """
+        self._running.clear()
+
+    def resume(self):
+        """Resume all paused transfers.
+
+        Returns:
+            None: No return value.
+        """
+        self._running.set()
+
+    def wait_resumed(self) -> float:
+        """Block while paused.
+
+        Returns:
+            float: Seconds spent paused.
+        """
+        started = time.monotonic()
+        # Short waits keep Ctrl+C responsive on every platform.
+        while not self._running.wait(0.5):
+            pass
+        return time.monotonic() - started
+
+    def __enter__(self) -> "PauseControl":
+        if hasattr(signal, "SIGUSR1"):
+            for signum, handler in (
+                (signal.SIGUSR1, self._on_pause),
+                (signal.SIGUSR2, self._on_resume),
+            ):
+                self._previous[signum] = signal.signal(signum, handler)
+        return self
+
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_val: BaseException | None,
+        exc_tb: TracebackType | None,
+    ):
+        for signum, handler in self._previous.items():
+            signal.signal(signum, handler)
+        self._previous.clear()
+
+    def _on_pause(self, signum: int, frame: FrameType | None):
+        """SIGUSR1 handler."""
+        logger.info("SIGUSR1 received: pausing transfers.")
+        self.pause()
+
+    def _on_resume(self, signum: int, frame: FrameType | None):
+        """SIGUSR2 handler."""
+        logger.info("SIGUSR2 received: resuming transfers.")
+        self.resume()
@@ -0,0 +1,153 @@
+import json
+from dataclasses import asdict, dataclass, fields
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import DEFAULT_SERVER_PORT, PRESETS_PATH
+from tpi_redes.transfer.write_policy import write_atomic
+
+
+@dataclass
+class Preset:
+    """Named set of `send-file` options for a frequent destination.
+
+    Attributes:
+        name (str): Preset name, e.g. `lab-server`.
+        ip (str): Receiver IP address.
+        port (int): Receiver port.
+        protocol (str): `tcp` or `udp`.
+        chunk_size (int | None): Buffer size in bytes, if not the default.
+        delay (float | None): Delay between chunks, if any.
+        pacing_rate (int | None): UDP send rate in bytes per second.
+        no_pacing (bool): Whether to send UDP at line rate.
+        emit_manifest (bool): Whether to send a SHA256SUMS manifest.
+        sla (float | None): Expected transfer duration in seconds.
+        knock (bool): Whether to knock before sending.
+    """
+
+    name: str
+    ip: str
+    port: int = DEFAULT_SERVER_PORT
+    protocol: str = "tcp"
+    chunk_size: int | None = None
+    delay: float | None = None
+    pacing_rate: int | None = None
+    no_pacing: bool = False
+    emit_manifest: bool = False
+    sla: float | None = None
+    knock: bool = False
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Preset":
+        """Build a preset from stored data, ignoring unknown keys.
+
+        Args:
+            data: Stored preset fields.
+
+        Returns:
+            Preset: The preset.
+
+        Raises:
+            ValueError: If required fields are missing.
+        """
+        known = {f.name for f in fields(cls)}
+        try:
+            return cls(**{k: v for k, v in data.items() if k in known})
+        except TypeError as e:
+            raise ValueError(f"Invalid preset: {e}") from e
+
+
+class PresetStore:
+    """JSON file holding the user's presets, keyed by name.
+
+    Writes go through a temporary file and an atomic rename so a crash never
+    leaves a truncated store behind.
+    """
+
+    def __init__(self, path: Path = PRESETS_PATH):
+        """Initialize the store; the file is created on first save.
+
+        Args:
+            path: Location of the presets file.
+        """
+        self.path = path
+
+    def load(self) -> list[Preset]:
+        """Return all presets, sorted by name.
+
+        Returns:
+            list[Preset]: The stored presets.
+
+        Raises:
+            ValueError: If the file is not a valid presets file.
+        """
+        if not self.path.exists():
+            return []
+        data = json.loads(self.path.read_text(encoding="utf-8"))
+        if not isinstance(data, dict):
+            raise ValueError(f"{self.path}: expected an object of presets")
+        return [
+            Preset.from_dict({**item, "name": name})
+            for name, item in sorted(data.items())
+        ]
+
+    def get(self, name: str) -> Preset:
+        """Return a preset by name.
+
+        Args:
+            name: Preset name.
+
+        Returns:
+            Preset: The stored preset.
+
+        Raises:
+            KeyError: If no preset has that name.
+        """
+        for preset in self.load():
+            if preset.name == name:
+                return preset
+        raise KeyError(name)
+
+    def save(self, preset: Preset):
+        """Create or replace a preset.
+
+        Args:
+            preset: The preset to store.
+
+        Returns:
+            None: No return value.
+        """
+        presets = {p.name: p for p in self.load()}
+        presets[preset.name] = preset
+        self._write(presets)
+
+    def delete(self, name: str):
+        """Remove a preset.
+
+        Args:
+            name: Preset name.
+
+        Raises:
+            KeyError: If no preset has that name.
+
+        Returns:
+            None: No return value.
+        """
+        presets = {p.name: p for p in self.load()}
+        del presets[name]
+        self._write(presets)
+
+    def _write(self, presets: dict[str, Preset]):
+        """Atomically replace the presets file.
+
+        Args:
+            presets: All presets, keyed by name.
+
+        Returns:
+            None: No return value.
+        """
+        data = {
+            name: {k: v for k, v in asdict(p).items() if k != "name"}
+            for name, p in sorted(presets.items())
+        }
+        write_atomic(self.path, json.dumps(data, indent=2))
@@ -3,7 +3,8 @@ import random
 import socket
 import threading
 
-from tpi_redes.config import CHUNK_SIZE, MAX_UDP_PACKET_SIZE
+from tpi_redes.config import CHUNK_SIZE, MAX_UDP_PACKET_SIZE, UDP_DISABLED
+from tpi_redes.core.errors import ProtocolDisabledError
 from tpi_redes.observability.packet_logger import PacketLogger
 
 logger = logging.getLogger("tpi-redes")
@@ -35,7 +36,13 @@ class ProxyServer:
             corruption_rate: Probability (0.0 to 1.0) of corrupting a packet chunk.
             interface: Network interface to bind/sniff on.
             protocol: Protocol to proxy ('tcp' or 'udp').
+
+        Raises:
+            ProtocolDisabledError: If UDP is requested but disabled by config.
         """
+        if protocol.lower() == "udp" and UDP_DISABLED:
+            raise ProtocolDisabledError("udp")
+
         self.listen_port = listen_port
         self.target_ip = target_ip
         self.target_port = target_port
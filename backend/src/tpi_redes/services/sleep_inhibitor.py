@@ -0,0 +1,164 @@
+import json
+import logging
+import os
+import shutil
+import subprocess
+import sys
+import threading
+from types import TracebackType
+
+logger = logging.getLogger("tpi-redes")
+
+ES_CONTINUOUS = 0x80000000
+ES_SYSTEM_REQUIRED = 0x00000001
+
+
+class SleepInhibitor:
+    """Keeps the operating system awake while transfers are active.
+
+    Uses the native mechanism of each platform:
+    - Linux: a `systemd-inhibit` child process holding a sleep/idle lock.
+    - macOS: a `caffeinate -i` child process.
+    - Windows: `SetThreadExecutionState` with `ES_SYSTEM_REQUIRED`.
+
+    Acquisitions are reference counted so overlapping transfers share a single
+    inhibitor, which is released when the last one finishes. A `SLEEP_INHIBIT`
+    event is emitted whenever inhibition starts or stops.
+    """
+
+    def __init__(self, reason: str = "File transfer in progress"):
+        """Initialize the inhibitor without acquiring it.
+
+        Args:
+            reason: Explanation shown by the OS to users inspecting the lock.
+        """
+        self.reason = reason
+        self.method: str | None = None
+        self._count = 0
+        self._lock = threading.Lock()
+        self._process: subprocess.Popen[bytes] | None = None
+
+    @property
+    def active(self) -> bool:
+        """Whether sleep is currently being inhibited."""
+        return self.method is not None
+
+    def acquire(self):
+        """Register an active transfer, inhibiting sleep if it is the first.
+
+        Platforms without a supported mechanism only log a warning; the
+        transfer itself is never affected.
+
+        Returns:
+            None: No return value.
+        """
+        with self._lock:
+            self._count += 1
+            if self._count > 1:
+                return
+            try:
+                self.method = self._start()
+            except OSError as e:
+                logger.warning(f"Could not inhibit system sleep: {e}")
+                self.method = None
+            if self.method:
+                logger.info(f"System sleep inhibited via {self.method}.")
+                self._emit(True)
+            else:
+                logger.warning("System sleep inhibition unsupported on this host.")
+
+    def release(self):
+        """Unregister an active transfer, allowing sleep once none remain.
+
+        Returns:
+            None: No return value.
+        """
+        with self._lock:
+            if self._count == 0:
+                return
+            self._count -= 1
+            if self._count > 0 or not self.method:
+                return
+            self._stop()
+            logger.info("System sleep no longer inhibited.")
+            self._emit(False)
+            self.method = None
+
+    def __enter__(self) -> "SleepInhibitor":
+        self.acquire()
+        return self
+
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc: BaseException | None,
+        tb: TracebackType | None,
+    ):
+        self.release()
+
+    def _start(self) -> str | None:
+        """Start the platform mechanism.
+
+        Returns:
+            str | None: Name of the mechanism in use, or None if unsupported.
+        """
+        if sys.platform == "win32":
+            import ctypes
+
+            ctypes.windll.kernel32.SetThreadExecutionState(
+                ES_CONTINUOUS | ES_SYSTEM_REQUIRED
+            )
+            return "SetThreadExecutionState"
+
+        if sys.platform == "darwin":
+            cmd = ["caffeinate", "-i", "-w", str(os.getpid())]
+        else:
+            cmd = [
+                "systemd-inhibit",
+                "--what=sleep:idle",
+                "--who=tpi-redes",
+                f"--why={self.reason}",
+                "--mode=block",
+                "sleep",
+                "infinity",
+            ]
+
+        if shutil.which(cmd[0]) is None:
+            return None
+        self._process = subprocess.Popen(
+            cmd, stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL
+        )
+        return cmd[0]
+
+    def _stop(self):
+        """Stop the platform mechanism started by `_start`.
+
+        Returns:
+            None: No return value.
+        """
+        if sys.platform == "win32":
+            import ctypes
+
+            ctypes.windll.kernel32.SetThreadExecutionState(ES_CONTINUOUS)
+            return
+
+        if self._process:
+            self._process.terminate()
+            self._process.wait()
+            self._process = None
+
+    def _emit(self, active: bool):
+        """Emit a SLEEP_INHIBIT event to stdout for the frontend.
+
+        Args:
+            active: Whether inhibition is now active.
+
+        Returns:
+            None: No return value.
+        """
+        print(
+            json.dumps(
+                {"type": "SLEEP_INHIBIT", "active": active, "method": self.method}
+            ),
+            flush=True,
+        )
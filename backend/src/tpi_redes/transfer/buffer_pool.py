@@ -0,0 +1,86 @@
+import threading
+from typing import Any
+
+from tpi_redes.config import BUFFER_POOL_BUFFERS, READAHEAD_BUDGET
+
+
+class BufferPool:
+    """Reusable chunk buffers for one transfer.
+
+    Reading every chunk into a fresh `bytes` object allocates and frees
+    `chunk_size` bytes per chunk, which adds up over multi-hour transfers.
+    The pool keeps up to `capacity` buffers and hands them out again once
+    released. When every pooled buffer is in use, `acquire` still returns a
+    new one, so a small pool only costs allocations, never a stall.
+    """
+
+    def __init__(
+        self,
+        buffer_size: int,
+        capacity: int = BUFFER_POOL_BUFFERS,
+        budget: int = READAHEAD_BUDGET,
+    ):
+        """Create an empty pool.
+
+        Args:
+            buffer_size: Size of each buffer, the transfer's chunk size.
+            capacity: Buffers kept for reuse; 0 sizes the pool to the
+                read-ahead window (`budget` bytes of chunks plus the one being
+                sent and the one being read).
+            budget: Read-ahead budget used to size the pool by default.
+        """
+        self.buffer_size = buffer_size
+        self.capacity = capacity or budget // buffer_size + 2
+        self._free: list[bytearray] = []
+        self._lock = threading.Lock()
+        self._allocated = 0
+        self._reused = 0
+        self._in_use = 0
+        self._peak_in_use = 0
+
+    def acquire(self) -> bytearray:
+        """Take a buffer, reusing a released one if possible.
+
+        Returns:
+            bytearray: A buffer of `buffer_size` bytes with stale content.
+        """
+        with self._lock:
+            self._in_use += 1
+            self._peak_in_use = max(self._peak_in_use, self._in_use)
+            if self._free:
+                self._reused += 1
+                return self._free.pop()
+            self._allocated += 1
+        return bytearray(self.buffer_size)
+
+    def release(self, buffer: bytearray):
+        """Give a buffer back; it is dropped if the pool is already full.
+
+        Args:
+            buffer: A buffer from `acquire`, no longer referenced elsewhere.
+
+        Returns:
+            None: No return value.
+        """
+        with self._lock:
+            self._in_use -= 1
+            if len(self._free) < self.capacity:
+                self._free.append(buffer)
+
+    def stats(self) -> dict[str, Any]:
+        """Return the pool's counters.
+
+        Returns:
+            dict[str, Any]: `buffer_size`, `capacity`, buffers `allocated`,
+            acquisitions served by `reused` buffers, and `in_use` and
+            `peak_in_use` buffers.
+        """
+        with self._lock:
+            return {
+                "buffer_size": self.buffer_size,
+                "capacity": self.capacity,
+                "allocated": self._allocated,
+                "reused": self._reused,
+                "in_use": self._in_use,
+                "peak_in_use": self._peak_in_use,
+            }
@@ -0,0 +1,77 @@
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import BUNDLE_MAX_BYTES
+
+
+def bundle_prefix(
+    files: list[Path], sizes: dict[Path, int], threshold: int
+) -> list[Path]:
+    """Pick the leading files that can be sent together in one bundle.
+
+    Args:
+        files: Files still to send, in sending order.
+        sizes: Bytes to send per file.
+        threshold: Largest file size that may be bundled.
+
+    Returns:
+        list[Path]: The longest run of small files at the start of `files`
+        whose sizes fit in `BUNDLE_MAX_BYTES`, or an empty list when fewer
+        than two files qualify (a lone file gains nothing from a bundle).
+    """
+    bundle: list[Path] = []
+    total = 0
+    for f in files:
+        if sizes[f] > threshold or total + sizes[f] > BUNDLE_MAX_BYTES:
+            break
+        bundle.append(f)
+        total += sizes[f]
+    return bundle if len(bundle) > 1 else []
+
+
+class BundleReader:
+    """A connection that yields received bundles before the socket itself.
+
+    A bundle carries ordinary file messages back to back. Once the receiver
+    has verified one, it feeds the payload here so the messages are read and
+    handled exactly like those arriving on the socket; when the payload runs
+    out, reads go to the connection again. Everything else is delegated to
+    the wrapped connection.
+    """
+
+    def __init__(self, conn: Any):
+        """Wrap a connection with no pending bundle.
+
+        Args:
+            conn: The accepted socket object.
+        """
+        self.conn = conn
+        self._pending = memoryview(b"")
+
+    def feed(self, payload: bytes):
+        """Queue a verified bundle's messages for reading.
+
+        Args:
+            payload: The bundle's content.
+
+        Returns:
+            None: No return value.
+        """
+        self._pending = memoryview(bytes(self._pending) + payload)
+
+    def recv(self, bufsize: int) -> bytes:
+        """Read from the pending bundle, or from the connection when empty.
+
+        Args:
+            bufsize: Maximum number of bytes to return.
+
+        Returns:
+            bytes: The data read, empty on EOF.
+        """
+        if not self._pending:
+            return self.conn.recv(bufsize)
+        data, self._pending = self._pending[:bufsize], self._pending[bufsize:]
+        return bytes(data)
+
+    def __getattr__(self, name: str) -> Any:
+        return getattr(self.conn, name)
@@ -0,0 +1,140 @@
+import json
+import logging
+import time
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import CHECKSUM_CACHE_MAX_ENTRIES, CHECKSUM_CACHE_PATH
+from tpi_redes.transfer.integrity import IntegrityVerifier
+from tpi_redes.transfer.write_policy import write_atomic
+
+logger = logging.getLogger("tpi-redes")
+
+# Files modified this recently are not cached: a write within the same mtime
+# tick would leave size and mtime unchanged, making a stale entry look valid.
+RACY_MTIME_SECONDS = 2.0
+
+
+class ChecksumCache:
+    """Local store of source file checksums keyed by path, size and mtime.
+
+    Re-sending an unchanged file reuses its checksum instead of reading the
+    whole file again before the transfer. An entry is discarded as soon as the
+    file's size or modification time changes. The least recently used entries
+    are evicted beyond `max_entries`.
+    """
+
+    def __init__(
+        self,
+        path: Path = CHECKSUM_CACHE_PATH,
+        max_entries: int = CHECKSUM_CACHE_MAX_ENTRIES,
+    ):
+        """Load the cache; a missing or unreadable file starts it empty.
+
+        Args:
+            path: Location of the cache file.
+            max_entries: Maximum number of files remembered.
+        """
+        self.path = path
+        self.max_entries = max_entries
+        self.hits = 0
+        self.misses = 0
+        self.invalidated = 0
+        self._dirty = False
+        self._entries: dict[str, dict[str, Any]] = {}
+        try:
+            data = json.loads(path.read_text(encoding="utf-8"))
+            if isinstance(data, dict):
+                self._entries = data
+        except FileNotFoundError:
+            pass
+        except (OSError, ValueError) as e:
+            logger.warning(f"Ignoring unreadable checksum cache {path}: {e}")
+
+    def checksum(self, file_path: Path, limit: int | None = None) -> str:
+        """Return the SHA-256 of a file, from the cache when still valid.
+
+        Args:
+            file_path: File to hash.
+            limit: Optional number of leading bytes to hash.
+
+        Returns:
+            str: Hex SHA-256 digest.
+        """
+        key = str(file_path.resolve())
+        stat = file_path.stat()
+        entry = self._entries.pop(key, None)
+
+        if entry is not None:
+            if (
+                entry.get("size") == stat.st_size
+                and entry.get("mtime_ns") == stat.st_mtime_ns
+                and entry.get("limit") == limit
+            ):
+                self.hits += 1
+                self._entries[key] = entry
+                return entry["sha256"]
+            self.invalidated += 1
+            self._dirty = True
+
+        self.misses += 1
+        digest = IntegrityVerifier(file_path).calculate_hash(limit=limit)
+        if time.time() - stat.st_mtime >= RACY_MTIME_SECONDS:
+            self._entries[key] = {
+                "size": stat.st_size,
+                "mtime_ns": stat.st_mtime_ns,
+                "limit": limit,
+                "sha256": digest,
+            }
+            while len(self._entries) > self.max_entries:
+                del self._entries[next(iter(self._entries))]
+            self._dirty = True
+        return digest
+
+    def stats(self) -> dict[str, int]:
+        """Return counters for this session and the number of stored entries.
+
+        Returns:
+            dict[str, int]: `hits`, `misses`, `invalidated` and `entries`.
+        """
+        return {
+            "hits": self.hits,
+            "misses": self.misses,
+            "invalidated": self.invalidated,
+            "entries": len(self._entries),
+        }
+
+    def save(self):
+        """Persist the cache if it changed, replacing the file atomically.
+
+        Failures are logged and otherwise ignored; the cache is an
+        optimization only.
+
+        Returns:
+            None: No return value.
+        """
+        if not self._dirty:
+            return
+        try:
+            write_atomic(self.path, json.dumps(self._entries))
+            self._dirty = False
+        except OSError as e:
+            logger.warning(f"Could not save checksum cache {self.path}: {e}")
+
+
+def file_checksum(
+    file_path: Path, limit: int | None = None, cache: ChecksumCache | None = None
+) -> str:
+    """Hash a file, through the checksum cache when one is given.
+
+    Args:
+        file_path: File to hash.
+        limit: Optional number of leading bytes to hash.
+        cache: Optional checksum cache.
+
+    Returns:
+        str: Hex SHA-256 digest.
+    """
+    if cache:
+        return cache.checksum(file_path, limit)
+    return IntegrityVerifier(file_path).calculate_hash(limit=limit)
@@ -0,0 +1,96 @@
+import os
+import shutil
+from pathlib import Path
+
+from tpi_redes.config import STAGING_PREFIX
+
+
+class ContentIndex:
+    """Finds files already received with a given checksum.
+
+    Built from the `.sha256` sidecars under a directory on first use and
+    updated as files are stored. Entries whose file is gone or has another
+    size are dropped on lookup, so files deleted behind the index's back are
+    never linked.
+    """
+
+    def __init__(self, root: Path):
+        """Initialize the index.
+
+        Args:
+            root: Directory whose received files are indexed (recursively).
+        """
+        self.root = root
+        self._entries: dict[str, Path] | None = None
+
+    def lookup(self, sha256: str, size: int) -> Path | None:
+        """Return a stored file with this checksum and size, if any.
+
+        Args:
+            sha256: SHA-256 hex digest announced by the sender.
+            size: Size announced by the sender.
+
+        Returns:
+            Path | None: An existing file with the same content, or None.
+        """
+        entries = self._load()
+        path = entries.get(sha256)
+        if path is None:
+            return None
+        try:
+            if path.is_file() and path.stat().st_size == size:
+                return path
+        except OSError:
+            pass
+        del entries[sha256]
+        return None
+
+    def add(self, sha256: str, path: Path):
+        """Record a newly stored file.
+
+        Args:
+            sha256: SHA-256 hex digest of the file.
+            path: Where the file is stored.
+
+        Returns:
+            None: No return value.
+        """
+        self._load()[sha256] = path
+
+    def _load(self) -> dict[str, Path]:
+        """Scan the sidecars under the root the first time they are needed.
+
+        Returns:
+            dict[str, Path]: Stored file per checksum.
+        """
+        if self._entries is None:
+            self._entries = {}
+            for sidecar in self.root.rglob("*.sha256"):
+                if any(part.startswith(STAGING_PREFIX) for part in sidecar.parts):
+                    continue
+                try:
+                    digest = sidecar.read_text(encoding="utf-8").strip()
+                except OSError:
+                    continue
+                self._entries.setdefault(digest, sidecar.with_suffix(""))
+        return self._entries
+
+
+def link_or_copy(source: Path, target: Path):
+    """Make `target` a hard link to `source`, copying if linking fails.
+
+    Hard links share their data: editing one name changes the other. Copying
+    is the fallback across filesystems or where links are not supported.
+
+    Args:
+        source: Existing file with the wanted content.
+        target: Path to create (replaced if it exists).
+
+    Returns:
+        None: No return value.
+    """
+    target.unlink(missing_ok=True)
+    try:
+        os.link(source, target)
+    except OSError:
+        shutil.copy2(source, target)
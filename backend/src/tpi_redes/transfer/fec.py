@@ -0,0 +1,194 @@
+import struct
+
+from tpi_redes.core.protocol import ProtocolHandler
+
+# Groups this far behind the newest one are dropped from the decoder; by then
+# their parity frame has long arrived or was lost too.
+_GROUP_HORIZON = 64
+
+
+def _xor(blocks: list[bytes]) -> bytes:
+    """XOR byte strings together, zero-padding them to the longest.
+
+    Args:
+        blocks: The byte strings.
+
+    Returns:
+        bytes: Their XOR, as long as the longest input.
+    """
+    size = max(len(b) for b in blocks)
+    acc = 0
+    for block in blocks:
+        acc ^= int.from_bytes(block.ljust(size, b"\x00"))
+    return acc.to_bytes(size)
+
+
+class FecEncoder:
+    """Frames file content for UDP with one XOR parity frame per group.
+
+    Each data frame carries its block (offset, length and content); after
+    every `group_size` data frames a parity frame carries the XOR of their
+    blocks, so the receiver can rebuild any single lost frame of the group.
+    The redundancy is therefore one extra datagram per `group_size`.
+
+    Attributes:
+        group_size (int): Data frames per parity frame.
+        data_frames (int): Data frames produced so far.
+        parity_frames (int): Parity frames produced so far.
+    """
+
+    def __init__(self, group_size: int):
+        self.group_size = group_size
+        self.data_frames = 0
+        self.parity_frames = 0
+        self._group = 0
+        self._blocks: list[bytes] = []
+
+    def encode(self, offset: int, data: bytes) -> list[bytes]:
+        """Frame one chunk, followed by the group's parity once it is full.
+
+        Args:
+            offset: Position of the chunk in the file.
+            data: The chunk's content.
+
+        Returns:
+            list[bytes]: Datagrams to send, in order.
+        """
+        block = struct.pack(ProtocolHandler.FEC_BLOCK_FORMAT, offset, len(data)) + data
+        frames = [
+            ProtocolHandler.pack_fec_frame(
+                ProtocolHandler.FEC_DATA_OP_CODE, self._group, len(self._blocks), block
+            )
+        ]
+        self.data_frames += 1
+        self._blocks.append(block)
+        if len(self._blocks) == self.group_size:
+            frames += self.flush()
+        return frames
+
+    def flush(self) -> list[bytes]:
+        """Close the current group, e.g. after the file's last chunk.
+
+        Returns:
+            list[bytes]: The group's parity frame, or nothing if it is empty.
+        """
+        if not self._blocks:
+            return []
+        parity = ProtocolHandler.pack_fec_frame(
+            ProtocolHandler.FEC_PARITY_OP_CODE,
+            self._group,
+            len(self._blocks),
+            _xor(self._blocks),
+        )
+        self.parity_frames += 1
+        self._group += 1
+        self._blocks = []
+        return [parity]
+
+
+class FecDecoder:
+    """Reassembles FEC frames into in-order content, repairing single losses.
+
+    Attributes:
+        next_offset (int): Bytes of contiguous content released so far.
+        repaired (int): Data frames rebuilt from parity.
+    """
+
+    def __init__(self):
+        self.next_offset = 0
+        self.repaired = 0
+        self._groups: dict[int, dict[int, bytes]] = {}
+        self._parity: dict[int, tuple[int, bytes]] = {}
+        self._pending: dict[int, bytes] = {}
+
+    def add(self, datagram: bytes) -> list[bytes]:
+        """Accept one content datagram.
+
+        Args:
+            datagram: An FEC frame as received.
+
+        Returns:
+            list[bytes]: Content that became contiguous, in file order.
+
+        Raises:
+            ValueError: If the datagram is not a valid FEC frame.
+        """
+        frame = ProtocolHandler.unpack_fec_frame(datagram)
+        group = self._groups.setdefault(frame.group, {})
+        if frame.op_code == ProtocolHandler.FEC_PARITY_OP_CODE:
+            self._parity[frame.group] = (frame.index, frame.body)
+        elif frame.index not in group:
+            group[frame.index] = frame.body
+            self._accept(frame.body)
+
+        self._repair(frame.group)
+        self._forget_before(frame.group - _GROUP_HORIZON)
+        return self._drain()
+
+    @property
+    def buffered_bytes(self) -> int:
+        """Content received out of order and waiting for a gap to fill."""
+        return sum(len(d) for d in self._pending.values())
+
+    def _repair(self, group_id: int):
+        """Rebuild the group's missing data frame if exactly one is missing.
+
+        Args:
+            group_id: The group to check.
+
+        Returns:
+            None: No return value.
+        """
+        if group_id not in self._parity:
+            return
+        count, parity = self._parity[group_id]
+        group = self._groups[group_id]
+        missing = [i for i in range(count) if i not in group]
+        if len(missing) == 1:
+            block = _xor([parity, *group.values()])
+            self.repaired += 1
+            self._accept(block)
+        if len(missing) <= 1:
+            del self._groups[group_id]
+            del self._parity[group_id]
+
+    def _accept(self, block: bytes):
+        """Queue a data block's content for in-order release.
+
+        Args:
+            block: Offset, length and content of one chunk.
+
+        Returns:
+            None: No return value.
+        """
+        header_size = ProtocolHandler.FEC_BLOCK_HEADER_SIZE
+        offset, length = struct.unpack(
+            ProtocolHandler.FEC_BLOCK_FORMAT, block[:header_size]
+        )
+        if offset >= self.next_offset:
+            self._pending[offset] = block[header_size : header_size + length]
+
+    def _drain(self) -> list[bytes]:
+        """Release the content that now follows `next_offset` without gaps.
+
+        Returns:
+            list[bytes]: The released chunks, in order.
+        """
+        chunks: list[bytes] = []
+        while (data := self._pending.pop(self.next_offset, None)) is not None:
+            chunks.append(data)
+            self.next_offset += len(data)
+        return chunks
+
+    def _forget_before(self, group_id: int):
+        """Drop state of groups that can no longer be repaired.
+
+        Args:
+            group_id: Groups below this one are dropped.
+
+        Returns:
+            None: No return value.
+        """
+        for stale in [g for g in self._groups if g < group_id]:
+            self._groups.pop(stale)
+            self._parity.pop(stale, None)
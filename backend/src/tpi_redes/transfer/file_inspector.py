@@ -0,0 +1,228 @@
+import codecs
+import mimetypes
+import struct
+from dataclasses import asdict, dataclass
+from pathlib import Path
+from typing import Any
+
+# Bytes read from the start of the file; enough for every signature below
+# and for most JPEG headers.
+INSPECT_READ_BYTES = 64 * 1024
+
+_SIGNATURES: list[tuple[bytes, str]] = [
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"BM", "image/bmp"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (b"\x7fELF", "application/x-executable"),
+    (b"ID3", "audio/mpeg"),
+    (b"OggS", "audio/ogg"),
+    (b"fLaC", "audio/flac"),
+]
+
+_BOMS: list[tuple[bytes, str]] = [
+    (codecs.BOM_UTF8, "utf-8-sig"),
+    (codecs.BOM_UTF32_LE, "utf-32-le"),
+    (codecs.BOM_UTF32_BE, "utf-32-be"),
+    (codecs.BOM_UTF16_LE, "utf-16-le"),
+    (codecs.BOM_UTF16_BE, "utf-16-be"),
+]
+
+
+@dataclass
+class FileInfo:
+    """Lightweight preview metadata for a file card in the UI.
+
+    Attributes:
+        name (str): File name.
+        size (int): Size in bytes.
+        mime (str): MIME type from magic bytes, else from the extension.
+        width (int | None): Image width in pixels, if known.
+        height (int | None): Image height in pixels, if known.
+        duration (float | None): Media duration in seconds, if known.
+        encoding (str | None): Text encoding, or None for binary files.
+    """
+
+    name: str
+    size: int
+    mime: str
+    width: int | None = None
+    height: int | None = None
+    duration: float | None = None
+    encoding: str | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        """Serialize the metadata for JSON events.
+
+        Returns:
+            dict[str, Any]: The metadata fields.
+        """
+        return asdict(self)
+
+
+def inspect_file(path: Path) -> FileInfo:
+    """Extract preview metadata from the start of a file.
+
+    Only the first `INSPECT_READ_BYTES` are read, so inspecting large files
+    is cheap; fields that cannot be determined from them are left as None.
+    FIFOs and devices are not read at all, since what is read from them
+    would be missing from the transfer.
+
+    Args:
+        path: File to inspect.
+
+    Returns:
+        FileInfo: The extracted metadata.
+
+    Raises:
+        OSError: If the file cannot be read.
+    """
+    if path.is_fifo() or path.is_char_device() or path.is_block_device():
+        return FileInfo(name=path.name, size=0, mime="application/octet-stream")
+    with open(path, "rb") as f:
+        head = f.read(INSPECT_READ_BYTES)
+
+    sniffed = _sniff_mime(head)
+    info = FileInfo(
+        name=path.name,
+        size=path.stat().st_size,
+        mime=sniffed
+        or mimetypes.guess_type(path.name)[0]
+        or "application/octet-stream",
+    )
+
+    dimensions = _image_dimensions(head, info.mime)
+    if dimensions:
+        info.width, info.height = dimensions
+    if info.mime == "audio/wav":
+        info.duration = _wav_duration(head)
+    if not sniffed:
+        info.encoding = _text_encoding(head)
+        if info.encoding and info.mime == "application/octet-stream":
+            info.mime = "text/plain"
+    return info
+
+
+def _sniff_mime(head: bytes) -> str | None:
+    """Identify a file type from its leading magic bytes.
+
+    Args:
+        head: Leading bytes of the file.
+
+    Returns:
+        str | None: The MIME type, or None if no signature matches.
+    """
+    for signature, mime in _SIGNATURES:
+        if head.startswith(signature):
+            return mime
+    if head[:4] == b"RIFF" and head[8:12] == b"WAVE":
+        return "audio/wav"
+    if head[:4] == b"RIFF" and head[8:12] == b"WEBP":
+        return "image/webp"
+    if head[4:8] == b"ftyp":
+        return "video/mp4"
+    return None
+
+
+def _image_dimensions(head: bytes, mime: str) -> tuple[int, int] | None:
+    """Read image width and height from the file header.
+
+    Args:
+        head: Leading bytes of the file.
+        mime: The file's MIME type.
+
+    Returns:
+        tuple[int, int] | None: (width, height), or None if not found.
+    """
+    try:
+        if mime == "image/png" and head[12:16] == b"IHDR":
+            width, height = struct.unpack(">II", head[16:24])
+            return width, height
+        if mime == "image/gif":
+            width, height = struct.unpack("<HH", head[6:10])
+            return width, height
+        if mime == "image/bmp":
+            width, height = struct.unpack("<ii", head[18:26])
+            return width, abs(height)
+        if mime == "image/jpeg":
+            return _jpeg_dimensions(head)
+    except struct.error:
+        pass
+    return None
+
+
+def _jpeg_dimensions(head: bytes) -> tuple[int, int] | None:
+    """Find the frame size in a JPEG's start-of-frame segment.
+
+    Args:
+        head: Leading bytes of the file.
+
+    Returns:
+        tuple[int, int] | None: (width, height), or None if not found.
+
+    Raises:
+        struct.error: If a segment header is truncated.
+    """
+    pos = 2
+    while pos + 4 <= len(head):
+        if head[pos] != 0xFF:
+            return None
+        marker = head[pos + 1]
+        length = struct.unpack(">H", head[pos + 2 : pos + 4])[0]
+        # SOF0-SOF15, except DHT (C4), JPG (C8) and DAC (CC).
+        if 0xC0 <= marker <= 0xCF and marker not in (0xC4, 0xC8, 0xCC):
+            height, width = struct.unpack(">HH", head[pos + 5 : pos + 9])
+            return width, height
+        pos += 2 + length
+    return None
+
+
+def _wav_duration(head: bytes) -> float | None:
+    """Compute a WAV file's duration from its fmt and data chunks.
+
+    Args:
+        head: Leading bytes of the file.
+
+    Returns:
+        float | None: Duration in seconds, or None if not found.
+    """
+    byte_rate = None
+    pos = 12
+    while pos + 8 <= len(head):
+        chunk_id = head[pos : pos + 4]
+        chunk_size = struct.unpack("<I", head[pos + 4 : pos + 8])[0]
+        if chunk_id == b"fmt " and pos + 20 <= len(head):
+            byte_rate = struct.unpack("<I", head[pos + 16 : pos + 20])[0]
+        elif chunk_id == b"data":
+            return round(chunk_size / byte_rate, 3) if byte_rate else None
+        pos += 8 + chunk_size + (chunk_size & 1)
+    return None
+
+
+def _text_encoding(head: bytes) -> str | None:
+    """Guess the encoding of a text file.
+
+    Args:
+        head: Leading bytes of the file.
+
+    Returns:
+        str | None: `utf-8`, a BOM-marked Unicode encoding, or None if the
+        content looks binary.
+    """
+    for bom, encoding in _BOMS:
+        if head.startswith(bom):
+            return encoding
+    if b"\x00" in head:
+        return None
+    try:
+        head.decode("utf-8")
+    except UnicodeDecodeError as e:
+        # The read may have cut a multi-byte character at the end.
+        if e.start < len(head) - 3:
+            return None
+    return "utf-8"
@@ -0,0 +1,77 @@
+import stat
+from pathlib import Path
+
+
+class InvalidFileError(ValueError):
+    """Raised when a path cannot be transferred as a regular file."""
+
+
+class FileValidator:
+    """Checks that a source path is safe to send and decides how much to read.
+
+    Regular files are accepted as they are. FIFOs and devices have no
+    meaningful size and reading them may never reach EOF (e.g. `/dev/zero`),
+    so they are only accepted with an explicit cap on the bytes to read;
+    directories and sockets are always refused.
+    """
+
+    _KINDS = (
+        (stat.S_ISDIR, "directory"),
+        (stat.S_ISFIFO, "FIFO"),
+        (stat.S_ISCHR, "character device"),
+        (stat.S_ISBLK, "block device"),
+        (stat.S_ISSOCK, "socket"),
+    )
+
+    def __init__(self, file_path: Path):
+        self._file_path = file_path
+
+    def validate(self, max_read_bytes: int | None = None) -> int:
+        """Ensure the path can be sent.
+
+        Args:
+            max_read_bytes: Optional cap on the number of bytes to send; FIFOs
+                and devices are refused without one.
+
+        Returns:
+            int: The current size of a regular file in bytes, or
+            `max_read_bytes` for a FIFO or device.
+
+        Raises:
+            FileNotFoundError: If the path does not exist.
+            InvalidFileError: If the path cannot be sent.
+        """
+        st = self._file_path.stat()
+        if stat.S_ISREG(st.st_mode):
+            return st.st_size
+        kind = next(
+            (name for check, name in self._KINDS if check(st.st_mode)), "special file"
+        )
+        if kind in ("FIFO", "character device", "block device"):
+            if max_read_bytes is not None:
+                return max_read_bytes
+            raise InvalidFileError(
+                f"'{self._file_path}' is a {kind}, not a regular file; "
+                "refusing to send it without --max-read-bytes"
+            )
+        raise InvalidFileError(
+            f"'{self._file_path}' is a {kind}, not a regular file; "
+            "refusing to send it"
+        )
+
+    def transfer_size(self, max_read_bytes: int | None = None) -> int:
+        """Return how many bytes of the file will be sent.
+
+        The size is fixed when the header is built, so a file that keeps
+        growing (e.g. a log) is only sent up to this point.
+
+        Args:
+            max_read_bytes: Optional cap on the number of bytes to send.
+
+        Returns:
+            int: The number of bytes to read and announce in the header.
+        """
+        size = self.validate(max_read_bytes)
+        if max_read_bytes is not None:
+            size = min(size, max_read_bytes)
+        return size
@@ -6,12 +6,24 @@ class IntegrityVerifier:
     def __init__(self, file_path: Path):
         self._file_path = file_path
 
-    def calculate_hash(self) -> str:
-        """Calculate SHA-256 hash of the file."""
+    def calculate_hash(self, limit: int | None = None) -> str:
+        """Calculate SHA-256 hash of the file.
+
+        Args:
+            limit: Optional number of leading bytes to hash instead of the
+                whole file.
+        """
         sha256_hash = hashlib.sha256()
+        remaining = limit
         with open(self._file_path, "rb") as f:
-            for byte_block in iter(lambda: f.read(4096), b""):
+            while remaining is None or remaining > 0:
+                block_size = 4096 if remaining is None else min(4096, remaining)
+                byte_block = f.read(block_size)
+                if not byte_block:
+                    break
                 sha256_hash.update(byte_block)
+                if remaining is not None:
+                    remaining -= len(byte_block)
         return sha256_hash.hexdigest()
 
     def save_hash_file(self) -> Path:
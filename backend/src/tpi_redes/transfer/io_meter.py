@@ -0,0 +1,74 @@
+import time
+from collections.abc import Callable
+
+
+class IoMeter:
+    """Splits a receive's time between the network and the destination disk.
+
+    Each rate is bytes over the time actually spent in that stage, so a slow
+    network shows as a low `network_rate` while `disk_rate` stays high, and
+    the other way round when the destination disk is the bottleneck.
+    """
+
+    def __init__(self, clock: Callable[[], float] = time.perf_counter):
+        """Start with empty counters.
+
+        Args:
+            clock: High-resolution time source.
+        """
+        self.clock = clock
+        self.network_bytes = 0
+        self.network_seconds = 0.0
+        self.disk_bytes = 0
+        self.disk_seconds = 0.0
+
+    def network(self, nbytes: int, started: float):
+        """Account for bytes read from the socket.
+
+        Args:
+            nbytes: Bytes received.
+            started: `clock()` value taken before the read.
+
+        Returns:
+            None: No return value.
+        """
+        self.network_bytes += nbytes
+        self.network_seconds += self.clock() - started
+
+    def disk(self, nbytes: int, started: float):
+        """Account for bytes written to the destination.
+
+        Args:
+            nbytes: Bytes written.
+            started: `clock()` value taken before the write.
+
+        Returns:
+            None: No return value.
+        """
+        self.disk_bytes += nbytes
+        self.disk_seconds += self.clock() - started
+
+    def rates(self) -> dict[str, int | None]:
+        """Return both rates for progress and completion events.
+
+        Returns:
+            dict[str, int | None]: `network_rate` and `disk_rate` in bytes
+            per second, None while a stage has no measurable time yet.
+        """
+        return {
+            "network_rate": _rate(self.network_bytes, self.network_seconds),
+            "disk_rate": _rate(self.disk_bytes, self.disk_seconds),
+        }
+
+
+def _rate(nbytes: int, seconds: float) -> int | None:
+    """Return bytes per second, or None if no time was measured.
+
+    Args:
+        nbytes: Bytes processed.
+        seconds: Time spent processing them.
+
+    Returns:
+        int | None: The rounded rate.
+    """
+    return round(nbytes / seconds) if seconds > 0 else None
@@ -0,0 +1,128 @@
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from tpi_redes.transfer.integrity import IntegrityVerifier
+
+MANIFEST_NAME = "SHA256SUMS"
+
+
+def build_manifest(hashes: dict[str, str]) -> str:
+    """Render per-file checksums in `sha256sum` format.
+
+    Args:
+        hashes: Mapping of file name to SHA-256 hex digest.
+
+    Returns:
+        str: One `<digest>  <name>` line per file, checkable with
+        `sha256sum -c`.
+    """
+    return "".join(f"{digest}  {name}\n" for name, digest in hashes.items())
+
+
+def parse_manifest(text: str) -> dict[str, str]:
+    """Parse a manifest produced by `build_manifest` (or `sha256sum`).
+
+    Args:
+        text: Manifest contents.
+
+    Returns:
+        dict[str, str]: Mapping of file name to expected hex digest.
+
+    Raises:
+        ValueError: If a non-empty line is not a valid entry or names a path
+            outside the manifest's directory.
+    """
+    entries: dict[str, str] = {}
+    for line in text.splitlines():
+        if not line.strip():
+            continue
+        digest, sep, name = line[:64], line[64:66], line[66:]
+        if sep not in ("  ", " *") or not name or Path(name).name != name:
+            raise ValueError(f"Invalid manifest line: {line!r}")
+        entries[name] = digest.lower()
+    return entries
+
+
+def build_batch_preview(sizes: dict[str, int]) -> str:
+    """Render the files of a batch and their sizes, announced before sending.
+
+    Args:
+        sizes: Mapping of file name to bytes to send, in sending order.
+
+    Returns:
+        str: One `<size> <name>` line per file.
+    """
+    return "".join(f"{size} {name}\n" for name, size in sizes.items())
+
+
+def parse_batch_preview(text: str) -> dict[str, int]:
+    """Parse a batch preview produced by `build_batch_preview`.
+
+    Args:
+        text: Preview contents.
+
+    Returns:
+        dict[str, int]: Mapping of file name to announced size, in order.
+
+    Raises:
+        ValueError: If a non-empty line is not a valid entry or names a path
+            instead of a file.
+    """
+    entries: dict[str, int] = {}
+    for line in text.splitlines():
+        if not line.strip():
+            continue
+        size, _, name = line.partition(" ")
+        if not size.isdigit() or not name or Path(name).name != name:
+            raise ValueError(f"Invalid batch preview line: {line!r}")
+        entries[name] = int(size)
+    return entries
+
+
+@dataclass
+class ManifestReport:
+    """Result of checking a directory against its manifest.
+
+    Attributes:
+        ok (list[str]): Files whose checksum matches.
+        mismatched (list[str]): Files whose checksum differs.
+        missing (list[str]): Files listed in the manifest but absent.
+    """
+
+    ok: list[str] = field(default_factory=list[str])
+    mismatched: list[str] = field(default_factory=list[str])
+    missing: list[str] = field(default_factory=list[str])
+
+    @property
+    def valid(self) -> bool:
+        """Whether every listed file is present and intact."""
+        return not self.mismatched and not self.missing
+
+
+def verify_tree(directory: Path, manifest_path: Path | None = None) -> ManifestReport:
+    """Re-verify received files against the manifest stored with them.
+
+    Args:
+        directory: Directory containing the received files.
+        manifest_path: Manifest to use; defaults to `SHA256SUMS` in `directory`.
+
+    Returns:
+        ManifestReport: Per-file verification outcome.
+
+    Raises:
+        FileNotFoundError: If the manifest does not exist.
+        ValueError: If the manifest is malformed.
+    """
+    manifest_path = manifest_path or directory / MANIFEST_NAME
+    entries = parse_manifest(manifest_path.read_text(encoding="utf-8"))
+
+    report = ManifestReport()
+    for name, expected in entries.items():
+        file_path = directory / name
+        if not file_path.is_file():
+            report.missing.append(name)
+        elif IntegrityVerifier(file_path).calculate_hash() == expected:
+            report.ok.append(name)
+        else:
+            report.mismatched.append(name)
+    return report
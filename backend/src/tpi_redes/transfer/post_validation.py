@@ -0,0 +1,240 @@
+import csv
+import json
+import struct
+import tarfile
+import zipfile
+import zlib
+from collections.abc import Callable
+from dataclasses import asdict, dataclass
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.transfer.file_inspector import inspect_file
+
+# A checksum only proves the file arrived as it was sent; these checks catch
+# files that were already broken at the source (truncated archives, invalid
+# JSON, half-written images).
+
+_TAR_SUFFIXES = (".tar", ".tar.gz", ".tgz", ".tar.bz2", ".tbz2", ".tar.xz", ".txz")
+_IMAGE_MIMES = ("image/png", "image/jpeg", "image/gif")
+
+
+@dataclass
+class ValidationResult:
+    """Outcome of one validator on a received file.
+
+    Attributes:
+        validator (str): Name of the validator, e.g. "zip".
+        ok (bool): Whether the file passed.
+        error (str | None): Why it failed, if it did.
+    """
+
+    validator: str
+    ok: bool
+    error: str | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        """Serialize the result for JSON events and the audit log.
+
+        Returns:
+            dict[str, Any]: The result fields.
+        """
+        return asdict(self)
+
+
+@dataclass
+class Validator:
+    """A file-type specific check run after checksum verification.
+
+    Attributes:
+        applies (Callable[[Path, str], bool]): Whether the check applies to a
+            file, given its path and sniffed MIME type.
+        check (Callable[[Path], None]): Raises if the file is corrupt.
+    """
+
+    applies: Callable[[Path, str], bool]
+    check: Callable[[Path], None]
+
+
+def _check_zip(path: Path):
+    """Read every member of a zip archive, verifying its CRC.
+
+    Args:
+        path: The archive.
+
+    Raises:
+        ValueError: If a member is corrupt.
+        zipfile.BadZipFile: If the archive cannot be read.
+    """
+    with zipfile.ZipFile(path) as archive:
+        if bad := archive.testzip():
+            raise ValueError(f"Corrupt member '{bad}'")
+
+
+def _check_tar(path: Path):
+    """Read every member of a (possibly compressed) tar archive.
+
+    Args:
+        path: The archive.
+
+    Raises:
+        tarfile.TarError: If the archive cannot be read.
+        EOFError: If it is truncated.
+    """
+    with tarfile.open(path) as archive:
+        for member in archive:
+            if member.isfile() and (f := archive.extractfile(member)):
+                while f.read(1024 * 1024):
+                    pass
+
+
+def _check_json(path: Path):
+    """Parse a JSON document.
+
+    Args:
+        path: The document.
+
+    Raises:
+        ValueError: If it is not valid JSON.
+    """
+    with open(path, encoding="utf-8-sig") as f:
+        json.load(f)
+
+
+def _check_csv(path: Path):
+    """Parse a CSV file and check every row has the header's column count.
+
+    Args:
+        path: The file.
+
+    Raises:
+        csv.Error: If the file cannot be parsed.
+        ValueError: If a row has a different number of columns.
+    """
+    with open(path, encoding="utf-8-sig", newline="") as f:
+        reader = csv.reader(f, strict=True)
+        header = next(reader, None)
+        for row in reader:
+            if header is not None and row and len(row) != len(header):
+                raise ValueError(
+                    f"Line {reader.line_num} has {len(row)} columns, "
+                    f"expected {len(header)}"
+                )
+
+
+def _check_image(path: Path):
+    """Check an image's structure down to its end marker.
+
+    PNG chunks are CRC-checked and their pixel data decompressed; JPEG and
+    GIF files must end with their end-of-image marker.
+
+    Args:
+        path: The image.
+
+    Raises:
+        ValueError: If the image is truncated or corrupt.
+    """
+    data = path.read_bytes()
+    if data.startswith(b"\x89PNG\r\n\x1a\n"):
+        _check_png(data)
+    elif data.startswith(b"\xff\xd8\xff"):
+        if not data.rstrip(b"\x00").endswith(b"\xff\xd9"):
+            raise ValueError("JPEG is truncated (no end-of-image marker)")
+    elif data.startswith(b"GIF8") and not data.endswith(b";"):
+        raise ValueError("GIF is truncated (no trailer)")
+
+
+def _check_png(data: bytes):
+    """Walk a PNG's chunks, checking CRCs and decompressing the pixel data.
+
+    Args:
+        data: The whole PNG file.
+
+    Raises:
+        ValueError: If a chunk is corrupt or the image is truncated.
+    """
+    offset = 8
+    pixels = zlib.decompressobj()
+    while offset + 8 <= len(data):
+        length, kind = struct.unpack(">I4s", data[offset : offset + 8])
+        body = data[offset + 8 : offset + 8 + length]
+        crc = data[offset + 8 + length : offset + 12 + length]
+        if len(crc) < 4:
+            break
+        if zlib.crc32(kind + body) != struct.unpack(">I", crc)[0]:
+            raise ValueError(f"PNG chunk {kind.decode('latin-1')} has a bad CRC")
+        if kind == b"IDAT":
+            try:
+                pixels.decompress(body)
+            except zlib.error as e:
+                raise ValueError(f"PNG pixel data is corrupt: {e}") from e
+        if kind == b"IEND":
+            return
+        offset += 12 + length
+    raise ValueError("PNG is truncated (no IEND chunk)")
+
+
+VALIDATORS: dict[str, Validator] = {
+    "zip": Validator(lambda p, mime: mime == "application/zip", _check_zip),
+    "tar": Validator(
+        lambda p, mime: p.name.lower().endswith(_TAR_SUFFIXES), _check_tar
+    ),
+    "json": Validator(lambda p, mime: p.suffix.lower() == ".json", _check_json),
+    "csv": Validator(lambda p, mime: p.suffix.lower() == ".csv", _check_csv),
+    "image": Validator(lambda p, mime: mime in _IMAGE_MIMES, _check_image),
+}
+
+
+def parse_validators(spec: str) -> tuple[str, ...]:
+    """Parse a comma-separated list of validator names.
+
+    Args:
+        spec: E.g. "zip,json", "all", or empty for none.
+
+    Returns:
+        tuple[str, ...]: The selected validator names.
+
+    Raises:
+        ValueError: If a name is not a known validator.
+    """
+    names = tuple(n.strip().lower() for n in spec.split(",") if n.strip())
+    if names == ("all",):
+        return tuple(VALIDATORS)
+    if unknown := [n for n in names if n not in VALIDATORS]:
+        raise ValueError(
+            f"Unknown validator(s): {', '.join(unknown)}; "
+            f"expected {', '.join(VALIDATORS)} or all"
+        )
+    return names
+
+
+def validate_file(
+    path: Path, names: tuple[str, ...], mime: str | None = None
+) -> list[ValidationResult]:
+    """Run the selected validators that apply to a file.
+
+    Args:
+        path: The received file.
+        names: Validators to consider, see `parse_validators`.
+        mime: Content type announced by the sender; sniffed from the file
+            if None.
+
+    Returns:
+        list[ValidationResult]: One result per validator that applied.
+    """
+    if not names:
+        return []
+    mime = mime or inspect_file(path).mime
+
+    results: list[ValidationResult] = []
+    for name in names:
+        validator = VALIDATORS[name]
+        if not validator.applies(path, mime):
+            continue
+        try:
+            validator.check(path)
+        except Exception as e:
+            results.append(ValidationResult(name, False, str(e) or type(e).__name__))
+        else:
+            results.append(ValidationResult(name, True))
+    return results
@@ -0,0 +1,188 @@
+import queue
+import threading
+from pathlib import Path
+from types import TracebackType
+
+from tpi_redes.config import READAHEAD_BUDGET
+from tpi_redes.transfer.buffer_pool import BufferPool
+
+
+class ReadaheadReader:
+    """Reads a source file ahead of the sender in a background thread.
+
+    Up to `budget` bytes of chunks are buffered, so a slow disk (spinning
+    drive, network mount) keeps the socket busy instead of stalling it on
+    every read. With a budget smaller than one chunk, reads happen inline.
+    `fill` reports how full the buffer is: near 0 means the disk is the
+    bottleneck, near 1 means the network is.
+
+    With a `BufferPool`, chunks are read into pooled buffers and returned as
+    memoryviews that stay valid only until the next `read` or `close`.
+    """
+
+    def __init__(
+        self,
+        path: Path,
+        limit: int,
+        chunk_size: int,
+        budget: int = READAHEAD_BUDGET,
+        pool: BufferPool | None = None,
+    ):
+        """Open the file and start reading ahead.
+
+        Args:
+            path: The source file.
+            limit: Number of bytes to read from the start of the file.
+            chunk_size: Size of each chunk returned by `read`.
+            budget: Maximum bytes buffered ahead of the sender.
+            pool: Optional pool of `chunk_size` buffers to read into.
+
+        Raises:
+            OSError: If the file cannot be opened.
+        """
+        self.limit = limit
+        self.chunk_size = chunk_size
+        self.budget = budget
+        self._file = open(path, "rb")
+        self._remaining = limit
+        self._done = False
+        self._buffered = 0
+        self._lock = threading.Lock()
+        self._stop = threading.Event()
+        self._pool = pool
+        self._lent: memoryview | None = None
+        self._queue: queue.Queue[bytes | memoryview | OSError] | None = None
+        self._thread: threading.Thread | None = None
+        if budget >= chunk_size:
+            self._queue = queue.Queue(maxsize=budget // chunk_size)
+            self._thread = threading.Thread(target=self._fill, daemon=True)
+            self._thread.start()
+
+    def __enter__(self) -> "ReadaheadReader":
+        return self
+
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_val: BaseException | None,
+        exc_tb: TracebackType | None,
+    ):
+        self.close()
+
+    @property
+    def fill(self) -> float:
+        """Fraction of the budget currently buffered, from 0.0 to 1.0."""
+        if self._queue is None:
+            return 0.0
+        with self._lock:
+            return min(1.0, self._buffered / self.budget)
+
+    def read(self) -> bytes | memoryview:
+        """Return the next chunk.
+
+        Returns:
+            bytes | memoryview: Up to `chunk_size` bytes, or empty bytes once
+            `limit` bytes were read or the file ended. A memoryview when
+            reading into a pool; it is reused after the next call.
+
+        Raises:
+            OSError: If reading the file failed.
+        """
+        self._release()
+        if self._done:
+            return b""
+        if self._queue is None:
+            chunk = self._read_chunk(min(self.chunk_size, self._remaining))
+            self._remaining -= len(chunk)
+        else:
+            item = self._queue.get()
+            if isinstance(item, OSError):
+                self._done = True
+                raise item
+            chunk = item
+            with self._lock:
+                self._buffered -= len(chunk)
+        self._done = not chunk
+        if isinstance(chunk, memoryview):
+            self._lent = chunk
+        return chunk
+
+    def close(self):
+        """Stop the background reader and close the file.
+
+        Returns:
+            None: No return value.
+        """
+        self._stop.set()
+        if self._thread:
+            self._thread.join()
+        self._release()
+        self._file.close()
+
+    def _read_chunk(self, size: int) -> bytes | memoryview:
+        """Read up to `size` bytes, into a pooled buffer if there is a pool.
+
+        Args:
+            size: Bytes to read, at most `chunk_size`.
+
+        Returns:
+            bytes | memoryview: The chunk, empty at the end of the file.
+        """
+        if self._pool is None:
+            return self._file.read(size)
+        buffer = self._pool.acquire()
+        view = memoryview(buffer)[:size]
+        read = self._file.readinto(view)
+        if not read:
+            view.release()
+            self._pool.release(buffer)
+            return b""
+        return view[:read]
+
+    def _release(self):
+        """Return the chunk handed out by the previous `read` to the pool.
+
+        Returns:
+            None: No return value.
+        """
+        if self._lent is not None and self._pool is not None:
+            self._pool.release(self._lent.obj)
+            self._lent = None
+
+    def _fill(self):
+        """Read chunks into the buffer until the limit, EOF or `close`.
+
+        Returns:
+            None: No return value.
+        """
+        remaining = self.limit
+        try:
+            while remaining > 0 and not self._stop.is_set():
+                chunk = self._read_chunk(min(self.chunk_size, remaining))
+                if not chunk:
+                    break
+                remaining -= len(chunk)
+                with self._lock:
+                    self._buffered += len(chunk)
+                self._put(chunk)
+        except OSError as e:
+            self._put(e)
+            return
+        self._put(b"")
+
+    def _put(self, item: bytes | memoryview | OSError):
+        """Queue an item, giving up if the reader is closed meanwhile.
+
+        Args:
+            item: A chunk, the empty end marker or a read error.
+
+        Returns:
+            None: No return value.
+        """
+        assert self._queue is not None
+        while not self._stop.is_set():
+            try:
+                self._queue.put(item, timeout=0.1)
+                return
+            except queue.Full:
+                continue
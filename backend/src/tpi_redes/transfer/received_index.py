@@ -0,0 +1,116 @@
+import fnmatch
+import json
+import re
+import threading
+import time
+import uuid
+from pathlib import Path
+from typing import Any
+
+_CHECKSUM_QUERY = re.compile(r"[0-9a-fA-F]{8,64}")
+
+
+class ReceivedIndex:
+    """Persistent index of received files: path, checksum and transfer id.
+
+    Stored as JSON lines, one per verified file, so answering "have I already
+    received this and where did it go?" does not require re-hashing the
+    output tree. Unlike the audit log it is not tamper-evident and only lists
+    files that were stored.
+    """
+
+    def __init__(self, path: Path):
+        """Initialize the index; the file is created on first add.
+
+        Args:
+            path: Location of the index file.
+        """
+        self.path = path
+        self._lock = threading.Lock()
+
+    def add(
+        self, stored_path: Path, filename: str, size: int, sha256: str, peer: str
+    ) -> dict[str, Any]:
+        """Append an entry for a stored, verified file.
+
+        Args:
+            stored_path: Where the file was stored.
+            filename: Name announced by the sender.
+            size: Size in bytes.
+            sha256: SHA-256 hex digest of the content.
+            peer: Sender "ip:port".
+
+        Returns:
+            dict[str, Any]: The entry as written.
+        """
+        entry = {
+            "transfer_id": uuid.uuid4().hex,
+            "timestamp": time.time(),
+            "path": str(stored_path.resolve()),
+            "filename": filename,
+            "size": size,
+            "sha256": sha256,
+            "peer": peer,
+        }
+        with self._lock:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            with open(self.path, "a", encoding="utf-8") as f:
+                f.write(json.dumps(entry) + "\n")
+        return entry
+
+    def entries(self) -> list[dict[str, Any]]:
+        """Read every entry, skipping unreadable lines.
+
+        Returns:
+            list[dict[str, Any]]: Entries in the order they were added; empty
+            if the index does not exist yet.
+        """
+        if not self.path.exists():
+            return []
+        entries: list[dict[str, Any]] = []
+        with open(self.path, encoding="utf-8") as f:
+            for line in f:
+                try:
+                    entries.append(json.loads(line))
+                except ValueError:
+                    continue
+        return entries
+
+    def find(self, query: str) -> list[dict[str, Any]]:
+        """Find received files by checksum or by name.
+
+        A query of 8 to 64 hex digits matches checksums starting with it;
+        anything else is a case-insensitive glob on the file name (e.g.
+        "*.iso").
+
+        Args:
+            query: Checksum (or prefix) or name pattern.
+
+        Returns:
+            list[dict[str, Any]]: Matching entries, oldest first, each with
+            `exists` telling whether the file is still at its path.
+        """
+        if _CHECKSUM_QUERY.fullmatch(query):
+            prefix = query.lower()
+            matches = [e for e in self.entries() if e["sha256"].startswith(prefix)]
+        else:
+            pattern = query.lower()
+            matches = [
+                e
+                for e in self.entries()
+                if fnmatch.fnmatchcase(e["filename"].lower(), pattern)
+            ]
+        return [{**e, "exists": Path(e["path"]).is_file()} for e in matches]
+
+
+def find_received(index_path: Path, query: str) -> list[dict[str, Any]]:
+    """Look up received files in an index, see `ReceivedIndex.find`.
+
+    Args:
+        index_path: The index file written by `start-server --index`.
+        query: Checksum (or prefix) or name pattern.
+
+    Returns:
+        list[dict[str, Any]]: Matching entries.
+    """
+    return ReceivedIndex(index_path).find(query)
@@ -0,0 +1,116 @@
+import ipaddress
+import json
+from dataclasses import dataclass
+from pathlib import Path
+
+
+@dataclass(frozen=True)
+class RouteRule:
+    """Sends files from matching senders to a specific directory.
+
+    Attributes:
+        match (str): Sender IP address or CIDR network, e.g. `10.0.0.5` or
+            `10.0.0.0/24`.
+        directory (Path): Destination for files from matching senders.
+    """
+
+    match: str
+    directory: Path
+
+    def matches(self, ip: str) -> bool:
+        """Return whether a sender address is covered by this rule.
+
+        Args:
+            ip: The sender's IP address.
+
+        Returns:
+            bool: True if the address falls within `match`.
+        """
+        try:
+            return ipaddress.ip_address(ip) in ipaddress.ip_network(
+                self.match, strict=False
+            )
+        except ValueError:
+            return False
+
+
+class OutputRouter:
+    """Chooses a receiver's output directory from the sender's address.
+
+    Rules are evaluated in order and the first match wins; senders that match
+    no rule use the default directory. Relative rule directories are resolved
+    against the default directory.
+    """
+
+    def __init__(self, rules: list[RouteRule], default_dir: Path):
+        """Initialize the router.
+
+        Args:
+            rules: Ordered routing rules.
+            default_dir: Directory for senders matching no rule.
+        """
+        self.default_dir = default_dir
+        self.rules = [
+            RouteRule(rule.match, default_dir / rule.directory) for rule in rules
+        ]
+
+    def resolve(self, ip: str) -> tuple[Path, RouteRule | None]:
+        """Return the output directory for a sender.
+
+        Args:
+            ip: The sender's IP address.
+
+        Returns:
+            tuple[Path, RouteRule | None]: The directory and the rule that
+            selected it, or None when the default directory applies.
+        """
+        for rule in self.rules:
+            if rule.matches(ip):
+                return rule.directory, rule
+        return self.default_dir, None
+
+
+def parse_route(spec: str) -> RouteRule:
+    """Parse a `MATCH=DIR` routing rule given on the command line.
+
+    Args:
+        spec: Rule specification, e.g. `10.0.0.5=from-anna`.
+
+    Returns:
+        RouteRule: The parsed rule.
+
+    Raises:
+        ValueError: If the rule is malformed or MATCH is not an IP or network.
+    """
+    match, sep, directory = spec.partition("=")
+    if not sep or not directory:
+        raise ValueError(f"Invalid route '{spec}', expected MATCH=DIR")
+    ipaddress.ip_network(match, strict=False)
+    return RouteRule(match, Path(directory).expanduser())
+
+
+def load_routes(path: Path) -> list[RouteRule]:
+    """Load routing rules from a JSON file.
+
+    The file holds a list of objects such as
+    `[{"match": "10.0.0.5", "dir": "from-anna"}]`.
+
+    Args:
+        path: Location of the rules file.
+
+    Returns:
+        list[RouteRule]: The rules, in file order.
+
+    Raises:
+        ValueError: If the file is not a list of valid rules.
+    """
+    data = json.loads(path.read_text(encoding="utf-8"))
+    if not isinstance(data, list):
+        raise ValueError(f"{path}: expected a list of routing rules")
+
+    rules: list[RouteRule] = []
+    for item in data:
+        if not isinstance(item, dict) or "match" not in item or "dir" not in item:
+            raise ValueError(f"{path}: each rule needs 'match' and 'dir'")
+        rules.append(parse_route(f"{item['match']}={item['dir']}"))
+    return rules
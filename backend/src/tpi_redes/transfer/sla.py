@@ -0,0 +1,78 @@
+import time
+from collections.abc import Callable
+from typing import Any
+
+from tpi_redes.config import SLA_MIN_SAMPLE_SECONDS
+
+
+class SlaMonitor:
+    """Tracks a batch transfer against an expected duration (SLA).
+
+    Projects the finish time from the average rate so far and warns once when
+    it falls past the deadline (`at_risk`), and once more when the deadline
+    itself passes (`overrun`). Projections are only made after
+    `SLA_MIN_SAMPLE_SECONDS` so the first chunks do not trigger false alarms.
+    """
+
+    def __init__(
+        self,
+        deadline: float,
+        total_bytes: int,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        """Start the SLA timer.
+
+        Args:
+            deadline: Expected duration of the whole transfer, in seconds.
+            total_bytes: Bytes the transfer will send.
+            clock: Monotonic time source.
+        """
+        self.deadline = deadline
+        self.total_bytes = total_bytes
+        self._clock = clock
+        self._started = clock()
+        self._warned: set[str] = set()
+
+    @property
+    def missed(self) -> bool:
+        """Whether the transfer was at risk of missing, or missed, its SLA."""
+        return bool(self._warned)
+
+    def check(self, done_bytes: int) -> dict[str, Any] | None:
+        """Evaluate progress against the deadline.
+
+        Args:
+            done_bytes: Bytes sent so far across the whole transfer.
+
+        Returns:
+            dict[str, Any] | None: An `SLA_WARNING` event the first time a
+            status is reached, otherwise None.
+        """
+        elapsed = self._clock() - self._started
+        eta: float | None = None
+        if done_bytes > 0:
+            eta = elapsed * (self.total_bytes - done_bytes) / done_bytes
+
+        if elapsed > self.deadline:
+            status = "overrun"
+        elif (
+            eta is not None
+            and elapsed >= SLA_MIN_SAMPLE_SECONDS
+            and elapsed + eta > self.deadline
+        ):
+            status = "at_risk"
+        else:
+            return None
+
+        if status in self._warned:
+            return None
+        self._warned.add(status)
+        return {
+            "type": "SLA_WARNING",
+            "status": status,
+            "deadline": self.deadline,
+            "elapsed": round(elapsed, 3),
+            "eta": round(eta, 3) if eta is not None else None,
+            "current": done_bytes,
+            "total": self.total_bytes,
+        }
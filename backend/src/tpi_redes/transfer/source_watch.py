@@ -0,0 +1,73 @@
+import time
+from collections.abc import Callable
+from pathlib import Path
+from stat import S_ISREG
+
+from tpi_redes.config import SOURCE_CHECK_INTERVAL
+
+
+class SourceWatcher:
+    """Detects changes to a source file while it is being sent.
+
+    The file is stat-ed at most every `interval` seconds and compared with
+    its size and mtime when the transfer started, so a file rewritten midway
+    fails fast instead of ending in a checksum mismatch. When only a prefix
+    of the file is sent (e.g. a growing log with `--max-read-bytes`), appends
+    are expected and only truncation below that prefix counts as a change.
+    FIFOs and devices have no size or mtime to compare and are not watched.
+    """
+
+    def __init__(
+        self,
+        path: Path,
+        sent_size: int,
+        prefix_only: bool = False,
+        interval: float = SOURCE_CHECK_INTERVAL,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        """Record the file's current state.
+
+        Args:
+            path: The source file.
+            sent_size: Bytes of the file the transfer sends.
+            prefix_only: Whether only the first `sent_size` bytes matter.
+            interval: Minimum seconds between two checks.
+            clock: Monotonic time source.
+
+        Raises:
+            OSError: If the file cannot be stat-ed.
+        """
+        self.path = path
+        self.sent_size = sent_size
+        self.prefix_only = prefix_only
+        self.interval = interval
+        self._clock = clock
+        stat = path.stat()
+        self._regular = S_ISREG(stat.st_mode)
+        self._snapshot = (stat.st_size, stat.st_mtime_ns)
+        self._last_check = clock()
+
+    def changed(self, force: bool = False) -> bool:
+        """Return whether the file changed since the transfer started.
+
+        Args:
+            force: Check even if `interval` has not elapsed since the last
+                check (e.g. once the content has been sent).
+
+        Returns:
+            bool: True if the file was modified, truncated or removed.
+        """
+        now = self._clock()
+        if not self._regular:
+            return False
+        if not force and now - self._last_check < self.interval:
+            return False
+        self._last_check = now
+
+        try:
+            stat = self.path.stat()
+        except OSError:
+            return True
+        if self.prefix_only:
+            return stat.st_size < self.sent_size
+        return (stat.st_size, stat.st_mtime_ns) != self._snapshot
@@ -0,0 +1,124 @@
+import time
+from collections.abc import Callable
+from typing import Any
+
+from tpi_redes.config import SPEED_SAMPLE_SECONDS, SPEED_SMOOTHING
+
+# Throughput within this fraction of the cap counts as saturating it; pacing
+# and shaping rarely reach a configured rate exactly.
+_SATURATION_RATIO = 0.95
+
+
+class SpeedMonitor:
+    """Alerts when a transfer's smoothed speed leaves an acceptable range.
+
+    The rate is sampled at most every `SPEED_SAMPLE_SECONDS` and smoothed
+    with an exponential moving average. A `SLOW_TRANSFER` event is returned
+    once the rate has stayed below `min_rate` for `hold_seconds`, and a
+    `SPEED_SATURATED` event once it has stayed at (95% of) `max_rate` that
+    long. Each alert fires once per episode and re-arms when the rate is
+    back in range.
+    """
+
+    def __init__(
+        self,
+        min_rate: float | None,
+        max_rate: float | None,
+        hold_seconds: float,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        """Start monitoring.
+
+        Args:
+            min_rate: Slowest acceptable speed in bytes/s, or None.
+            max_rate: Speed cap in bytes/s to report reaching, or None.
+            hold_seconds: How long the speed must stay out of range.
+            clock: Monotonic time source.
+        """
+        self.min_rate = min_rate
+        self.max_rate = max_rate
+        self.hold_seconds = hold_seconds
+        self.rate: float | None = None
+        self.alerts: list[str] = []
+        self._clock = clock
+        self._last_time = clock()
+        self._last_bytes = 0
+        self._since: dict[str, float | None] = {"slow": None, "saturated": None}
+        self._active: set[str] = set()
+
+    @property
+    def slow(self) -> bool:
+        """Whether the speed is currently below the minimum for long enough."""
+        return "slow" in self._active
+
+    def check(self, done_bytes: int) -> dict[str, Any] | None:
+        """Update the smoothed speed and report a newly reached alert.
+
+        Args:
+            done_bytes: Bytes sent so far across the whole transfer.
+
+        Returns:
+            dict[str, Any] | None: A `SLOW_TRANSFER` or `SPEED_SATURATED`
+            event the first time an episode lasts `hold_seconds`, else None.
+        """
+        now = self._clock()
+        elapsed = now - self._last_time
+        if elapsed < SPEED_SAMPLE_SECONDS:
+            return None
+        sample = (done_bytes - self._last_bytes) / elapsed
+        self._last_time, self._last_bytes = now, done_bytes
+        if self.rate is None:
+            self.rate = sample
+        else:
+            self.rate += SPEED_SMOOTHING * (sample - self.rate)
+
+        out_of_range = {
+            "slow": self.min_rate is not None and self.rate < self.min_rate,
+            "saturated": self.max_rate is not None
+            and self.rate >= self.max_rate * _SATURATION_RATIO,
+        }
+        alert = None
+        for status, breached in out_of_range.items():
+            since = self._since[status]
+            if not breached:
+                self._since[status] = None
+                self._active.discard(status)
+                continue
+            if since is None:
+                self._since[status] = since = now
+            if now - since >= self.hold_seconds and status not in self._active:
+                self._active.add(status)
+                self.alerts.append(status)
+                alert = self._event(status, now - since, done_bytes)
+        return alert
+
+    def skip(self, done_bytes: int):
+        """Start the next sample now, e.g. after a pause, so it is not slow.
+
+        Args:
+            done_bytes: Bytes sent so far across the whole transfer.
+
+        Returns:
+            None: No return value.
+        """
+        self._last_time, self._last_bytes = self._clock(), done_bytes
+        self._since = {"slow": None, "saturated": None}
+
+    def _event(self, status: str, duration: float, done_bytes: int) -> dict[str, Any]:
+        """Build the alert event.
+
+        Args:
+            status: "slow" or "saturated".
+            duration: Seconds the speed has been out of range.
+            done_bytes: Bytes sent so far.
+
+        Returns:
+            dict[str, Any]: The event.
+        """
+        return {
+            "type": "SLOW_TRANSFER" if status == "slow" else "SPEED_SATURATED",
+            "rate": round(self.rate or 0.0, 1),
+            "threshold": self.min_rate if status == "slow" else self.max_rate,
+            "duration": round(duration, 3),
+            "current": done_bytes,
+        }
@@ -0,0 +1,230 @@
+import time
+from collections.abc import Callable
+from dataclasses import asdict, dataclass, field
+from typing import Any
+
+from tpi_redes.config import SPEED_SAMPLE_SECONDS
+
+
+@dataclass
+class TransferSummary:
+    """End-of-transfer report of a sent batch.
+
+    Attributes:
+        protocol (str): "tcp" or "udp".
+        peer (str): Receiver as `ip:port`.
+        files (list[dict[str, Any]]): Per file: `filename`, `size` and
+            `result` ("sent", "confirmed", "mismatch" or "unconfirmed").
+        total_bytes (int): Content bytes sent.
+        elapsed (float): Seconds from connecting to the last file.
+        avg_speed (float): Content bytes per second over `elapsed`.
+        peak_speed (float): Fastest speed over a sampling period.
+        retries (int): Files sent again from scratch (`--restart-on-change`).
+        loss (float | None): Fraction of UDP content the receiver did not
+            confirm; None for TCP.
+        settings (dict[str, Any]): Effective parameters, e.g. chunk size.
+        phases (dict[str, float]): Seconds spent connecting, in the
+            handshake, sending data and waiting for verification.
+        errors (dict[str, dict[str, Any]]): Error events seen per kind, with
+            how many were dropped as repeats (see `ErrorEventLimiter`).
+        warnings (dict[str, dict[str, Any]]): Non-fatal events seen per kind
+            (restarts, SLA and speed alerts, `TRANSFER_WARNING`), counted
+            like `errors`.
+        peer_version (str | None): tpi-redes version of the receiver, if it
+            announced one (TCP only).
+        disabled_features (list[str]): Protocol features not used because
+            the receiver is older.
+    """
+
+    protocol: str
+    peer: str
+    files: list[dict[str, Any]]
+    total_bytes: int
+    elapsed: float
+    avg_speed: float
+    peak_speed: float
+    retries: int = 0
+    loss: float | None = None
+    settings: dict[str, Any] = field(default_factory=dict[str, Any])
+    phases: dict[str, float] = field(default_factory=dict[str, float])
+    errors: dict[str, dict[str, Any]] = field(
+        default_factory=dict[str, dict[str, Any]]
+    )
+    peer_version: str | None = None
+    disabled_features: list[str] = field(default_factory=list[str])
+    warnings: dict[str, dict[str, Any]] = field(
+        default_factory=dict[str, dict[str, Any]]
+    )
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the summary as a JSON-serializable dictionary."""
+        return asdict(self)
+
+    def __str__(self) -> str:
+        """Render the summary as a few lines for the terminal."""
+        lines = [
+            f"Sent {len(self.files)} file(s), {_mb(self.total_bytes)} MB to "
+            f"{self.peer} over {self.protocol.upper()} in {self.elapsed:.2f} s",
+            f"  Speed: avg {_mb(self.avg_speed)} MB/s, "
+            f"peak {_mb(self.peak_speed)} MB/s",
+        ]
+        if self.peer_version:
+            peer = f"  Receiver: tpi-redes {self.peer_version}"
+            if self.disabled_features:
+                disabled = ", ".join(self.disabled_features)
+                peer += f" (disabled for compatibility: {disabled})"
+            lines.append(peer)
+        if self.phases:
+            phases = ", ".join(f"{k} {v:.3f} s" for k, v in self.phases.items())
+            lines.append(f"  Phases: {phases}")
+        loss = "n/a" if self.loss is None else f"{self.loss:.1%}"
+        lines.append(f"  Retries: {self.retries}, loss: {loss}")
+        if self.settings:
+            settings = ", ".join(f"{k}={v}" for k, v in self.settings.items())
+            lines.append(f"  Settings: {settings}")
+        if self.errors:
+            errors = ", ".join(
+                f"{k} x{v['count']} ({v['suppressed']} suppressed)"
+                for k, v in self.errors.items()
+            )
+            lines.append(f"  Errors: {errors}")
+        if self.warnings:
+            warnings = ", ".join(f"{k} x{v['count']}" for k, v in self.warnings.items())
+            lines.append(f"  Warnings: {warnings}")
+        lines += [
+            f"  {f['filename']}: {_mb(f['size'])} MB, {f['result']}"
+            for f in self.files
+        ]
+        return "\n".join(lines)
+
+
+class SummaryRecorder:
+    """Collects timings and speeds while a batch is sent.
+
+    Phases are consecutive: entering one ends the previous, so their
+    durations add up to the time spent in the batch.
+    """
+
+    def __init__(self, clock: Callable[[], float] = time.monotonic):
+        """Start the clock.
+
+        Args:
+            clock: Monotonic time source.
+        """
+        self.phases: dict[str, float] = {}
+        self.files: list[dict[str, Any]] = []
+        self.peak_speed = 0.0
+        self._clock = clock
+        self._started = clock()
+        self._phase: str | None = None
+        self._phase_started = self._started
+        self._sample_time = self._started
+        self._sample_bytes = 0
+
+    def enter(self, phase: str | None):
+        """Switch to another phase, or stop timing with None.
+
+        Args:
+            phase: "connect", "handshake", "data" or "verify".
+
+        Returns:
+            None: No return value.
+        """
+        now = self._clock()
+        if self._phase is not None:
+            spent = now - self._phase_started
+            self.phases[self._phase] = self.phases.get(self._phase, 0.0) + spent
+        self._phase, self._phase_started = phase, now
+
+    def sample(self, done_bytes: int):
+        """Update the peak speed with the bytes sent so far.
+
+        Args:
+            done_bytes: Bytes sent so far across the batch.
+
+        Returns:
+            None: No return value.
+        """
+        now = self._clock()
+        elapsed = now - self._sample_time
+        if elapsed < SPEED_SAMPLE_SECONDS:
+            return
+        rate = (done_bytes - self._sample_bytes) / elapsed
+        self.peak_speed = max(self.peak_speed, rate)
+        self._sample_time, self._sample_bytes = now, done_bytes
+
+    def file(self, filename: str, size: int, result: str):
+        """Record a file's outcome.
+
+        Args:
+            filename: Name of the file.
+            size: Bytes sent.
+            result: "sent", "confirmed", "mismatch" or "unconfirmed".
+
+        Returns:
+            None: No return value.
+        """
+        self.files.append({"filename": filename, "size": size, "result": result})
+
+    def finish(
+        self,
+        protocol: str,
+        peer: str,
+        settings: dict[str, Any],
+        retries: int = 0,
+        loss: float | None = None,
+        errors: dict[str, dict[str, Any]] | None = None,
+        peer_version: str | None = None,
+        disabled_features: list[str] | None = None,
+        warnings: dict[str, dict[str, Any]] | None = None,
+    ) -> TransferSummary:
+        """Stop timing and build the summary.
+
+        Args:
+            protocol: "tcp" or "udp".
+            peer: Receiver as `ip:port`.
+            settings: Effective parameters.
+            retries: Files sent again from scratch.
+            loss: Fraction of UDP content not confirmed, None for TCP.
+            errors: Error events seen per kind.
+            peer_version: tpi-redes version of the receiver, if known.
+            disabled_features: Protocol features not used with this receiver.
+            warnings: Warning events seen per kind.
+
+        Returns:
+            TransferSummary: The summary.
+        """
+        self.enter(None)
+        elapsed = self._clock() - self._started
+        total = sum(f["size"] for f in self.files)
+        avg = total / elapsed if elapsed > 0 else 0.0
+        return TransferSummary(
+            protocol=protocol,
+            peer=peer,
+            files=self.files,
+            total_bytes=total,
+            elapsed=round(elapsed, 3),
+            avg_speed=round(avg, 1),
+            # A batch shorter than one sample period never sets a peak.
+            peak_speed=round(max(self.peak_speed, avg), 1),
+            retries=retries,
+            loss=None if loss is None else round(loss, 4),
+            settings=settings,
+            phases={k: round(v, 3) for k, v in self.phases.items()},
+            errors=errors or {},
+            peer_version=peer_version,
+            disabled_features=disabled_features or [],
+            warnings=warnings or {},
+        )
+
+
+def _mb(value: float) -> str:
+    """Format bytes (or bytes per second) in megabytes.
+
+    Args:
+        value: The amount.
+
+    Returns:
+        str: The amount in MB with one decimal.
+    """
+    return f"{value / 1e6:.1f}"
@@ -0,0 +1,152 @@
+import os
+import tempfile
+from dataclasses import dataclass
+from pathlib import Path
+from typing import BinaryIO
+
+from tpi_redes.config import WRITE_BUFFER_BYTES
+
+FSYNC_NEVER = "never"
+FSYNC_ON_COMPLETE = "on-complete"
+
+
+@dataclass(frozen=True)
+class WritePolicy:
+    """How a receiver writes incoming content to disk.
+
+    Attributes:
+        buffer_bytes (int): Size of the write buffer that batches small
+            chunks into fewer, larger writes.
+        fsync (str): `"never"` (leave flushing to the OS), `"on-complete"`
+            (fsync each file once fully received) or `"every"`.
+        fsync_every_bytes (int): With `"every"`, bytes written between
+            fsyncs; each file is also synced once complete.
+    """
+
+    buffer_bytes: int = WRITE_BUFFER_BYTES
+    fsync: str = FSYNC_NEVER
+    fsync_every_bytes: int = 0
+
+    @property
+    def syncs(self) -> bool:
+        """Whether files are synced to disk at all."""
+        return self.fsync != FSYNC_NEVER
+
+
+def parse_fsync_policy(
+    spec: str, buffer_bytes: int = WRITE_BUFFER_BYTES
+) -> WritePolicy:
+    """Parse an fsync policy such as `never`, `on-complete` or `every:64`.
+
+    Args:
+        spec: The policy; `every:N` syncs every N MiB written.
+        buffer_bytes: Write buffer size for the resulting policy.
+
+    Returns:
+        WritePolicy: The parsed policy.
+
+    Raises:
+        ValueError: If the policy is not recognized.
+    """
+    if spec in (FSYNC_NEVER, FSYNC_ON_COMPLETE):
+        return WritePolicy(buffer_bytes, spec)
+    kind, _, megabytes = spec.partition(":")
+    if kind != "every" or not megabytes.isdigit() or int(megabytes) == 0:
+        raise ValueError(
+            f"Invalid fsync policy '{spec}'; use never, on-complete or every:N (MiB)"
+        )
+    return WritePolicy(buffer_bytes, "every", int(megabytes) * 1024 * 1024)
+
+
+class SyncedWriter:
+    """Writes to an open file and fsyncs it according to a `WritePolicy`."""
+
+    def __init__(self, f: BinaryIO, policy: WritePolicy):
+        """Wrap a file opened for binary writing.
+
+        Args:
+            f: The destination file.
+            policy: When to sync it to disk.
+        """
+        self.f = f
+        self.policy = policy
+        self._unsynced = 0
+
+    def write(self, data: bytes) -> int:
+        """Write data, syncing if the policy's interval has been reached.
+
+        Args:
+            data: Bytes to write.
+
+        Returns:
+            int: Number of bytes written.
+        """
+        written = self.f.write(data)
+        self._unsynced += written
+        every = self.policy.fsync_every_bytes
+        if every and self._unsynced >= every:
+            self.sync()
+        return written
+
+    def complete(self):
+        """Sync the finished file unless the policy never syncs.
+
+        Returns:
+            None: No return value.
+        """
+        if self.policy.syncs:
+            self.sync()
+
+    def sync(self):
+        """Flush buffered data and fsync the file.
+
+        Returns:
+            None: No return value.
+        """
+        self.f.flush()
+        os.fsync(self.f.fileno())
+        self._unsynced = 0
+
+
+def fsync_dir(path: Path):
+    """Persist a directory's entries, e.g. after renaming files into it.
+
+    Not supported on Windows, where this does nothing.
+
+    Args:
+        path: The directory.
+
+    Returns:
+        None: No return value.
+    """
+    if os.name == "nt":
+        return
+    fd = os.open(path, os.O_RDONLY)
+    try:
+        os.fsync(fd)
+    finally:
+        os.close(fd)
+
+
+def write_atomic(path: Path, content: str):
+    """Replace a file through a temporary file and a rename.
+
+    A crash midway leaves either the old file or the new one, never a
+    truncated mix, and the temporary file is removed if writing fails.
+
+    Args:
+        path: File to write; its directory is created if needed.
+        content: New content.
+
+    Returns:
+        None: No return value.
+    """
+    path.parent.mkdir(parents=True, exist_ok=True)
+    fd, tmp = tempfile.mkstemp(dir=path.parent, prefix=f".{path.name}.")
+    try:
+        with os.fdopen(fd, "w", encoding="utf-8") as f:
+            f.write(content)
+        os.replace(tmp, path)
+    except BaseException:
+        Path(tmp).unlink(missing_ok=True)
+        raise
@@ -0,0 +1,108 @@
+import logging
+import sys
+import time
+from collections.abc import Callable
+from dataclasses import dataclass
+
+from tpi_redes.config import (
+    DEBUG_CORRUPT_EVERY_NTH_CHUNK,
+    DEBUG_DELAY_PER_CHUNK_MS,
+    DEBUG_DROP_EVERY_NTH_PACKET,
+)
+
+logger = logging.getLogger("tpi-redes")
+
+
+@dataclass(frozen=True)
+class DebugHooks:
+    """Deterministic faults injected by senders to reproduce bug reports.
+
+    Set through the hidden `TPI_REDES_DEBUG_*` variables and only honored
+    when running from source: the packaged binary always ignores them.
+
+    Attributes:
+        delay_per_chunk_ms (int): Pause before sending each content chunk.
+        drop_every_nth_packet (int): Skip every Nth UDP content datagram, as
+            if it were lost on the wire. TCP cannot lose data, so it is
+            ignored there.
+        corrupt_every_nth_chunk (int): Flip the first byte of every Nth
+            chunk after its checksum was computed.
+    """
+
+    delay_per_chunk_ms: int = 0
+    drop_every_nth_packet: int = 0
+    corrupt_every_nth_chunk: int = 0
+
+    @property
+    def active(self) -> bool:
+        """Whether any fault is configured."""
+        return bool(
+            self.delay_per_chunk_ms
+            or self.drop_every_nth_packet
+            or self.corrupt_every_nth_chunk
+        )
+
+    @classmethod
+    def from_config(cls) -> "DebugHooks":
+        """Read the hooks from configuration, unless running frozen.
+
+        Returns:
+            DebugHooks: The configured hooks, inactive in packaged builds.
+        """
+        if getattr(sys, "frozen", False):
+            return cls()
+        hooks = cls(
+            DEBUG_DELAY_PER_CHUNK_MS,
+            DEBUG_DROP_EVERY_NTH_PACKET,
+            DEBUG_CORRUPT_EVERY_NTH_CHUNK,
+        )
+        if hooks.active:
+            logger.warning(f"Debug traffic hooks enabled: {hooks}")
+        return hooks
+
+
+class ChunkShaper:
+    """Applies `DebugHooks` to the chunks of one `send_files` call.
+
+    Chunks are counted from 1 across the whole call, so the same files and
+    settings always hit the same chunks.
+    """
+
+    def __init__(self, hooks: DebugHooks, sleep: Callable[[float], None] = time.sleep):
+        """Initialize the counters.
+
+        Args:
+            hooks: Faults to inject.
+            sleep: Function used to pause before a chunk.
+        """
+        self.hooks = hooks
+        self._sleep = sleep
+        self._chunks = 0
+        self._packets = 0
+
+    def shape(self, chunk: bytes) -> bytes:
+        """Delay and possibly corrupt the next chunk.
+
+        Args:
+            chunk: Content about to be sent.
+
+        Returns:
+            bytes: The chunk to send instead.
+        """
+        self._chunks += 1
+        if self.hooks.delay_per_chunk_ms:
+            self._sleep(self.hooks.delay_per_chunk_ms / 1000)
+        nth = self.hooks.corrupt_every_nth_chunk
+        if nth and chunk and self._chunks % nth == 0:
+            return bytes([chunk[0] ^ 0xFF]) + chunk[1:]
+        return chunk
+
+    def should_drop(self) -> bool:
+        """Return whether the next datagram should be silently skipped.
+
+        Returns:
+            bool: True for every Nth datagram when drops are configured.
+        """
+        self._packets += 1
+        nth = self.hooks.drop_every_nth_packet
+        return bool(nth) and self._packets % nth == 0
@@ -0,0 +1,41 @@
+import time
+
+
+class Pacer:
+    """Spaces out datagrams so the average send rate stays under a target.
+
+    Each call to `wait` books the transmission time of the next packet
+    (`nbytes / rate`) and sleeps until the previous booking has elapsed.
+    Idle periods do not accumulate credit, so a stalled sender cannot burst
+    at line rate once it resumes.
+    """
+
+    def __init__(self, rate: int):
+        """Initialize the pacer.
+
+        Args:
+            rate: Target send rate in bytes per second. Must be positive.
+
+        Raises:
+            ValueError: If the rate is not positive.
+        """
+        if rate <= 0:
+            raise ValueError(f"Pacing rate must be positive, got {rate}")
+        self.rate = rate
+        self._next_send = time.monotonic()
+
+    def wait(self, nbytes: int):
+        """Block until `nbytes` may be sent without exceeding the target rate.
+
+        Args:
+            nbytes: Size of the packet about to be sent.
+
+        Returns:
+            None: No return value.
+        """
+        now = time.monotonic()
+        if self._next_send > now:
+            time.sleep(self._next_send - now)
+        else:
+            self._next_send = now
+        self._next_send += nbytes / self.rate
@@ -0,0 +1,283 @@
+import logging
+import socket
+import time
+from dataclasses import asdict, dataclass
+from typing import Any
+
+from tpi_redes.config import (
+    MAX_UDP_PACKET_SIZE,
+    PEER_PREVIEW_BYTES,
+    PING_TIMEOUT,
+    PROBE_BYTES,
+)
+from tpi_redes.core.protocol import Header, ProtocolHandler
+
+logger = logging.getLogger("tpi-redes")
+
+
+@dataclass
+class PingResult:
+    """Outcome of a liveness probe against a receiver.
+
+    Attributes:
+        ip (str): Probed IP address.
+        port (int): Probed port.
+        protocol (str): "tcp" or "udp".
+        reachable (bool): Whether the receiver answered the probe.
+        rtt_ms (float | None): Round-trip time of the probe in milliseconds.
+        version (str | None): Version string reported by the receiver.
+        error (str | None): Why the probe failed, if it did.
+    """
+
+    ip: str
+    port: int
+    protocol: str
+    reachable: bool
+    rtt_ms: float | None = None
+    version: str | None = None
+    error: str | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the result as a JSON-serializable dictionary."""
+        return asdict(self)
+
+
+@dataclass
+class ProbeResult:
+    """Measured path to a TCP receiver and the settings suggested for it.
+
+    Attributes:
+        probe_bytes (int): Throwaway bytes sent.
+        rtt_ms (float): Round-trip time of a ping, in milliseconds.
+        throughput (float): Estimated bytes per second.
+        chunk_size (int): Suggested chunk size (about 10 ms of data).
+        socket_buffer (int): Suggested send buffer (twice the
+            bandwidth-delay product).
+    """
+
+    probe_bytes: int
+    rtt_ms: float
+    throughput: float
+    chunk_size: int
+    socket_buffer: int
+
+    def eta(self, total_bytes: int) -> float:
+        """Estimate how long sending some bytes will take at this throughput.
+
+        Args:
+            total_bytes: Bytes to send.
+
+        Returns:
+            float: Seconds, including one round trip.
+        """
+        return total_bytes / self.throughput + self.rtt_ms / 1000
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the result as a JSON-serializable dictionary."""
+        return asdict(self)
+
+
+def ping_peer(
+    addr: tuple[str, int], protocol: str, timeout: float = PING_TIMEOUT
+) -> PingResult:
+    """Check that a receiver is listening and measure its round-trip time.
+
+    Sends a ping message (see `ProtocolHandler`) and waits for the pong.
+    For TCP the RTT excludes the connection handshake.
+
+    Args:
+        addr: The receiver's (IP, Port) tuple.
+        protocol: "tcp" or "udp".
+        timeout: Seconds to wait for the connection and the reply.
+
+    Returns:
+        PingResult: The probe outcome; failures are reported, not raised.
+    """
+    ip, port = addr
+    try:
+        if protocol == "tcp":
+            rtt, version = _ping_tcp(addr, timeout)
+        else:
+            rtt, version = _ping_udp(addr, timeout)
+    except TimeoutError:
+        return PingResult(ip, port, protocol, False, error="timeout")
+    except (OSError, ValueError) as e:
+        return PingResult(ip, port, protocol, False, error=str(e))
+
+    logger.info(f"Pong from {ip}:{port} in {rtt * 1000:.1f} ms (v{version})")
+    return PingResult(
+        ip, port, protocol, True, rtt_ms=round(rtt * 1000, 3), version=version
+    )
+
+
+def probe_bandwidth(
+    addr: tuple[str, int], size: int = PROBE_BYTES, timeout: float = PING_TIMEOUT
+) -> ProbeResult:
+    """Measure RTT and throughput to a TCP receiver with throwaway data.
+
+    Pings once for the RTT, then sends a probe of `size` bytes and times it
+    until the receiver acknowledges; one RTT is subtracted from that time.
+
+    Args:
+        addr: The receiver's (IP, Port) tuple.
+        size: Probe bytes to send.
+        timeout: Seconds to wait for the connection and each reply.
+
+    Returns:
+        ProbeResult: The measurement and suggested settings.
+
+    Raises:
+        OSError: If the connection fails or times out.
+        ValueError: If the receiver does not acknowledge the probe.
+    """
+    with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as s:
+        s.settimeout(timeout)
+        s.connect(addr)
+        start = time.perf_counter()
+        s.sendall(ProtocolHandler.pack_ping())
+        pong = _pong_header(_recv_exact(s, ProtocolHandler.HEADER_SIZE))
+        rtt = time.perf_counter() - start
+        _recv_exact(s, pong.name_len)
+
+        start = time.perf_counter()
+        s.sendall(ProtocolHandler.pack_probe(size))
+        payload = bytes(min(size, 65536))
+        remaining = size
+        while remaining:
+            s.sendall(payload[:remaining])
+            remaining -= min(remaining, len(payload))
+        reply = ProtocolHandler.unpack_header(
+            _recv_exact(s, ProtocolHandler.HEADER_SIZE)
+        )
+        elapsed = time.perf_counter() - start
+        if reply.op_code == ProtocolHandler.ABORT_OP_CODE:
+            abort = ProtocolHandler.unpack_abort(
+                _recv_exact(s, reply.name_len), _recv_exact(s, reply.hash_len)
+            )
+            raise ValueError(f"Receiver rejected the probe: {abort.message}")
+        if reply.op_code != ProtocolHandler.ACK_OP_CODE or reply.file_size != size:
+            raise ValueError("Receiver did not acknowledge the probe")
+
+    throughput = size / max(elapsed - rtt, 1e-6)
+    bdp = throughput * rtt
+    result = ProbeResult(
+        probe_bytes=size,
+        rtt_ms=round(rtt * 1000, 3),
+        throughput=round(throughput, 1),
+        chunk_size=_power_of_two(throughput / 100, 4096, 65536),
+        socket_buffer=_power_of_two(2 * bdp, 64 * 1024, 16 * 1024 * 1024),
+    )
+    logger.info(
+        f"Probe to {addr[0]}:{addr[1]}: {throughput / 1e6:.1f} MB/s, "
+        f"RTT {rtt * 1000:.1f} ms"
+    )
+    return result
+
+
+def _power_of_two(value: float, low: int, high: int) -> int:
+    """Round a size down to a power of two within bounds.
+
+    Args:
+        value: The size wanted.
+        low: Smallest result (a power of two).
+        high: Largest result (a power of two).
+
+    Returns:
+        int: The bounded power of two.
+    """
+    size = low
+    while size * 2 <= min(value, high):
+        size *= 2
+    return size
+
+
+def _ping_tcp(addr: tuple[str, int], timeout: float) -> tuple[float, str]:
+    """Probe a TCP receiver.
+
+    Args:
+        addr: The receiver's (IP, Port) tuple.
+        timeout: Socket timeout in seconds.
+
+    Returns:
+        tuple[float, str]: RTT in seconds and the receiver's version.
+
+    Raises:
+        ValueError: If the reply is not a pong.
+    """
+    with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as s:
+        s.settimeout(timeout)
+        s.connect(addr)
+        start = time.perf_counter()
+        s.sendall(ProtocolHandler.pack_ping())
+        header_data = _recv_exact(s, ProtocolHandler.HEADER_SIZE)
+        rtt = time.perf_counter() - start
+        header = _pong_header(header_data)
+        return rtt, _recv_exact(s, header.name_len).decode("utf-8", "replace")
+
+
+def _ping_udp(addr: tuple[str, int], timeout: float) -> tuple[float, str]:
+    """Probe a UDP receiver.
+
+    Args:
+        addr: The receiver's (IP, Port) tuple.
+        timeout: Socket timeout in seconds.
+
+    Returns:
+        tuple[float, str]: RTT in seconds and the receiver's version.
+
+    Raises:
+        ValueError: If the reply is not a pong.
+    """
+    with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as s:
+        s.settimeout(timeout)
+        start = time.perf_counter()
+        s.sendto(ProtocolHandler.pack_ping(), addr)
+        data, _ = s.recvfrom(MAX_UDP_PACKET_SIZE)
+        rtt = time.perf_counter() - start
+        header = _pong_header(data[: ProtocolHandler.HEADER_SIZE])
+        version = data[ProtocolHandler.HEADER_SIZE :][: header.name_len]
+        return rtt, version.decode("utf-8", "replace")
+
+
+def _recv_exact(s: socket.socket, n: int) -> bytes:
+    """Receive exactly n bytes from the socket.
+
+    Args:
+        s: The socket object.
+        n: Number of bytes to receive.
+
+    Returns:
+        bytes: The received data.
+
+    Raises:
+        ConnectionError: If the peer closes the connection early.
+    """
+    data = b""
+    while len(data) < n:
+        packet = s.recv(n - len(data))
+        if not packet:
+            raise ConnectionError("Connection closed by receiver")
+        data += packet
+    return data
+
+
+def _pong_header(data: bytes) -> Header:
+    """Parse a pong, reporting what a foreign service replied instead.
+
+    Args:
+        data: The reply's first bytes.
+
+    Returns:
+        Header: The pong header.
+
+    Raises:
+        ValueError: If the reply is not a pong, with its first bytes.
+    """
+    if (
+        len(data) != ProtocolHandler.HEADER_SIZE
+        or data[:1] != ProtocolHandler.PONG_OP_CODE
+    ):
+        raise ValueError(
+            f"Not a tpi-redes receiver; it replied {data[:PEER_PREVIEW_BYTES]!r}"
+        )
+    return ProtocolHandler.unpack_header(data)
@@ -0,0 +1,159 @@
+import hashlib
+import logging
+import os
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import CHUNK_SIZE, PROGRESS_REPORT_INTERVAL_BYTES
+from tpi_redes.core.protocol import AbortReason
+from tpi_redes.observability.audit_log import AuditLog
+from tpi_redes.observability.packet_logger import PacketLogger
+from tpi_redes.services.knock import KnockGate
+from tpi_redes.services.sleep_inhibitor import SleepInhibitor
+from tpi_redes.transfer.routing import RouteRule
+from tpi_redes.transport.pacing import Pacer
+from tpi_redes.transport.tcp_server import TCPServer
+
+logger = logging.getLogger("tpi-redes")
+
+
+class RawTCPServer(TCPServer):
+    """TCP receiver for unframed byte streams (e.g. from `nc` or `socat`).
+
+    Each connection is stored as one file: everything received until EOF is
+    written under the configured name and its SHA-256 is computed on the fly.
+    Since the size is unknown in advance, events carry no `total` and there
+    is no sender checksum to verify against.
+    """
+
+    def __init__(
+        self,
+        host: str,
+        port: int,
+        save_dir: str,
+        raw_name: str,
+        per_peer_dirs: bool = False,
+        audit: bool = False,
+        sleep_inhibitor: SleepInhibitor | None = None,
+        audit_log: AuditLog | None = None,
+        routes: list[RouteRule] | None = None,
+        knock_gate: KnockGate | None = None,
+    ):
+        """Initialize the raw receiver.
+
+        Args:
+            host: IP address to bind to.
+            port: Port number to use.
+            save_dir: Path to storage directory.
+            raw_name: File name for received streams; later connections get a
+                numeric suffix instead of overwriting earlier ones.
+            per_peer_dirs: Whether to group received files by sender IP.
+            audit: Whether to run in read-only audit mode.
+            sleep_inhibitor: Optional inhibitor held during active transfers.
+            audit_log: Optional journal recording every received file.
+            routes: Optional rules mapping sender addresses to directories.
+            knock_gate: Optional authorization gate for incoming senders.
+        """
+        super().__init__(
+            host,
+            port,
+            save_dir,
+            per_peer_dirs,
+            audit,
+            sleep_inhibitor,
+            audit_log,
+            routes,
+            knock_gate,
+        )
+        self.raw_name = Path(raw_name).name
+
+    def handle_client(self, conn: Any, addr: Any):
+        """Store everything received on the connection until EOF.
+
+        Args:
+            conn: The accepted socket object.
+            addr: The client address tuple (IP, Port).
+
+        Returns:
+            None: No return value.
+        """
+        self._pacer = Pacer(self.max_rate) if self.max_rate else None
+        save_path = self._unique_path(self.output_dir_for(addr) / self.raw_name)
+        filename = save_path.name
+
+        logger.info(f"Receiving raw stream from {addr} into '{filename}'...")
+        start_event: dict[str, Any] = {
+            "type": "TRANSFER_UPDATE",
+            "status": "start",
+            "filename": filename,
+            "total": None,
+            "raw": True,
+        }
+        if route := self.matched_route(addr):
+            start_event["route"] = route
+        PacketLogger.emit_event(start_event)
+
+        sha256_hash = hashlib.sha256()
+        received_bytes = 0
+        reported_bytes = 0
+        try:
+            with open(os.devnull if self.audit else save_path, "wb") as f:
+                while chunk := conn.recv(CHUNK_SIZE):
+                    self._throttle(len(chunk))
+                    f.write(chunk)
+                    sha256_hash.update(chunk)
+                    received_bytes += len(chunk)
+
+                    if (
+                        received_bytes - reported_bytes
+                        >= PROGRESS_REPORT_INTERVAL_BYTES
+                    ):
+                        reported_bytes = received_bytes
+                        self._emit_stream_progress(filename, received_bytes)
+        except ConnectionError as e:
+            logger.debug(f"Connection from {addr} reset: {e}")
+        except OSError as e:
+            logger.error(f"Cannot store '{filename}': {e}")
+            self._emit_aborted(filename, AbortReason.WRITE_ERROR, "local", str(e))
+            self._discard_partial(save_path)
+            self.record_audit(
+                addr, filename, received_bytes, "", f"aborted:{AbortReason.WRITE_ERROR}"
+            )
+            return
+
+        digest = sha256_hash.hexdigest()
+        complete_event: dict[str, Any] = {
+            "type": "TRANSFER_UPDATE",
+            "status": "complete",
+            "filename": filename,
+            "current": received_bytes,
+            "sha256": digest,
+            "raw": True,
+        }
+
+        if self.audit:
+            logger.info(f"[audit] Would store '{save_path}' ({received_bytes} bytes).")
+            complete_event |= {"audit": True, "path": str(save_path)}
+        else:
+            with open(Path(f"{save_path}.sha256"), "w") as f:
+                f.write(digest)
+            logger.info(f"Raw stream saved to '{save_path}' ({received_bytes} bytes).")
+
+        self.record_audit(addr, filename, received_bytes, digest, "raw")
+        PacketLogger.emit_event(complete_event)
+
+    def _unique_path(self, path: Path) -> Path:
+        """Return `path`, or a suffixed variant if it already exists.
+
+        Args:
+            path: The preferred destination.
+
+        Returns:
+            Path: A destination that does not exist yet (`name-1.ext`, ...).
+        """
+        candidate = path
+        counter = 1
+        while candidate.exists():
+            candidate = path.with_name(f"{path.stem}-{counter}{path.suffix}")
+            counter += 1
+        return candidate
@@ -0,0 +1,255 @@
+import asyncio
+import dataclasses
+import logging
+import socket
+import time
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from tpi_redes.config import MAX_UDP_PACKET_SIZE
+from tpi_redes.core.base import BaseServer
+from tpi_redes.observability.audit_log import AuditLog
+from tpi_redes.transport.tcp_server import TCPServer
+from tpi_redes.transport.udp_server import UDPServer
+
+logger = logging.getLogger("tpi-redes")
+
+
+@dataclass
+class TransferResult:
+    """Outcome of a one-shot receive.
+
+    Attributes:
+        ok (bool): True if a file was stored and its checksum verified.
+        status (str): `"verified"`, `"mismatch"`, `"aborted:<reason>"` or
+            `"timeout"` if the deadline passed first.
+        path (Path | None): Where the file was stored; None if nothing was
+            kept (aborted, timed out or audit mode).
+        filename (str | None): Name announced by the sender.
+        size (int): Bytes received.
+        sha256 (str): Checksum of the received content.
+        peer (str | None): Sender as `ip:port`.
+    """
+
+    ok: bool
+    status: str
+    path: Path | None = None
+    filename: str | None = None
+    size: int = 0
+    sha256: str = ""
+    peer: str | None = None
+
+
+class _CaptureFirstFile(BaseServer):
+    """Keeps the outcome of the first file the server records."""
+
+    result: TransferResult | None = None
+
+    def record_audit(
+        self,
+        addr: tuple[str, int],
+        filename: str,
+        size: int,
+        sha256: str,
+        result: str,
+        validation: list[dict[str, Any]] | None = None,
+    ):
+        """Journal the file as usual and remember it if it is the first one.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+            filename: Name of the file.
+            size: Bytes received.
+            sha256: Checksum of the received content.
+            result: Outcome, e.g. "verified", "mismatch" or "aborted:<reason>".
+            validation: Results of post-transfer validators, if any ran.
+
+        Returns:
+            None: No return value.
+        """
+        super().record_audit(addr, filename, size, sha256, result, validation)
+        if self.result is not None:
+            return
+        stored = result in ("verified", "mismatch") and not self.audit
+        self.result = TransferResult(
+            ok=result == "verified",
+            status=result,
+            path=self.output_dir_for(addr) / filename if stored else None,
+            filename=filename,
+            size=size,
+            sha256=sha256,
+            peer=f"{addr[0]}:{addr[1]}",
+        )
+
+
+class _OneShotTCPServer(_CaptureFirstFile, TCPServer):
+    """TCP receiver that remembers its first file."""
+
+    def serve_once(self, deadline_at: float | None):
+        """Accept connections until one of them delivers a file.
+
+        Args:
+            deadline_at: `time.monotonic()` value to give up at, if any.
+
+        Returns:
+            None: No return value.
+
+        Raises:
+            TimeoutError: If the deadline passes while waiting.
+        """
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as s:
+            s.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
+            s.bind((self.host, self.port))
+            s.listen()
+            logger.info(f"Waiting for one file on {self.host}:{self.port} (TCP)")
+
+            while self.result is None:
+                s.settimeout(_remaining(deadline_at))
+                conn, addr = s.accept()
+                if not self.is_authorized(addr):
+                    conn.close()
+                    continue
+                with conn, self.inhibit_sleep():
+                    conn.settimeout(_remaining(deadline_at))
+                    self.handle_client(conn, addr)
+
+
+class _OneShotUDPServer(_CaptureFirstFile, UDPServer):
+    """UDP receiver that remembers its first file."""
+
+    def serve_once(self, deadline_at: float | None):
+        """Process datagrams until a session completes a file.
+
+        Args:
+            deadline_at: `time.monotonic()` value to give up at, if any.
+
+        Returns:
+            None: No return value.
+
+        Raises:
+            TimeoutError: If the deadline passes while waiting.
+        """
+        with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as s:
+            s.bind((self.host, self.port))
+            self.sock = s
+            logger.info(f"Waiting for one file on {self.host}:{self.port} (UDP)")
+
+            while self.result is None:
+                s.settimeout(_remaining(deadline_at))
+                data, addr = s.recvfrom(MAX_UDP_PACKET_SIZE)
+                if self.is_authorized(addr):
+                    self.process_datagram(data, addr)
+
+
+def receive_single_file(
+    port: int,
+    protocol: str = "tcp",
+    output_dir: str | Path = ".",
+    host: str = "0.0.0.0",
+    deadline: float | None = None,
+    audit_log: AuditLog | None = None,
+) -> TransferResult:
+    """Wait for one file, store it and return the outcome.
+
+    Meant for scripts and embedders that need a single file without running
+    `start-server`. Over TCP the first connection that sends a file is
+    served to completion; the result describes its first file. Events are
+    still printed to stdout as JSON lines.
+
+    Args:
+        port: Port to listen on.
+        protocol: `"tcp"` or `"udp"`.
+        output_dir: Directory where the file is stored.
+        host: IP address to bind to.
+        deadline: Optional limit in seconds for the whole receive.
+        audit_log: Optional journal recording the received file.
+
+    Returns:
+        TransferResult: What was received, or status `"timeout"` if the
+        deadline passed before a file was complete.
+
+    Raises:
+        ValueError: If the protocol is unknown.
+        ProtocolDisabledError: If UDP is requested but disabled.
+    """
+    deadline_at = time.monotonic() + deadline if deadline is not None else None
+    save_dir = str(output_dir)
+    server: _OneShotTCPServer | _OneShotUDPServer
+    if protocol == "tcp":
+        server = _OneShotTCPServer(host, port, save_dir, audit_log=audit_log)
+    elif protocol == "udp":
+        server = _OneShotUDPServer(host, port, save_dir, audit_log=audit_log)
+    else:
+        raise ValueError(f"Unknown protocol '{protocol}'")
+
+    try:
+        server.serve_once(deadline_at)
+    except TimeoutError:
+        logger.warning(f"No file received within {deadline}s.")
+
+    result = server.result
+    if result is None:
+        return TransferResult(ok=False, status="timeout")
+    if result.status.startswith("aborted:") and _expired(deadline_at):
+        # The server reports a socket timeout mid-file as a failed read.
+        return dataclasses.replace(result, status="timeout")
+    return result
+
+
+async def receive_single_file_async(
+    port: int,
+    protocol: str = "tcp",
+    output_dir: str | Path = ".",
+    host: str = "0.0.0.0",
+    deadline: float | None = None,
+    audit_log: AuditLog | None = None,
+) -> TransferResult:
+    """Awaitable form of `receive_single_file`, run in a worker thread.
+
+    Args:
+        port: Port to listen on.
+        protocol: `"tcp"` or `"udp"`.
+        output_dir: Directory where the file is stored.
+        host: IP address to bind to.
+        deadline: Optional limit in seconds for the whole receive.
+        audit_log: Optional journal recording the received file.
+
+    Returns:
+        TransferResult: See `receive_single_file`.
+    """
+    return await asyncio.to_thread(
+        receive_single_file, port, protocol, output_dir, host, deadline, audit_log
+    )
+
+
+def _remaining(deadline_at: float | None) -> float | None:
+    """Return the seconds left before the deadline, for socket timeouts.
+
+    Args:
+        deadline_at: `time.monotonic()` value to give up at, if any.
+
+    Returns:
+        float | None: Seconds left, or None to block without a limit.
+
+    Raises:
+        TimeoutError: If the deadline has already passed.
+    """
+    if deadline_at is None:
+        return None
+    remaining = deadline_at - time.monotonic()
+    if remaining <= 0:
+        raise TimeoutError("Deadline exceeded")
+    return remaining
+
+
+def _expired(deadline_at: float | None) -> bool:
+    """Return whether the deadline, if any, has passed.
+
+    Args:
+        deadline_at: `time.monotonic()` value to give up at, or None.
+
+    Returns:
+        bool: True if a deadline was set and has passed.
+    """
+    return deadline_at is not None and time.monotonic() >= deadline_at
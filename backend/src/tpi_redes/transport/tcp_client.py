@@ -1,11 +1,51 @@
+import hashlib
 import logging
 import socket
 import time
 from pathlib import Path
+from typing import Any, BinaryIO
 
-from tpi_redes.config import CHUNK_SIZE
-from tpi_redes.core.protocol import ProtocolHandler
-from tpi_redes.transfer.integrity import IntegrityVerifier
+from tpi_redes import __version__
+from tpi_redes.config import (
+    ABORT_DRAIN_TIMEOUT,
+    ACK_TIMEOUT,
+    CHUNK_SIZE,
+    PEER_PREVIEW_BYTES,
+)
+from tpi_redes.core.errors import (
+    ChecksumMismatchError,
+    NotAFileTransferPeerError,
+    SourceModifiedError,
+    TransferAbortedError,
+)
+from tpi_redes.core.protocol import (
+    AbortMessage,
+    AbortReason,
+    Header,
+    ProtocolHandler,
+)
+from tpi_redes.observability.audit_log import AuditLog
+from tpi_redes.observability.environment import (
+    compatibility_warning,
+    environment_snapshot,
+)
+from tpi_redes.services.pause_control import PauseControl
+from tpi_redes.transfer.buffer_pool import BufferPool
+from tpi_redes.transfer.bundle import bundle_prefix
+from tpi_redes.transfer.checksum_cache import ChecksumCache, file_checksum
+from tpi_redes.transfer.file_inspector import inspect_file
+from tpi_redes.transfer.file_validator import FileValidator
+from tpi_redes.transfer.manifest import (
+    MANIFEST_NAME,
+    build_batch_preview,
+    build_manifest,
+)
+from tpi_redes.transfer.readahead import ReadaheadReader
+from tpi_redes.transfer.sla import SlaMonitor
+from tpi_redes.transfer.source_watch import SourceWatcher
+from tpi_redes.transfer.speed_alert import SpeedMonitor
+from tpi_redes.transfer.summary import SummaryRecorder, TransferSummary
+from tpi_redes.transport.debug_hooks import ChunkShaper, DebugHooks
 
 logger = logging.getLogger("tpi-redes")
 
@@ -15,8 +55,25 @@ class TCPClient:
 
     Establishes a connection to a specific IP and port, and sequentially
     transmits the provided files adhering to the custom binary protocol.
+    Watches the reverse channel for abort messages from the receiver and,
+    when asked to, waits for the receiver to confirm each file before
+    starting the next one.
     """
 
+    _mid_file = False
+    _audit_log: AuditLog | None = None
+    _peer = ""
+    _sla: SlaMonitor | None = None
+    _speed: SpeedMonitor | None = None
+    _cancel_when_slow = False
+    _batch_sent = 0
+    _checksum_cache: ChecksumCache | None = None
+    _confirm = False
+    _prefix_only = False
+    _shaper: ChunkShaper | None = None
+    _ack_timeout = ACK_TIMEOUT
+    _socket_buffer: int | None = None
+
     def send_files(
         self,
         files: list[Path],
@@ -24,7 +81,21 @@ class TCPClient:
         port: int,
         delay: float = 0.0,
         chunk_size: int = CHUNK_SIZE,
-    ):
+        max_read_bytes: int | None = None,
+        emit_manifest: bool = False,
+        audit_log: AuditLog | None = None,
+        sla: float | None = None,
+        checksum_cache: ChecksumCache | None = None,
+        confirm: bool = False,
+        restarts: int = 0,
+        ack_timeout: float = ACK_TIMEOUT,
+        socket_buffer: int | None = None,
+        speed_monitor: SpeedMonitor | None = None,
+        cancel_when_slow: bool = False,
+        pause: PauseControl | None = None,
+        bundle_threshold: int = 0,
+        trailing_hash: bool = False,
+    ) -> TransferSummary:
         """Send multiple files to a remote TCP server.
 
         Established a single TCP connection and reuses it for all files in the list.
@@ -36,85 +107,1055 @@ class TCPClient:
             port: Destination port number.
             delay: Optional delay in seconds between sending chunks (for testing).
             chunk_size: Size of data chunks to read/send (default: CHUNK_SIZE).
+            max_read_bytes: Optional cap on the bytes sent per file.
+            emit_manifest: Send a `SHA256SUMS` manifest of the batch first so
+                the receiver can verify and store it alongside the files.
+            audit_log: Optional journal recording every sent file.
+            sla: Expected duration of the whole batch in seconds; an
+                `SLA_WARNING` event is emitted if it is likely to be missed.
+            checksum_cache: Optional cache of checksums of unchanged files.
+            confirm: Wait for the receiver to acknowledge each file with the
+                checksum it stored before sending the next one.
+            restarts: How many times to reconnect and resend the batch from
+                its first file when a file changes while being sent. The
+                whole batch is resent because an atomic receiver rolls back
+                the files already stored on the aborted connection. Not
+                supported together with `emit_manifest`.
+            ack_timeout: Seconds to wait for each confirmation.
+            socket_buffer: Optional send buffer size (`SO_SNDBUF`), which
+                bounds how much data can be in flight.
+            speed_monitor: Optional monitor emitting `SLOW_TRANSFER` and
+                `SPEED_SATURATED` events for the batch.
+            cancel_when_slow: Abort with reason `cancelled` when the monitor
+                reports the transfer as too slow.
+            pause: Optional control that holds the transfer between chunks
+                while paused.
+            bundle_threshold: Send runs of files up to this size together in
+                bundles; 0 disables bundling. Bundling is skipped when every
+                file must be confirmed.
+            trailing_hash: Hash each file while sending it and send the
+                checksum after the content, so it is read only once. Skipped
+                with `confirm` or `emit_manifest`, which announce checksums
+                beforehand, and with receivers that predate it.
 
         Raises:
+            ValueError: If restarts are combined with a manifest.
             FileNotFoundError: If no valid existing files are provided.
+            InvalidFileError: If a file is not a regular file.
             ConnectionError: If the connection to the server fails.
+            TransferAbortedError: If either side aborts the transfer.
+            SourceModifiedError: If a file changes while being sent and no
+                restarts are left.
+            TimeoutError: If a confirmation does not arrive in time.
+            ChecksumMismatchError: If the receiver confirms a file whose
+                checksum differs from the source.
 
         Returns:
-            None: No return value.
+            TransferSummary: Timings, speeds and per-file results of the batch.
         """
+        if restarts and emit_manifest:
+            raise ValueError("Restarts cannot be combined with a manifest")
         valid_files = [f for f in files if f.exists()]
         if not valid_files:
             raise FileNotFoundError("No valid files to send")
+        file_sizes = {
+            f: FileValidator(f).transfer_size(max_read_bytes) for f in valid_files
+        }
+        file_hashes: dict[Path, str] = {}
+        if emit_manifest:
+            logger.info("Calculating hashes for manifest...")
+            file_hashes = {
+                f: file_checksum(f, file_sizes[f], checksum_cache) for f in valid_files
+            }
+
+        self._audit_log = audit_log
+        self._checksum_cache = checksum_cache
+        self._peer = f"{ip}:{port}"
+        self._batch_sent = 0
+        self._confirm = confirm
+        self._ack_timeout = ack_timeout
+        self._socket_buffer = socket_buffer
+        self._bundle_threshold = 0 if confirm else bundle_threshold
+        self._trailing_hash = trailing_hash and not (confirm or emit_manifest)
+        self._prefix_only = max_read_bytes is not None
+        self._shaper = self._debug_shaper()
+        self._settings = {
+            "chunk_size": chunk_size,
+            "socket_buffer": socket_buffer,
+            "ack_timeout": ack_timeout,
+            "confirm": confirm,
+            "manifest": emit_manifest,
+            "max_restarts": restarts,
+            "bundle_threshold": self._bundle_threshold,
+            "trailing_hash": self._trailing_hash,
+        }
+        self._retries: dict[str, int] = {}
+        self._peer_version: str | None = None
+        self._protocol_version: int | None = None
+        self._disabled: list[str] = []
+        mismatched: list[str] = []
+        self._sla = SlaMonitor(sla, sum(file_sizes.values())) if sla else None
+        self._speed = speed_monitor
+        self._cancel_when_slow = cancel_when_slow
+        self._pause = pause
+        self._summary = SummaryRecorder()
+        self._pool = BufferPool(chunk_size)
 
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        PacketLogger.reset_errors()
+
+        pending = list(valid_files)
+        attempt = 0
+        while pending:
+            try:
+                self._send_batch(
+                    pending,
+                    ip,
+                    port,
+                    file_sizes,
+                    file_hashes if emit_manifest else None,
+                    delay,
+                    chunk_size,
+                    mismatched,
+                )
+            except SourceModifiedError as e:
+                if attempt >= restarts:
+                    raise
+                attempt += 1
+                self._retries[e.path.name] = self._retries.get(e.path.name, 0) + 1
+                file_sizes[e.path] = FileValidator(e.path).transfer_size(
+                    max_read_bytes
+                )
+                # Files sent on the aborted connection may have been rolled
+                # back, so none of them counts as sent.
+                pending = list(valid_files)
+                mismatched.clear()
+                self._summary.files.clear()
+                self._batch_sent = 0
+                self._emit_restarting(e.path.name, attempt)
+
+        summary = self._summary.finish(
+            "tcp",
+            self._peer,
+            self._settings,
+            retries=sum(self._retries.values()),
+            errors=PacketLogger.error_stats(),
+            warnings=PacketLogger.warning_stats(),
+            peer_version=self._peer_version,
+            disabled_features=self._disabled,
+        )
+        if mismatched:
+            raise ChecksumMismatchError(mismatched)
+        return summary
+
+    def _send_batch(
+        self,
+        pending: list[Path],
+        ip: str,
+        port: int,
+        file_sizes: dict[Path, int],
+        manifest_hashes: dict[Path, str] | None,
+        delay: float,
+        chunk_size: int,
+        mismatched: list[str],
+    ):
+        """Send the pending files over one connection.
+
+        Files are removed from `pending` as they are sent, so a batch
+        interrupted by a source change can resume with the file that changed.
+
+        Args:
+            pending: Files still to send; consumed in order.
+            ip: Destination IP address.
+            port: Destination port number.
+            file_sizes: Bytes to send per file.
+            manifest_hashes: Checksums to announce in a manifest first, if any.
+            delay: Optional delay in seconds between chunks.
+            chunk_size: Size of data chunks to read/send.
+            mismatched: Collects files the receiver confirmed with another
+                checksum.
+
+        Raises:
+            TransferAbortedError: If either side aborts the transfer.
+
+        Returns:
+            None: No return value.
+        """
         logger.info(f"Connecting to {ip}:{port}...")
 
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as s:
+            if self._socket_buffer:
+                s.setsockopt(socket.SOL_SOCKET, socket.SO_SNDBUF, self._socket_buffer)
+            self._summary.enter("connect")
+            s.connect((ip, port))
+            local_ip, local_port = s.getsockname()
+            self._summary.enter("handshake")
+            self._exchange_preamble(s, pending[0].name)
+            self._environment = environment_snapshot(
+                "tcp",
+                f"{local_ip}:{local_port}",
+                self._peer,
+                self._peer_version,
+                self._settings,
+                self._protocol_version,
+            )
+
+            if len(pending) > 1 and "batch_preview" not in self._disabled:
+                self._send_batch_preview(s, {f.name: file_sizes[f] for f in pending})
+            if manifest_hashes is not None:
+                self._send_manifest(s, {f.name: h for f, h in manifest_hashes.items()})
+
+            while pending:
+                bundle: list[Path] = []
+                if self._bundle_threshold and "bundle" not in self._disabled:
+                    bundle = bundle_prefix(
+                        pending, file_sizes, self._bundle_threshold
+                    )
+                file_path = pending[0]
+                self._mid_file = False
+                self._summary.enter("data")
+                try:
+                    if bundle:
+                        self._send_bundle(s, bundle, file_sizes)
+                        del pending[: len(bundle)]
+                        continue
+                    receiver_verified = self._send_file(
+                        s,
+                        file_path,
+                        file_sizes[file_path],
+                        delay,
+                        chunk_size,
+                        manifest_hashes.get(file_path) if manifest_hashes else None,
+                    )
+                except KeyboardInterrupt:
+                    self._abort(
+                        s, file_path.name, AbortReason.CANCELLED, "Cancelled by user"
+                    )
+                    raise
+                pending.pop(0)
+                if receiver_verified is False:
+                    mismatched.append(file_path.name)
+
+    def _debug_shaper(self) -> ChunkShaper | None:
+        """Return a shaper for the configured debug hooks, if any are set.
+
+        Returns:
+            ChunkShaper | None: The shaper, or None when no hook is active.
+        """
+        hooks = DebugHooks.from_config()
+        return ChunkShaper(hooks) if hooks.active else None
+
+    def _emit_restarting(self, filename: str, attempt: int):
+        """Emit a TRANSFER_UPDATE event for a file sent again from scratch.
+
+        Args:
+            filename: The file that changed while being sent.
+            attempt: Number of this restart, starting at 1.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        logger.warning(f"'{filename}' changed while sending; restart {attempt}.")
+        PacketLogger.log_progress(
+            {
+                "type": "TRANSFER_UPDATE",
+                "status": "restarting",
+                "filename": filename,
+                "reason": AbortReason.SOURCE_MODIFIED,
+                "attempt": attempt,
+            }
+        )
+        PacketLogger.flush()
+
+    def send_stream(
+        self,
+        source: BinaryIO,
+        filename: str,
+        ip: str,
+        port: int,
+        chunk_size: int = CHUNK_SIZE,
+        audit_log: AuditLog | None = None,
+    ):
+        """Send a source of unknown length (e.g. stdin) to a remote TCP server.
+
+        Chunks are framed with their running offset and a trailer with the
+        final size and SHA-256 closes the stream, so the receiver can verify
+        it without knowing the size up front.
+
+        Args:
+            source: Binary stream read until EOF.
+            filename: Name the receiver stores the stream under.
+            ip: Destination IP address.
+            port: Destination port number.
+            chunk_size: Maximum bytes per chunk.
+            audit_log: Optional journal recording the sent stream.
+
+        Raises:
+            ConnectionError: If the connection to the server fails.
+            TransferAbortedError: If either side aborts the transfer.
+
+        Returns:
+            None: No return value.
+        """
         from tpi_redes.observability.packet_logger import PacketLogger
 
+        self._audit_log = audit_log
+        self._peer = f"{ip}:{port}"
+        self._mid_file = False
+        self._shaper = self._debug_shaper()
+        logger.info(f"Connecting to {ip}:{port}...")
+
         with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as s:
             s.connect((ip, port))
-            _local_ip, _local_port = s.getsockname()
+            self._exchange_preamble(s, filename)
+            s.sendall(ProtocolHandler.pack_stream_start(filename))
+            logger.info(f"Streaming '{filename}'...")
+            PacketLogger.log_progress(
+                {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "start",
+                    "filename": filename,
+                    "total": None,
+                }
+            )
+
+            sha256_hash = hashlib.sha256()
+            bytes_sent = 0
+            try:
+                while chunk := source.read(chunk_size):
+                    payload = self._shaper.shape(chunk) if self._shaper else chunk
+                    s.sendall(ProtocolHandler.pack_chunk(bytes_sent, payload))
+                    sha256_hash.update(chunk)
+                    bytes_sent += len(chunk)
+
+                    if abort := self._poll_peer_abort(s, filename):
+                        self._emit_aborted(
+                            filename, abort.reason, "peer", abort.message
+                        )
+                        raise TransferAbortedError(abort.reason, "peer", abort.message)
+
+                    PacketLogger.log_progress(
+                        {
+                            "type": "TRANSFER_UPDATE",
+                            "status": "progress",
+                            "filename": filename,
+                            "current": bytes_sent,
+                            "total": None,
+                        }
+                    )
+            except KeyboardInterrupt:
+                self._abort(s, filename, AbortReason.CANCELLED, "Cancelled by user")
+                raise
+
+            digest = sha256_hash.hexdigest()
+            s.sendall(ProtocolHandler.pack_trailer(bytes_sent, digest))
+
+        if self._audit_log:
+            self._audit_log.record(
+                "sent", self._peer, filename, bytes_sent, digest, "sent"
+            )
+        logger.info(f"Stream '{filename}' sent ({bytes_sent} bytes).")
+        PacketLogger.log_progress(
+            {
+                "type": "TRANSFER_UPDATE",
+                "status": "complete",
+                "filename": filename,
+                "current": bytes_sent,
+                "total": bytes_sent,
+            }
+        )
+        PacketLogger.flush()
+
+    def _send_file(
+        self,
+        s: socket.socket,
+        file_path: Path,
+        file_size: int,
+        delay: float,
+        chunk_size: int,
+        file_hash: str | None = None,
+    ) -> bool | None:
+        """Send a single file (header, metadata and content) over the socket.
+
+        Args:
+            s: The connected socket.
+            file_path: Path of the file to send.
+            file_size: Number of bytes to announce and send.
+            delay: Optional delay in seconds between chunks.
+            chunk_size: Size of data chunks to read/send.
+            file_hash: Precomputed SHA-256 of the content, if already known.
 
-            for file_path in valid_files:
+        Raises:
+            TransferAbortedError: If the file cannot be read or the receiver
+                aborts the transfer.
+            SourceModifiedError: If the file changes while being sent.
+            TimeoutError: If the receiver does not confirm the file in time.
+
+        Returns:
+            bool | None: Whether the receiver confirmed the file's checksum, or
+            None when no confirmation was requested.
+        """
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        filename = file_path.name
+        # FIFOs and devices may not give the same bytes twice: hash while sending.
+        read_once = not file_path.is_file()
+        hasher = None
+        if (
+            file_hash is None
+            and (self._trailing_hash or read_once)
+            and "trailing_hash" not in self._disabled
+        ):
+            hasher = hashlib.sha256()
+            file_hash = ""
+        elif read_once:
+            message = f"'{filename}' can only be read once; it needs a trailing hash"
+            self._abort(s, filename, AbortReason.SOURCE_ERROR, message)
+            raise TransferAbortedError(AbortReason.SOURCE_ERROR, "local", message)
+
+        try:
+            watcher = SourceWatcher(file_path, file_size, self._prefix_only)
+            if file_hash is None:
                 logger.info(f"Calculating hash for {file_path}...")
-                verifier = IntegrityVerifier(file_path)
-                file_hash = verifier.calculate_hash()
+                file_hash = file_checksum(file_path, file_size, self._checksum_cache)
+            mime = inspect_file(file_path).mime
+        except OSError as e:
+            self._abort(s, filename, AbortReason.SOURCE_ERROR, str(e))
+            raise TransferAbortedError(
+                AbortReason.SOURCE_ERROR, "local", str(e)
+            ) from e
+
+        op_code = (
+            ProtocolHandler.CONFIRMED_FILE_OP_CODE
+            if self._confirm
+            else ProtocolHandler.FILE_OP_CODE
+        )
+        if hasher:
+            op_code = ProtocolHandler.TRAILED_FILE_OP_CODE
+        content_type = ProtocolHandler.pack_content_type(mime)
+        header = ProtocolHandler.pack_header(op_code, filename, file_size, file_hash)
+        metadata = filename.encode("utf-8") + file_hash.encode("utf-8")
+
+        if "content_type" in self._disabled:
+            content_type = b""
+        s.sendall(content_type + header)
+        s.sendall(metadata)
+        framed = "framed_content" not in self._disabled
+        # Unframed content leaves no room for an abort until the file ends.
+        self._mid_file = not framed
+        logger.info(f"Sending content for '{filename}' ({mime})...")
+        PacketLogger.log_progress(
+            {
+                "type": "TRANSFER_UPDATE",
+                "status": "start",
+                "filename": filename,
+                "total": file_size,
+                "mime": mime,
+            }
+        )
+
+        total_bytes = file_size
+        bytes_sent = 0
+
+        current_seq = 1 + len(content_type) + len(header) + len(metadata)
+
+        with ReadaheadReader(
+            file_path, file_size, chunk_size, pool=self._pool
+        ) as reader:
+            while chunk := reader.read():
+                if self._pause and self._pause.paused:
+                    self._wait_resumed(filename, bytes_sent)
+                if hasher:
+                    hasher.update(chunk)
+                payload = self._shaper.shape(chunk) if self._shaper else chunk
+                if framed:
+                    s.sendall(ProtocolHandler.pack_chunk(bytes_sent, payload))
+                else:
+                    s.sendall(payload)
+
+                if delay > 0:
+                    time.sleep(delay)
+
+                chunk_len = len(chunk)
+                bytes_sent += chunk_len
 
-                file_size = file_path.stat().st_size
-                filename = file_path.name
+                current_seq += chunk_len
 
-                header = ProtocolHandler.pack_header(
-                    b"F", filename, file_size, file_hash
+                # Once the last chunk is out, the receiver's next message may
+                # be the acknowledgement; `_await_ack` reads it.
+                finished = self._confirm and bytes_sent >= total_bytes
+                if not finished and (abort := self._poll_peer_abort(s, filename)):
+                    self._emit_aborted(filename, abort.reason, "peer", abort.message)
+                    raise TransferAbortedError(abort.reason, "peer", abort.message)
+
+                if watcher.changed():
+                    self._source_modified(s, file_path)
+
+                if chunk_len > 0:
+                    PacketLogger.log_progress(
+                        {
+                            "type": "TRANSFER_UPDATE",
+                            "status": "progress",
+                            "filename": filename,
+                            "current": bytes_sent,
+                            "total": total_bytes,
+                            "buffer_fill": round(reader.fill, 2),
+                        }
+                    )
+                    self._check_sla(self._batch_sent + bytes_sent)
+                    self._check_speed(s, filename, self._batch_sent + bytes_sent)
+                    self._summary.sample(self._batch_sent + bytes_sent)
+
+        if watcher.changed(force=True):
+            self._source_modified(s, file_path)
+        if bytes_sent < file_size:
+            message = f"'{filename}' ended after {bytes_sent} of {file_size} bytes"
+            self._abort(s, filename, AbortReason.SOURCE_ERROR, message)
+            raise TransferAbortedError(AbortReason.SOURCE_ERROR, "local", message)
+        if hasher:
+            file_hash = hasher.hexdigest()
+            s.sendall(ProtocolHandler.pack_trailer(bytes_sent, file_hash))
+
+        self._mid_file = False
+        self._batch_sent += bytes_sent
+        self._check_sla(self._batch_sent)
+
+        receiver_verified = None
+        status = "sent"
+        if self._confirm:
+            self._summary.enter("verify")
+            receiver_verified = self._await_ack(s, filename, file_hash)
+            status = "confirmed" if receiver_verified else "mismatch"
+            if not receiver_verified:
+                logger.warning(f"Receiver stored '{filename}' with another checksum.")
+
+        if self._audit_log:
+            self._audit_log.record(
+                "sent", self._peer, filename, bytes_sent, file_hash, status
+            )
+        self._summary.file(filename, bytes_sent, status)
+        logger.info(f"File '{filename}' sent successfully.")
+        complete_event: dict[str, Any] = {
+            "type": "TRANSFER_UPDATE",
+            "status": "complete",
+            "filename": filename,
+            "environment": {
+                **self._environment,
+                "retries": self._retries.get(filename, 0),
+            },
+        }
+        if receiver_verified is not None:
+            complete_event["receiver_verified"] = receiver_verified
+        if self._sla:
+            complete_event["sla_missed"] = self._sla.missed
+        if self._speed:
+            complete_event["speed_alerts"] = self._speed.alerts
+        complete_event["buffer_pool"] = self._pool.stats()
+        PacketLogger.log_progress(complete_event)
+        PacketLogger.flush()
+        return receiver_verified
+
+    def _send_bundle(
+        self, s: socket.socket, files: list[Path], file_sizes: dict[Path, int]
+    ):
+        """Send several small files in a single bundle.
+
+        Each file is read whole and framed as its own file message, so the
+        receiver stores and verifies them one by one after checking the bundle.
+
+        Args:
+            s: The connected socket.
+            files: Files to bundle, in sending order.
+            file_sizes: Bytes to send per file.
+
+        Raises:
+            TransferAbortedError: If a file cannot be read or the receiver
+                aborts the transfer.
+            SourceModifiedError: If a file shrank since it was measured.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        payload = bytearray()
+        entries: list[tuple[str, int, str, str]] = []
+        for file_path in files:
+            filename = file_path.name
+            try:
+                with open(file_path, "rb") as f:
+                    data = f.read(file_sizes[file_path])
+                mime = inspect_file(file_path).mime
+            except OSError as e:
+                self._abort(s, filename, AbortReason.SOURCE_ERROR, str(e))
+                raise TransferAbortedError(
+                    AbortReason.SOURCE_ERROR, "local", str(e)
+                ) from e
+            if len(data) < file_sizes[file_path]:
+                self._source_modified(s, file_path)
+            file_hash = hashlib.sha256(data).hexdigest()
+            if "content_type" not in self._disabled:
+                payload += ProtocolHandler.pack_content_type(mime)
+            payload += ProtocolHandler.pack_header(
+                ProtocolHandler.FILE_OP_CODE, filename, len(data), file_hash
+            )
+            payload += filename.encode("utf-8") + file_hash.encode("utf-8")
+            if data and "framed_content" not in self._disabled:
+                payload += ProtocolHandler.pack_chunk(0, data)
+            else:
+                payload += data
+            entries.append((filename, len(data), file_hash, mime))
+
+        if self._pause and self._pause.paused:
+            self._wait_resumed(entries[0][0], 0)
+        logger.info(f"Sending {len(entries)} files in a bundle ({len(payload)} bytes)")
+        for filename, size, _, mime in entries:
+            PacketLogger.log_progress(
+                {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "start",
+                    "filename": filename,
+                    "total": size,
+                    "mime": mime,
+                    "bundled": True,
+                }
+            )
+        s.sendall(ProtocolHandler.pack_bundle(bytes(payload)))
+        s.sendall(payload)
+        if abort := self._poll_peer_abort(s, entries[-1][0]):
+            self._emit_aborted(entries[-1][0], abort.reason, "peer", abort.message)
+            raise TransferAbortedError(abort.reason, "peer", abort.message)
+
+        for filename, size, file_hash, _ in entries:
+            self._batch_sent += size
+            if self._audit_log:
+                self._audit_log.record(
+                    "sent", self._peer, filename, size, file_hash, "sent"
                 )
-                metadata = filename.encode("utf-8") + file_hash.encode("utf-8")
-
-                s.sendall(header)
-                s.sendall(metadata)
-                logger.info(f"Sending content for '{filename}'...")
-                PacketLogger.log_progress(
-                    {
-                        "type": "TRANSFER_UPDATE",
-                        "status": "start",
-                        "filename": filename,
-                        "total": file_size,
-                    }
+            self._summary.file(filename, size, "sent")
+            PacketLogger.log_progress(
+                {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "complete",
+                    "filename": filename,
+                    "bundled": True,
+                    "environment": {
+                        **self._environment,
+                        "retries": self._retries.get(filename, 0),
+                    },
+                }
+            )
+        self._check_sla(self._batch_sent)
+        self._summary.sample(self._batch_sent)
+        PacketLogger.flush()
+
+    def _source_modified(self, s: socket.socket, file_path: Path):
+        """Abort the transfer because the source changed while being sent.
+
+        Args:
+            s: The connected socket.
+            file_path: The file that changed.
+
+        Raises:
+            SourceModifiedError: Always.
+        """
+        error = SourceModifiedError(file_path)
+        logger.error(f"Aborting: {error.detail}.")
+        self._abort(s, file_path.name, AbortReason.SOURCE_MODIFIED, error.detail)
+        raise error
+
+    def _await_ack(self, s: socket.socket, filename: str, file_hash: str) -> bool:
+        """Wait for the receiver to confirm a file.
+
+        Args:
+            s: The connected socket.
+            filename: The file just sent.
+            file_hash: SHA-256 of the content that was sent.
+
+        Returns:
+            bool: True if the receiver stored the content with the same checksum.
+
+        Raises:
+            TransferAbortedError: If the receiver aborts, closes the connection
+                or answers with an unexpected message.
+            TimeoutError: If no confirmation arrives within the ack timeout.
+        """
+        s.settimeout(self._ack_timeout)
+        try:
+            header_data = self._recv_exact(s, ProtocolHandler.HEADER_SIZE)
+            if not header_data:
+                self._emit_aborted(filename, AbortReason.CONNECTION_LOST, "peer")
+                raise TransferAbortedError(AbortReason.CONNECTION_LOST, "peer")
+            header = self._reply_header(s, header_data, filename)
+            name_bytes = self._recv_exact(s, header.name_len)
+            hash_bytes = self._recv_exact(s, header.hash_len)
+        except TimeoutError as e:
+            raise TimeoutError(
+                f"Receiver did not confirm '{filename}' within {self._ack_timeout}s"
+            ) from e
+        finally:
+            s.settimeout(None)
+
+        if header.op_code == ProtocolHandler.ACK_OP_CODE:
+            ack = ProtocolHandler.unpack_ack(header, hash_bytes)
+            return ack.sha256 == file_hash
+        if header.op_code == ProtocolHandler.ABORT_OP_CODE:
+            abort = ProtocolHandler.unpack_abort(name_bytes, hash_bytes)
+        else:
+            abort = AbortMessage(
+                reason=AbortReason.PROTOCOL_ERROR,
+                message=f"Unexpected message from receiver: {header.op_code!r}",
+            )
+        self._emit_aborted(filename, abort.reason, "peer", abort.message)
+        raise TransferAbortedError(abort.reason, "peer", abort.message)
+
+    def _check_sla(self, done_bytes: int):
+        """Emit an SLA_WARNING event if the batch is falling behind its SLA.
+
+        Args:
+            done_bytes: Bytes sent so far across the batch.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        if self._sla and (warning := self._sla.check(done_bytes)):
+            logger.warning(
+                f"Transfer SLA {warning['status']}: {warning['elapsed']}s elapsed "
+                f"of {warning['deadline']}s."
+            )
+            PacketLogger.log_progress(warning)
+
+    def _check_speed(self, s: socket.socket, filename: str, done_bytes: int):
+        """Emit speed alerts and cancel a too-slow transfer if asked to.
+
+        Args:
+            s: The connected socket.
+            filename: The file being sent.
+            done_bytes: Bytes sent so far across the batch.
+
+        Raises:
+            TransferAbortedError: If the transfer is too slow and
+                `cancel_when_slow` is set.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        if not self._speed or not (alert := self._speed.check(done_bytes)):
+            return
+        logger.warning(
+            f"Transfer speed {alert['rate']:.0f} B/s vs {alert['threshold']} B/s "
+            f"for {alert['duration']}s."
+        )
+        PacketLogger.log_progress(alert)
+        if self._cancel_when_slow and self._speed.slow:
+            message = f"Slower than {self._speed.min_rate} B/s for {alert['duration']}s"
+            self._abort(s, filename, AbortReason.CANCELLED, message)
+            raise TransferAbortedError(AbortReason.CANCELLED, "local", message)
+
+    def _wait_resumed(self, filename: str, current: int):
+        """Hold the transfer while paused, emitting `paused` and `resumed`.
+
+        Args:
+            filename: The file being sent.
+            current: Bytes of the file sent so far.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        assert self._pause is not None
+        event = {"type": "TRANSFER_UPDATE", "filename": filename, "current": current}
+        PacketLogger.emit_event({**event, "status": "paused"})
+        logger.info(f"Transfer of '{filename}' paused.")
+        self._summary.enter("paused")
+        paused_for = self._pause.wait_resumed()
+        self._summary.enter("data")
+        if self._speed:
+            self._speed.skip(self._batch_sent + current)
+        PacketLogger.emit_event(
+            {**event, "status": "resumed", "paused_for": round(paused_for, 3)}
+        )
+
+    def _send_batch_preview(self, s: socket.socket, sizes: dict[str, int]):
+        """Announce the files of the batch and their sizes before sending.
+
+        Args:
+            s: The connected socket.
+            sizes: Mapping of file name to bytes to send, in sending order.
+
+        Returns:
+            None: No return value.
+        """
+        listing = build_batch_preview(sizes).encode("utf-8")
+        s.sendall(ProtocolHandler.pack_batch_preview(listing))
+        logger.info(
+            f"Announced batch of {len(sizes)} files, {sum(sizes.values())} bytes."
+        )
+
+    def _send_manifest(self, s: socket.socket, hashes: dict[str, str]):
+        """Send the batch manifest ahead of the files it lists.
+
+        Args:
+            s: The connected socket.
+            hashes: Mapping of file name to SHA-256 hex digest.
+
+        Returns:
+            None: No return value.
+        """
+        content = build_manifest(hashes).encode("utf-8")
+        manifest_hash = hashlib.sha256(content).hexdigest()
+        header = ProtocolHandler.pack_header(
+            ProtocolHandler.MANIFEST_OP_CODE, MANIFEST_NAME, len(content), manifest_hash
+        )
+        s.sendall(
+            header
+            + MANIFEST_NAME.encode("utf-8")
+            + manifest_hash.encode("utf-8")
+            + content
+        )
+        logger.info(f"Sent {MANIFEST_NAME} with {len(hashes)} entries.")
+
+    def _abort(
+        self, s: socket.socket, filename: str, reason: AbortReason, message: str
+    ):
+        """Abort the transfer locally, notifying the receiver when possible.
+
+        The abort message goes between files or content chunks; in the middle
+        of unframed content (peers older than protocol 5) the receiver
+        detects the closed connection instead.
+
+        Args:
+            s: The connected socket.
+            filename: The file being transferred.
+            reason: Why the transfer is aborted.
+            message: Human-readable detail.
+
+        Returns:
+            None: No return value.
+        """
+        if not self._mid_file:
+            try:
+                s.sendall(ProtocolHandler.pack_abort(reason, message))
+            except OSError as e:
+                logger.debug(f"Could not notify receiver of abort: {e}")
+        self._emit_aborted(filename, reason, "local", message)
+
+    def _poll_peer_abort(self, s: socket.socket, filename: str) -> AbortMessage | None:
+        """Check, without blocking, whether the receiver sent an abort message.
+
+        Args:
+            s: The connected socket.
+            filename: The file being sent, for the aborted event.
+
+        Returns:
+            AbortMessage | None: The receiver's abort notice, if one arrived.
+
+        Raises:
+            NotAFileTransferPeerError: If the peer sent something that is not
+                part of the protocol.
+        """
+        try:
+            pending = s.recv(1, socket.MSG_DONTWAIT | socket.MSG_PEEK)
+        except (BlockingIOError, InterruptedError):
+            return None
+        if not pending:
+            return None
+
+        s.settimeout(ABORT_DRAIN_TIMEOUT)
+        try:
+            header_data = self._recv_exact(s, ProtocolHandler.HEADER_SIZE)
+            header = self._reply_header(s, header_data, filename)
+            if header.op_code != ProtocolHandler.ABORT_OP_CODE:
+                return AbortMessage(
+                    reason=AbortReason.PROTOCOL_ERROR,
+                    message=f"Unexpected message from receiver: {header.op_code!r}",
                 )
+            reason_bytes = self._recv_exact(s, header.name_len)
+            message_bytes = self._recv_exact(s, header.hash_len)
+            return ProtocolHandler.unpack_abort(reason_bytes, message_bytes)
+        finally:
+            s.settimeout(None)
 
-                total_bytes = file_size
-                bytes_sent = 0
+    def _exchange_preamble(self, s: socket.socket, filename: str):
+        """Send our preamble and check the one the receiver answers with.
 
-                current_seq = 1 + len(header) + len(metadata)
+        An older receiver is still used, without the messages it does not
+        know; a `PEER_COMPATIBILITY` event lists them.
 
-                with open(file_path, "rb") as f:
-                    while chunk := f.read(chunk_size):
-                        s.sendall(chunk)
-
-                        if delay > 0:
-                            time.sleep(delay)
-
-                        chunk_len = len(chunk)
-                        bytes_sent += chunk_len
-
-                        current_seq += chunk_len
-
-                        if chunk_len > 0:
-                            PacketLogger.log_progress(
-                                {
-                                    "type": "TRANSFER_UPDATE",
-                                    "status": "progress",
-                                    "filename": filename,
-                                    "current": bytes_sent,
-                                    "total": total_bytes,
-                                }
-                            )
-
-                logger.info(f"File '{filename}' sent successfully.")
-                PacketLogger.log_progress(
-                    {
-                        "type": "TRANSFER_UPDATE",
-                        "status": "complete",
-                        "filename": filename,
-                    }
+        Args:
+            s: The connected socket.
+            filename: The first file to send, for the aborted event.
+
+        Returns:
+            None: No return value.
+
+        Raises:
+            NotAFileTransferPeerError: If the peer answers with something that
+                is not part of the protocol.
+            TransferAbortedError: If the receiver rejects our protocol version,
+                speaks one older than `MIN_PROTOCOL_VERSION` or does not
+                answer in time.
+        """
+        s.sendall(ProtocolHandler.pack_preamble(__version__))
+        s.settimeout(self._ack_timeout)
+        try:
+            abort = self._read_preamble_reply(s, filename)
+        except TimeoutError:
+            abort = AbortMessage(
+                reason=AbortReason.PROTOCOL_ERROR,
+                message=f"Receiver did not answer within {self._ack_timeout}s; "
+                "it may run an older tpi-redes version",
+            )
+        finally:
+            s.settimeout(None)
+
+        if abort:
+            logger.error(f"{self._peer} rejected the connection: {abort.message}")
+            self._emit_aborted(filename, abort.reason, "peer", abort.message)
+            raise TransferAbortedError(abort.reason, "peer", abort.message)
+
+        assert self._peer_version is not None and self._protocol_version
+        self._disabled = ProtocolHandler.disabled_features(self._protocol_version)
+        warning = compatibility_warning(
+            self._peer, self._peer_version, self._protocol_version
+        )
+        if warning:
+            from tpi_redes.observability.packet_logger import PacketLogger
+
+            logger.warning(
+                f"{self._peer} runs tpi-redes {self._peer_version} (protocol "
+                f"{self._protocol_version}); disabled: {', '.join(self._disabled)}"
+            )
+            PacketLogger.emit_event(warning)
+
+    def _read_preamble_reply(
+        self, s: socket.socket, filename: str
+    ) -> AbortMessage | None:
+        """Read the receiver's answer to our preamble.
+
+        Args:
+            s: The connected socket.
+            filename: The first file to send, for the aborted event.
+
+        Returns:
+            AbortMessage | None: Why the receiver cannot be used, or None if
+            it speaks a protocol version we support.
+
+        Raises:
+            NotAFileTransferPeerError: If the answer is not part of the protocol.
+        """
+        data = self._recv_exact(s, ProtocolHandler.PREAMBLE_SIZE)
+        if not data:
+            return AbortMessage(reason=AbortReason.CONNECTION_LOST, message="")
+        if data.startswith(ProtocolHandler.PREAMBLE_MAGIC):
+            preamble = ProtocolHandler.unpack_preamble(data)
+            if preamble.protocol_version >= ProtocolHandler.MIN_PROTOCOL_VERSION:
+                logger.debug(f"{self._peer} runs tpi-redes {preamble.version}")
+                self._peer_version = preamble.version
+                self._protocol_version = min(
+                    preamble.protocol_version, ProtocolHandler.PROTOCOL_VERSION
                 )
-                PacketLogger.flush()
+                return None
+            return AbortMessage(
+                reason=AbortReason.PROTOCOL_ERROR,
+                message=f"Receiver speaks protocol version "
+                f"{preamble.protocol_version}; this sender needs at least "
+                f"{ProtocolHandler.MIN_PROTOCOL_VERSION}",
+            )
+
+        header = self._reply_header(s, data, filename)
+        name_bytes = self._recv_exact(s, header.name_len)
+        hash_bytes = self._recv_exact(s, header.hash_len)
+        if header.op_code == ProtocolHandler.ABORT_OP_CODE:
+            return ProtocolHandler.unpack_abort(name_bytes, hash_bytes)
+        return AbortMessage(
+            reason=AbortReason.PROTOCOL_ERROR,
+            message=f"Unexpected message from receiver: {header.op_code!r}",
+        )
+
+    def _reply_header(self, s: socket.socket, data: bytes, filename: str) -> Header:
+        """Parse a message header from the receiver, rejecting foreign peers.
+
+        Args:
+            s: The connected socket, read for a longer diagnostic if needed.
+            data: The header bytes read so far.
+            filename: The file being sent, for the aborted event.
+
+        Returns:
+            Header: The parsed header.
+
+        Raises:
+            NotAFileTransferPeerError: If the bytes are not a receiver message,
+                e.g. an HTTP response.
+        """
+        if (
+            len(data) == ProtocolHandler.HEADER_SIZE
+            and data[:1] in ProtocolHandler.RECEIVER_OP_CODES
+        ):
+            return ProtocolHandler.unpack_header(data)
+        try:
+            data += s.recv(PEER_PREVIEW_BYTES, socket.MSG_DONTWAIT)
+        except OSError:
+            pass
+        error = NotAFileTransferPeerError(data[:PEER_PREVIEW_BYTES])
+        logger.error(f"{self._peer} {error.detail}")
+        self._emit_aborted(filename, error.reason, "peer", error.detail)
+        raise error
+
+    def _emit_aborted(
+        self, filename: str, reason: str, initiator: str, message: str = ""
+    ):
+        """Emit a TRANSFER_UPDATE event for an aborted transfer.
+
+        Args:
+            filename: The file being transferred.
+            reason: An `AbortReason` value.
+            initiator: `"local"` or `"peer"`.
+            message: Optional human-readable detail.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        if self._audit_log:
+            self._audit_log.record(
+                "sent", self._peer, filename, 0, "", f"aborted:{reason}"
+            )
+        PacketLogger.log_progress(
+            {
+                "type": "TRANSFER_UPDATE",
+                "status": "aborted",
+                "filename": filename,
+                "reason": reason,
+                "initiator": initiator,
+                "message": message,
+            }
+        )
+        PacketLogger.flush()
+
+    def _recv_exact(self, s: socket.socket, n: int) -> bytes:
+        """Receive exactly n bytes from the socket.
+
+        Args:
+            s: The socket object.
+            n: Number of bytes to receive.
+
+        Returns:
+            bytes: The received data, or empty bytes if EOF is reached.
+        """
+        data = b""
+        while len(data) < n:
+            packet = s.recv(n - len(data))
+            if not packet:
+                return b""
+            data += packet
+        return data
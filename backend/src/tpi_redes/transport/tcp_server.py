@@ -1,12 +1,45 @@
-import json
+import contextlib
+import errno
+import hashlib
 import logging
+import os
+import shutil
 import socket
+import tempfile
+from collections.abc import Iterator
 from pathlib import Path
-from typing import Any
+from typing import Any, BinaryIO
 
-from tpi_redes.config import CHUNK_SIZE, PROGRESS_REPORT_INTERVAL_BYTES
+from tpi_redes import __version__
+from tpi_redes.config import (
+    ABORT_DRAIN_TIMEOUT,
+    BATCH_PREVIEW_MAX_BYTES,
+    BUNDLE_MAX_BYTES,
+    CHUNK_SIZE,
+    PROBE_MAX_BYTES,
+    PROGRESS_REPORT_INTERVAL_BYTES,
+    SLOW_DISK_MIN_BYTES,
+    STAGING_PREFIX,
+)
 from tpi_redes.core.base import BaseServer
-from tpi_redes.core.protocol import ProtocolHandler
+from tpi_redes.core.errors import TransferAbortedError
+from tpi_redes.core.protocol import AbortReason, Preamble, ProtocolHandler
+from tpi_redes.observability.audit_log import AuditLog
+from tpi_redes.observability.environment import compatibility_warning
+from tpi_redes.observability.packet_logger import PacketLogger
+from tpi_redes.services.knock import KnockGate
+from tpi_redes.services.sleep_inhibitor import SleepInhibitor
+from tpi_redes.transfer.bundle import BundleReader
+from tpi_redes.transfer.content_index import ContentIndex, link_or_copy
+from tpi_redes.transfer.io_meter import IoMeter
+from tpi_redes.transfer.manifest import (
+    MANIFEST_NAME,
+    parse_batch_preview,
+    parse_manifest,
+)
+from tpi_redes.transfer.routing import RouteRule
+from tpi_redes.transfer.write_policy import SyncedWriter, WritePolicy, fsync_dir
+from tpi_redes.transport.pacing import Pacer
 
 logger = logging.getLogger("tpi-redes")
 
@@ -16,13 +49,74 @@ class TCPServer(BaseServer):
 
     Handles TCP connections, complying with the custom `ProtocolHandler`.
     Emits JSON events to stdout for IPC interaction with the frontend.
+    In audit mode the full protocol and checksum verification still run, but
+    content is discarded and completion events report where it would be stored.
+    In atomic mode each connection's files are staged and only moved into the
+    output directory once the whole batch has been received and verified.
+    With deduplication, a file whose checksum matches one already received is
+    verified in transit and then hard-linked to the existing copy.
+    Writes are buffered and synced to disk as the write policy says.
+    With `max_rate` set, content reads are paced so TCP flow control slows
+    the sender down to that rate.
     """
 
+    _pacer: Pacer | None = None
+
+    def __init__(
+        self,
+        host: str,
+        port: int,
+        save_dir: str,
+        per_peer_dirs: bool = False,
+        audit: bool = False,
+        sleep_inhibitor: SleepInhibitor | None = None,
+        audit_log: AuditLog | None = None,
+        routes: list[RouteRule] | None = None,
+        knock_gate: KnockGate | None = None,
+        atomic: bool = False,
+        dedup: bool = False,
+        write_policy: WritePolicy | None = None,
+    ):
+        """Initialize the TCP server.
+
+        Args:
+            host: IP address to bind to.
+            port: Port number to use.
+            save_dir: Path to storage directory.
+            per_peer_dirs: Whether to group received files by sender IP.
+            audit: Whether to run in read-only audit mode.
+            sleep_inhibitor: Optional inhibitor held during active transfers.
+            audit_log: Optional journal recording every received file.
+            routes: Optional rules mapping sender addresses to directories.
+            knock_gate: Optional authorization gate for incoming senders.
+            atomic: Whether to commit each connection's files all-or-nothing.
+            dedup: Whether to link files whose content was already received
+                instead of writing them again.
+            write_policy: Write buffering and fsync behavior; defaults to the
+                configured buffer size and never syncing.
+        """
+        super().__init__(
+            host,
+            port,
+            save_dir,
+            per_peer_dirs,
+            audit,
+            sleep_inhibitor,
+            audit_log,
+            routes,
+            knock_gate,
+        )
+        self.atomic = atomic
+        self.content_index = ContentIndex(Path(save_dir)) if dedup else None
+        self.write_policy = write_policy or WritePolicy()
+
     def start(self):
         """Start listening for TCP connections.
 
-        This method blocks the calling thread until a `KeyboardInterrupt` occurs.
-        It accepts connections and processes them sequentially via `handle_client`.
+        This method blocks the calling thread until a `KeyboardInterrupt` occurs,
+        until `max_files` files completed or until `window_ends` if set; the
+        connection in progress at that point is served to its end. It accepts
+        connections and processes them sequentially via `handle_client`.
 
         Returns:
             None: No return value.
@@ -35,10 +129,39 @@ class TCPServer(BaseServer):
 
             try:
                 while True:
-                    conn, addr = s.accept()
-                    with conn:
+                    remaining = self.window_remaining()
+                    if remaining is not None and remaining <= 0:
+                        logger.info("Receive window closed; stopping.")
+                        break
+                    s.settimeout(remaining)
+                    try:
+                        conn, addr = s.accept()
+                    except TimeoutError:
+                        continue
+                    if not self.is_authorized(addr):
+                        logger.warning(f"Closed connection from unauthorized {addr}")
+                        self.peer_stats.auth_failed(addr[0])
+                        conn.close()
+                        continue
+                    with conn, self.inhibit_sleep():
                         logger.info(f"Connected by {addr}")
-                        self.handle_client(conn, addr)
+                        self.active_transfers += 1
+                        self.peer_stats.connected(addr[0])
+                        try:
+                            self.handle_client(conn, addr)
+                        except Exception as e:
+                            # A bug in one session must not take the server down.
+                            message = f"{type(e).__name__}: {e}"
+                            logger.exception(f"Session with {addr} failed: {message}")
+                            self._emit_aborted(
+                                None, AbortReason.INTERNAL_ERROR, "local", message
+                            )
+                        finally:
+                            self.active_transfers -= 1
+                            self.peer_stats.disconnected(addr[0])
+                    if self.limit_reached:
+                        logger.info(f"Received {self.max_files} file(s); stopping.")
+                        break
             except KeyboardInterrupt:
                 logger.info("Server stopping...")
 
@@ -51,6 +174,10 @@ class TCPServer(BaseServer):
 
         Receives files sequentially over the established socket connection.
         Follows the protocol: Header -> Metadata -> Content.
+        Stops on an abort message from the sender; local write failures are
+        reported back to the sender with an abort message before closing. An
+        interrupt (Ctrl+C) mid-session cancels the transfer and tells the
+        sender so before stopping the server.
 
         Args:
             conn: The accepted socket object.
@@ -59,11 +186,72 @@ class TCPServer(BaseServer):
         Returns:
             None: No return value.
         """
+        self._pacer = Pacer(self.max_rate) if self.max_rate else None
+        output_dir = self.output_dir_for(addr)
+        if not self.atomic or self.audit:
+            self._receive_batch(conn, addr, output_dir)
+            return
+
+        staging_dir = Path(tempfile.mkdtemp(prefix=STAGING_PREFIX, dir=output_dir))
+        committed = False
+        first_outcome = len(self.outcomes)
+        self._staged = []
+        try:
+            committed = self._receive_batch(conn, addr, staging_dir)
+        finally:
+            self._finish_batch(addr, staging_dir, output_dir, committed, first_outcome)
+
+    def _receive_batch(self, conn: Any, addr: Any, store_dir: Path) -> bool:
+        """Receive files until the sender closes the connection.
+
+        Files the sender asked to confirm are acknowledged with the received
+        size and checksum once stored.
+
+        Args:
+            conn: The accepted socket object.
+            addr: The client address tuple (IP, Port).
+            store_dir: Directory where received files are written.
+
+        Returns:
+            bool: True if the stream ended cleanly and every file verified.
+        """
+        manifest: dict[str, str] = {}
+        batch_total: int | None = None
+        batch_done = 0
+        batch_ok = True
+        route = self.matched_route(addr)
+        first = True
+        filename: str | None = None
+        content_type: str | None = None
+        peer_version: str | None = None
+        framed = False
+        existing: Path | None = None
         try:
             while True:
+                filename, existing = None, None
                 header_data = self._recv_exact(conn, ProtocolHandler.HEADER_SIZE)
                 if not header_data:
-                    break
+                    return batch_ok
+
+                if first:
+                    first = False
+                    if header_data.startswith(ProtocolHandler.PREAMBLE_MAGIC):
+                        sender = self._answer_preamble(conn, addr, header_data)
+                        if sender is None:
+                            self.peer_stats.handshake_failed(addr[0])
+                            return False
+                        peer_version = sender.version
+                        framed = "framed_content" not in (
+                            ProtocolHandler.disabled_features(sender.protocol_version)
+                        )
+                        continue
+                    if header_data[:1] not in ProtocolHandler.SENDER_OP_CODES:
+                        logger.warning(
+                            f"Closed connection from {addr}: not a tpi-redes "
+                            f"sender; it sent {header_data!r}"
+                        )
+                        self.peer_stats.handshake_failed(addr[0])
+                        return False
 
                 header = ProtocolHandler.unpack_header(header_data)
 
@@ -71,72 +259,1061 @@ class TCPServer(BaseServer):
                 filename = filename_bytes.decode("utf-8")
 
                 hash_bytes = self._recv_exact(conn, header.hash_len)
+
+                if header.op_code == ProtocolHandler.ABORT_OP_CODE:
+                    abort = ProtocolHandler.unpack_abort(filename_bytes, hash_bytes)
+                    logger.warning(f"Sender {addr} aborted: {abort.reason}")
+                    self._emit_aborted(None, abort.reason, "peer", abort.message)
+                    return False
+
+                if header.op_code == ProtocolHandler.PING_OP_CODE:
+                    logger.debug(f"Ping from {addr}")
+                    conn.sendall(ProtocolHandler.pack_pong(__version__))
+                    continue
+
+                if header.op_code == ProtocolHandler.PROBE_OP_CODE:
+                    if not self._answer_probe(conn, addr, header.file_size):
+                        return False
+                    continue
+
+                if header.op_code == ProtocolHandler.MANIFEST_OP_CODE:
+                    received = self._receive_manifest(
+                        conn, store_dir, header.file_size, hash_bytes.decode("utf-8")
+                    )
+                    if received is None:
+                        return False
+                    manifest = received
+                    continue
+
+                if header.op_code == ProtocolHandler.CONTENT_TYPE_OP_CODE:
+                    content_type = filename
+                    continue
+
+                if header.op_code == ProtocolHandler.BUNDLE_OP_CODE:
+                    payload = self._receive_bundle(
+                        conn, addr, header.file_size, hash_bytes.decode("utf-8")
+                    )
+                    if payload is None:
+                        return False
+                    if not isinstance(conn, BundleReader):
+                        conn = BundleReader(conn)
+                    conn.feed(payload)
+                    continue
+
+                if header.op_code == ProtocolHandler.BATCH_OP_CODE:
+                    preview = self._receive_batch_preview(
+                        conn, addr, store_dir, header.file_size
+                    )
+                    if preview is None:
+                        return False
+                    batch_total = sum(preview.values())
+                    continue
+
+                if header.op_code == ProtocolHandler.STREAM_OP_CODE:
+                    stream_ok = self._receive_stream(
+                        conn, addr, store_dir, filename, route
+                    )
+                    if stream_ok is None:
+                        return False
+                    batch_ok = batch_ok and stream_ok
+                    continue
+
                 file_hash = hash_bytes.decode("utf-8")
                 logger.debug(f"Expected Hash: {file_hash}")
+                mime, content_type = content_type, None
 
                 logger.info(f"Receiving '{filename}' ({header.file_size} bytes)...")
-                print(
-                    json.dumps(
-                        {
-                            "type": "TRANSFER_UPDATE",
-                            "status": "start",
-                            "filename": filename,
-                            "total": header.file_size,
-                        }
-                    ),
-                    flush=True,
+                start_event: dict[str, Any] = {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "start",
+                    "filename": filename,
+                    "total": header.file_size,
+                }
+                if mime:
+                    start_event["mime"] = mime
+                if peer_version:
+                    start_event["peer_version"] = peer_version
+                if route:
+                    start_event["route"] = route
+                PacketLogger.emit_event(start_event)
+
+                save_path = store_dir / filename
+                if not self.audit:
+                    save_path.parent.mkdir(parents=True, exist_ok=True)
+
+                # A b'H' file's checksum arrives after it, too late to dedup.
+                if self.content_index and not self.audit and file_hash:
+                    existing = self.content_index.lookup(file_hash, header.file_size)
+                discard = self.audit or existing is not None
+
+                received_bytes, digest = 0, ""
+                meter = IoMeter()
+                try:
+                    with self._open_output(save_path, discard) as f:
+                        received_bytes, digest = self._receive_content(
+                            conn,
+                            f,
+                            filename,
+                            header.file_size,
+                            meter,
+                            (batch_done, batch_total) if batch_total else None,
+                            framed,
+                        )
+                except TransferAbortedError as e:
+                    if e.initiator == "local":
+                        logger.error(
+                            f"Invalid file '{filename}' from {addr}: {e.detail}"
+                        )
+                        self._abort_peer(conn, AbortReason(e.reason), e.detail)
+                    else:
+                        logger.warning(f"Sender {addr} aborted: {e.reason}")
+                    self._emit_aborted(filename, e.reason, e.initiator, e.detail)
+                    if not existing:
+                        self._discard_partial(save_path)
+                    self.record_audit(addr, filename, 0, "", f"aborted:{e.reason}")
+                    return False
+                except ConnectionError as e:
+                    logger.debug(f"Connection from {addr} reset: {e}")
+                except OSError as e:
+                    reason = (
+                        AbortReason.DISK_FULL
+                        if e.errno == errno.ENOSPC
+                        else AbortReason.WRITE_ERROR
+                    )
+                    logger.error(f"Cannot store '{filename}': {e}")
+                    self._abort_peer(conn, reason, str(e))
+                    self._emit_aborted(filename, reason, "local", str(e))
+                    if not existing:
+                        self._discard_partial(save_path)
+                    self.record_audit(
+                        addr, filename, received_bytes, "", f"aborted:{reason}"
+                    )
+                    return False
+
+                if received_bytes < header.file_size:
+                    logger.warning(f"Connection lost while receiving '{filename}'.")
+                    self._emit_aborted(filename, AbortReason.CONNECTION_LOST, "peer")
+                    if not existing:
+                        self._discard_partial(save_path)
+                    self.record_audit(
+                        addr,
+                        filename,
+                        received_bytes,
+                        digest,
+                        f"aborted:{AbortReason.CONNECTION_LOST}",
+                    )
+                    return False
+
+                if header.op_code == ProtocolHandler.TRAILED_FILE_OP_CODE:
+                    file_hash = self._receive_file_trailer(
+                        conn, addr, filename, save_path, received_bytes, digest
+                    )
+                    if file_hash is None:
+                        return False
+
+                batch_done += received_bytes
+                verified = digest == file_hash
+                batch_ok = batch_ok and verified
+                complete_event: dict[str, Any] = {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "complete",
+                    "filename": filename,
+                    "verified": verified,
+                    **meter.rates(),
+                }
+                if mime:
+                    complete_event["mime"] = mime
+                self._log_rates(filename, meter)
+                self._check_disk(filename, received_bytes, meter)
+                if filename in manifest:
+                    manifest_ok = manifest[filename] == digest
+                    complete_event["manifest_verified"] = manifest_ok
+                    batch_ok = batch_ok and manifest_ok
+                    if not manifest_ok:
+                        logger.warning(f"'{filename}' does not match {MANIFEST_NAME}.")
+
+                if self.audit:
+                    logger.info(
+                        f"[audit] Would store '{save_path}' "
+                        f"({received_bytes} bytes, verified={verified})."
+                    )
+                    complete_event |= {"audit": True, "path": str(save_path)}
+                elif existing and not verified:
+                    logger.warning(f"'{filename}' failed verification; not stored.")
+                else:
+                    if existing:
+                        if existing != save_path:
+                            link_or_copy(existing, save_path)
+                        complete_event["deduplicated_from"] = str(existing)
+                        logger.info(f"'{filename}' matches '{existing}'; linked.")
+                    hash_path = Path(f"{save_path}.sha256")
+                    with open(hash_path, "w") as f:
+                        f.write(file_hash)
+                    logger.info(f"File '{filename}' received successfully.")
+
+                validation: list[dict[str, Any]] = []
+                if verified and not self.audit:
+                    validation = self.validate(save_path, mime)
+                if validation:
+                    complete_event["validation"] = validation
+                self.record_audit(
+                    addr,
+                    filename,
+                    received_bytes,
+                    digest,
+                    "verified" if verified else "mismatch",
+                    validation,
+                )
+                filename = None  # Recorded; a later failure is not this file's.
+                PacketLogger.emit_event(complete_event)
+
+                if header.op_code == ProtocolHandler.CONFIRMED_FILE_OP_CODE:
+                    conn.sendall(ProtocolHandler.pack_ack(received_bytes, digest))
+
+        except KeyboardInterrupt:
+            message = "Cancelled by receiver"
+            logger.warning(f"Cancelled transfer from {addr}.")
+            self._abort_peer(conn, AbortReason.CANCELLED, message)
+            self._emit_aborted(filename, AbortReason.CANCELLED, "local", message)
+            if filename:
+                if not existing:
+                    self._discard_partial(store_dir / filename)
+                self.record_audit(
+                    addr, filename, 0, "", f"aborted:{AbortReason.CANCELLED}"
+                )
+            raise
+        except Exception as e:
+            message = f"{type(e).__name__}: {e}"
+            logger.exception(f"Error handling client {addr}: {message}")
+            self._abort_peer(conn, AbortReason.INTERNAL_ERROR, message)
+            self._emit_aborted(filename, AbortReason.INTERNAL_ERROR, "local", message)
+            if filename:
+                self.record_audit(
+                    addr, filename, 0, "", f"aborted:{AbortReason.INTERNAL_ERROR}"
+                )
+            return False
+
+    def store_verified(
+        self, addr: tuple[str, int], filename: str, size: int, sha256: str
+    ):
+        """Also offer a verified file, at its final location, for dedup.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+            filename: Name of the file.
+            size: Bytes received.
+            sha256: Checksum of the received content.
+
+        Returns:
+            None: No return value.
+        """
+        super().store_verified(addr, filename, size, sha256)
+        if self.content_index:
+            self.content_index.add(sha256, self.output_dir_for(addr) / filename)
+
+    def _finish_batch(
+        self,
+        addr: Any,
+        staging_dir: Path,
+        output_dir: Path,
+        committed: bool,
+        first_outcome: int,
+    ):
+        """Move a staged batch into place, or roll it back.
+
+        On rollback the outcomes already recorded for the batch's files are
+        replaced by `rolled_back`, so they no longer count as completed.
+
+        Args:
+            addr: The client address tuple (IP, Port).
+            staging_dir: Directory holding the batch's received files.
+            output_dir: Final destination of the files.
+            committed: Whether the batch completed and verified.
+            first_outcome: Index in `outcomes` of the batch's first file.
+
+        Returns:
+            None: No return value.
+        """
+        staged, self._staged = self._staged or [], None
+        entries = sorted(staging_dir.iterdir())
+        if committed:
+            for entry in entries:
+                os.replace(entry, output_dir / entry.name)
+            staging_dir.rmdir()
+            if entries and self.write_policy.syncs:
+                fsync_dir(output_dir)
+            for args in staged:
+                self.store_verified(*args)
+        else:
+            shutil.rmtree(staging_dir, ignore_errors=True)
+
+        if committed and not entries:
+            return
+
+        status = "committed" if committed else "rolled_back"
+        files = [e.name for e in entries if not e.name.endswith(".sha256")]
+        logger.info(f"Batch {status} ({len(files)} files).")
+        if not committed:
+            batch = self.outcomes[first_outcome:]
+            for name in files:
+                recorded = [o for o in batch if o["filename"] == name]
+                if not recorded:
+                    self.record_audit(addr, name, 0, "", "rolled_back")
+                    continue
+                recorded[-1]["result"] = "rolled_back"
+                if self.audit_log:
+                    self.audit_log.record(
+                        "received", recorded[-1]["peer"], name, 0, "", "rolled_back"
+                    )
+        PacketLogger.emit_event(
+            {"type": "BATCH_UPDATE", "status": status, "files": files}
+        )
+
+    @contextlib.contextmanager
+    def _open_output(
+        self, save_path: Path, discard: bool
+    ) -> Iterator[BinaryIO | SyncedWriter]:
+        """Open a received file for writing under the write policy.
+
+        The file is synced when the block exits normally, if the policy asks
+        for it.
+
+        Args:
+            save_path: Where the content is stored.
+            discard: Whether to throw the content away instead.
+
+        Returns:
+            Iterator[BinaryIO | SyncedWriter]: Context manager yielding the
+            destination to write content to.
+        """
+        if discard:
+            with open(os.devnull, "wb") as f:
+                yield f
+            return
+        with open(save_path, "wb", buffering=self.write_policy.buffer_bytes) as f:
+            writer = SyncedWriter(f, self.write_policy)
+            yield writer
+            writer.complete()
+
+    def _answer_probe(self, conn: Any, addr: Any, size: int) -> bool:
+        """Discard a bandwidth probe's bytes and acknowledge them.
+
+        Args:
+            conn: The accepted socket object.
+            addr: The client address tuple (IP, Port).
+            size: Probe bytes announced by the sender.
+
+        Returns:
+            bool: False if the probe was too large or the connection dropped.
+        """
+        if size > PROBE_MAX_BYTES:
+            detail = f"Probe of {size} bytes exceeds {PROBE_MAX_BYTES}"
+            logger.warning(f"Rejected probe from {addr}: {detail}.")
+            self._abort_peer(conn, AbortReason.PROTOCOL_ERROR, detail)
+            return False
+        remaining = size
+        while remaining:
+            data = conn.recv(min(remaining, 65536))
+            if not data:
+                return False
+            remaining -= len(data)
+        logger.debug(f"Probe of {size} bytes from {addr}")
+        conn.sendall(ProtocolHandler.pack_ack(size, ""))
+        return True
+
+    def _answer_preamble(self, conn: Any, addr: Any, data: bytes) -> Preamble | None:
+        """Answer a sender's preamble with ours, or abort if it is too old.
+
+        The answer carries the lower of both protocol versions, so an older
+        sender keeps working; a `PEER_COMPATIBILITY` event lists what it
+        cannot use.
+
+        Args:
+            conn: The accepted socket object.
+            addr: The client address tuple (IP, Port).
+            data: The preamble bytes received.
+
+        Returns:
+            Preamble | None: The sender's version and the protocol version
+            negotiated with it, or None if it was rejected.
+        """
+        preamble = ProtocolHandler.unpack_preamble(data)
+        if preamble.protocol_version < ProtocolHandler.MIN_PROTOCOL_VERSION:
+            message = (
+                f"Unsupported protocol version {preamble.protocol_version}; "
+                f"this receiver needs at least {ProtocolHandler.MIN_PROTOCOL_VERSION}"
+            )
+            logger.warning(f"Rejected {addr} (tpi-redes {preamble.version}): {message}")
+            self._abort_peer(conn, AbortReason.PROTOCOL_ERROR, message)
+            self._emit_aborted(None, AbortReason.PROTOCOL_ERROR, "local", message)
+            return None
+
+        logger.debug(f"{addr} runs tpi-redes {preamble.version}")
+        negotiated = min(preamble.protocol_version, ProtocolHandler.PROTOCOL_VERSION)
+        conn.sendall(ProtocolHandler.pack_preamble(__version__, negotiated))
+        peer = f"{addr[0]}:{addr[1]}"
+        if warning := compatibility_warning(peer, preamble.version, negotiated):
+            logger.warning(
+                f"{peer} runs tpi-redes {preamble.version} (protocol {negotiated}); "
+                f"disabled: {', '.join(warning['disabled'])}"
+            )
+            PacketLogger.emit_event(warning)
+        return Preamble(negotiated, preamble.version)
+
+    def _receive_content(
+        self,
+        conn: Any,
+        f: BinaryIO | SyncedWriter,
+        filename: str,
+        file_size: int,
+        meter: IoMeter,
+        batch: tuple[int, int] | None = None,
+        framed: bool = False,
+    ) -> tuple[int, str]:
+        """Receive a file's content from the socket into an open file.
+
+        The SHA-256 of the content is computed on the fly, and time spent
+        reading the socket and writing the file is measured separately.
+
+        Args:
+            conn: The socket object.
+            f: Destination opened with `_open_output`.
+            filename: Name of the file, used in progress events.
+            file_size: Number of content bytes announced in the header.
+            meter: Collects network and disk throughput.
+            batch: Bytes of the batch received before this file and its
+                total, when the sender announced it; progress events then
+                also carry `batch_current` and `batch_total`.
+            framed: Whether the content arrives in chunk frames (protocol 5).
+
+        Returns:
+            tuple[int, str]: Bytes received (less than `file_size` if the peer
+            disconnected) and the hex digest of the received content.
+
+        Raises:
+            TransferAbortedError: If the sender aborts mid-file or a chunk
+                frame does not continue the content.
+        """
+        sha256_hash = hashlib.sha256()
+        received_bytes = 0
+        frame_left = 0
+        while received_bytes < file_size:
+            if self.pause and self.pause.paused:
+                self._wait_resumed(filename, received_bytes)
+            chunk_size = min(CHUNK_SIZE, file_size - received_bytes)
+            if framed:
+                if not frame_left:
+                    frame_left = self._read_chunk_frame(
+                        conn, received_bytes, file_size - received_bytes
+                    )
+                    if not frame_left:
+                        break
+                chunk_size = min(chunk_size, frame_left)
+            started = meter.clock()
+            chunk = self._recv_exact(conn, chunk_size)
+            if not chunk:
+                break
+            frame_left -= len(chunk)
+            meter.network(len(chunk), started)
+            self._throttle(len(chunk))
+            started = meter.clock()
+            f.write(chunk)
+            meter.disk(len(chunk), started)
+            sha256_hash.update(chunk)
+            received_bytes += len(chunk)
+
+            if (
+                received_bytes % PROGRESS_REPORT_INTERVAL_BYTES < CHUNK_SIZE
+                or received_bytes == file_size
+            ):
+                progress_event: dict[str, Any] = {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "progress",
+                    "filename": filename,
+                    "current": received_bytes,
+                    "total": file_size,
+                    **meter.rates(),
+                }
+                if batch:
+                    progress_event["batch_current"] = batch[0] + received_bytes
+                    progress_event["batch_total"] = batch[1]
+                PacketLogger.emit_event(progress_event)
+        return received_bytes, sha256_hash.hexdigest()
+
+    def _read_chunk_frame(self, conn: Any, offset: int, limit: int) -> int:
+        """Read the frame announcing the next chunk of a file's content.
+
+        Args:
+            conn: The socket object.
+            offset: Content bytes received so far.
+            limit: Content bytes still expected.
+
+        Returns:
+            int: The chunk's length, or 0 if the connection closed.
+
+        Raises:
+            TransferAbortedError: If the sender aborts instead, or the frame
+                does not continue the content.
+        """
+        op_code = self._recv_exact(conn, 1)
+        if op_code == ProtocolHandler.CHUNK_OP_CODE:
+            frame = self._recv_exact(conn, ProtocolHandler.CHUNK_HEADER_SIZE - 1)
+            if not frame:
+                return 0
+            chunk = ProtocolHandler.unpack_chunk(op_code + frame)
+            if chunk.offset != offset or not 0 < chunk.length <= limit:
+                raise TransferAbortedError(
+                    AbortReason.PROTOCOL_ERROR,
+                    "local",
+                    f"Chunk of {chunk.length} bytes at offset {chunk.offset}, "
+                    f"expected offset {offset} and at most {limit} bytes",
                 )
+            return chunk.length
+        if op_code == ProtocolHandler.ABORT_OP_CODE:
+            rest = self._recv_exact(conn, ProtocolHandler.HEADER_SIZE - 1)
+            if not rest:
+                return 0
+            header = ProtocolHandler.unpack_header(op_code + rest)
+            reason_bytes = self._recv_exact(conn, header.name_len)
+            message_bytes = self._recv_exact(conn, header.hash_len)
+            abort = ProtocolHandler.unpack_abort(reason_bytes, message_bytes)
+            raise TransferAbortedError(abort.reason, "peer", abort.message)
+        if op_code:
+            raise TransferAbortedError(
+                AbortReason.PROTOCOL_ERROR,
+                "local",
+                f"Unexpected message in file content: {op_code!r}",
+            )
+        return 0
+
+    def _throttle(self, nbytes: int):
+        """Hold the next read long enough to keep the ingress under `max_rate`.
+
+        Args:
+            nbytes: Bytes just read from the connection.
 
-                save_path = Path(self.save_dir) / filename
-                save_path.parent.mkdir(parents=True, exist_ok=True)
+        Returns:
+            None: No return value.
+        """
+        if self._pacer:
+            self._pacer.wait(nbytes)
 
-                received_bytes = 0
-                with open(save_path, "wb") as f:
-                    while received_bytes < header.file_size:
-                        chunk_size = min(
-                            CHUNK_SIZE, header.file_size - received_bytes
+    def _wait_resumed(self, filename: str, current: int):
+        """Stop reading while paused; TCP flow control then holds the sender.
+
+        Args:
+            filename: The file being received.
+            current: Bytes of the file received so far.
+
+        Returns:
+            None: No return value.
+        """
+        assert self.pause is not None
+        event = {"type": "TRANSFER_UPDATE", "filename": filename, "current": current}
+        PacketLogger.emit_event({**event, "status": "paused"})
+        logger.info(f"Reception of '{filename}' paused.")
+        paused_for = self.pause.wait_resumed()
+        PacketLogger.emit_event(
+            {**event, "status": "resumed", "paused_for": round(paused_for, 3)}
+        )
+
+    def _receive_stream(
+        self, conn: Any, addr: Any, store_dir: Path, filename: str, route: str | None
+    ) -> bool | None:
+        """Receive a stream of unknown length, closed by a size/checksum trailer.
+
+        Progress events carry no `total` until the trailer arrives, and chunks
+        must arrive at the running offset they announce.
+
+        Args:
+            conn: The socket object.
+            addr: The client address tuple (IP, Port).
+            store_dir: Directory where the stream is written.
+            filename: Name announced by the sender.
+            route: Routing rule that selected `store_dir`, if any.
+
+        Returns:
+            bool | None: Whether the stream matched its trailer, or None if the
+            session must end (connection lost, aborted or malformed stream).
+        """
+        logger.info(f"Receiving stream '{filename}' (size unknown)...")
+        start_event: dict[str, Any] = {
+            "type": "TRANSFER_UPDATE",
+            "status": "start",
+            "filename": filename,
+            "total": None,
+            "stream": True,
+        }
+        if route:
+            start_event["route"] = route
+        PacketLogger.emit_event(start_event)
+
+        save_path = store_dir / filename
+        if not self.audit:
+            save_path.parent.mkdir(parents=True, exist_ok=True)
+
+        sha256_hash = hashlib.sha256()
+        received_bytes = 0
+        reported_bytes = 0
+        trailer = None
+        abort_reason: str | None = None
+        error = ""
+        meter = IoMeter()
+        try:
+            with self._open_output(save_path, self.audit) as f:
+                while trailer is None and not error:
+                    op_code = self._recv_exact(conn, 1)
+                    if op_code == ProtocolHandler.CHUNK_OP_CODE:
+                        frame = self._recv_exact(
+                            conn, ProtocolHandler.CHUNK_HEADER_SIZE - 1
                         )
-                        chunk = self._recv_exact(conn, chunk_size)
-                        if not chunk:
+                        if not frame:
+                            break
+                        chunk = ProtocolHandler.unpack_chunk(op_code + frame)
+                        if chunk.offset != received_bytes:
+                            error = (
+                                f"Chunk at offset {chunk.offset}, "
+                                f"expected {received_bytes}"
+                            )
+                            break
+                        remaining = chunk.length
+                        while remaining:
+                            started = meter.clock()
+                            data = self._recv_exact(conn, min(CHUNK_SIZE, remaining))
+                            if not data:
+                                break
+                            meter.network(len(data), started)
+                            self._throttle(len(data))
+                            started = meter.clock()
+                            f.write(data)
+                            meter.disk(len(data), started)
+                            sha256_hash.update(data)
+                            received_bytes += len(data)
+                            remaining -= len(data)
+                        if remaining:
                             break
-                        f.write(chunk)
-                        received_bytes += len(chunk)
-
                         if (
-                            received_bytes % PROGRESS_REPORT_INTERVAL_BYTES < CHUNK_SIZE
-                            or received_bytes == header.file_size
+                            received_bytes - reported_bytes
+                            >= PROGRESS_REPORT_INTERVAL_BYTES
                         ):
-                            print(
-                                json.dumps(
-                                    {
-                                        "type": "TRANSFER_UPDATE",
-                                        "status": "progress",
-                                        "filename": filename,
-                                        "current": received_bytes,
-                                        "total": header.file_size,
-                                    }
-                                ),
-                                flush=True,
+                            reported_bytes = received_bytes
+                            self._emit_stream_progress(filename, received_bytes, meter)
+                    elif op_code in (
+                        ProtocolHandler.TRAILER_OP_CODE,
+                        ProtocolHandler.ABORT_OP_CODE,
+                    ):
+                        rest = self._recv_exact(conn, ProtocolHandler.HEADER_SIZE - 1)
+                        if not rest:
+                            break
+                        header = ProtocolHandler.unpack_header(op_code + rest)
+                        name_bytes = self._recv_exact(conn, header.name_len)
+                        hash_bytes = self._recv_exact(conn, header.hash_len)
+                        if op_code == ProtocolHandler.ABORT_OP_CODE:
+                            abort = ProtocolHandler.unpack_abort(name_bytes, hash_bytes)
+                            logger.warning(f"Sender {addr} aborted: {abort.reason}")
+                            self._emit_aborted(
+                                filename, abort.reason, "peer", abort.message
                             )
+                            abort_reason = abort.reason
+                            break
+                        trailer = (header.file_size, hash_bytes.decode("utf-8"))
+                    elif op_code:
+                        error = f"Unexpected message in stream: {op_code!r}"
+                    else:
+                        break
+        except ConnectionError as e:
+            logger.debug(f"Connection from {addr} reset: {e}")
+        except OSError as e:
+            reason = (
+                AbortReason.DISK_FULL
+                if e.errno == errno.ENOSPC
+                else AbortReason.WRITE_ERROR
+            )
+            logger.error(f"Cannot store '{filename}': {e}")
+            self._abort_peer(conn, reason, str(e))
+            self._emit_aborted(filename, reason, "local", str(e))
+            self._discard_partial(save_path)
+            self.record_audit(addr, filename, received_bytes, "", f"aborted:{reason}")
+            return None
 
-                hash_path = Path(f"{save_path}.sha256")
-                with open(hash_path, "w") as f:
-                    f.write(file_hash)
-
-                logger.info(f"File '{filename}' received successfully.")
-                print(
-                    json.dumps(
-                        {
-                            "type": "TRANSFER_UPDATE",
-                            "status": "complete",
-                            "filename": filename,
-                        }
-                    ),
-                    flush=True,
-                )
+        if trailer is None:
+            if error:
+                abort_reason = AbortReason.PROTOCOL_ERROR
+                logger.error(f"Invalid stream '{filename}' from {addr}: {error}")
+                self._abort_peer(conn, abort_reason, error)
+                self._emit_aborted(filename, abort_reason, "local", error)
+            elif abort_reason is None:
+                abort_reason = AbortReason.CONNECTION_LOST
+                logger.warning(f"Connection lost while receiving '{filename}'.")
+                self._emit_aborted(filename, abort_reason, "peer")
+            self._discard_partial(save_path)
+            self.record_audit(
+                addr, filename, received_bytes, "", f"aborted:{abort_reason}"
+            )
+            return None
 
-        except Exception as e:
-            logger.error(f"Error handling client {addr}: {e}")
+        total_size, file_hash = trailer
+        digest = sha256_hash.hexdigest()
+        verified = total_size == received_bytes and file_hash == digest
+        if self.audit:
+            logger.info(
+                f"[audit] Would store '{save_path}' "
+                f"({received_bytes} bytes, verified={verified})."
+            )
+        else:
+            with open(Path(f"{save_path}.sha256"), "w") as f:
+                f.write(file_hash)
+            logger.info(f"Stream '{filename}' received ({received_bytes} bytes).")
+        self._log_rates(filename, meter)
+        self._check_disk(filename, received_bytes, meter)
+
+        self.record_audit(
+            addr,
+            filename,
+            received_bytes,
+            digest,
+            "verified" if verified else "mismatch",
+        )
+        complete_event: dict[str, Any] = {
+            "type": "TRANSFER_UPDATE",
+            "status": "complete",
+            "filename": filename,
+            "current": received_bytes,
+            "total": total_size,
+            "verified": verified,
+            "stream": True,
+            **meter.rates(),
+        }
+        if self.audit:
+            complete_event |= {"audit": True, "path": str(save_path)}
+        PacketLogger.emit_event(complete_event)
+        return verified
+
+    def _receive_file_trailer(
+        self,
+        conn: Any,
+        addr: Any,
+        filename: str,
+        save_path: Path,
+        size: int,
+        digest: str,
+    ) -> str | None:
+        """Read the trailer carrying the checksum of a b'H' file.
+
+        If the sender aborts instead, or the trailer is missing or does not
+        match the received size, the file is discarded and recorded as
+        aborted.
+
+        Args:
+            conn: The accepted socket object.
+            addr: The client address tuple (IP, Port).
+            filename: The file's name.
+            save_path: Where the file was written.
+            size: Bytes received.
+            digest: SHA-256 of the received content.
+
+        Returns:
+            str | None: The SHA-256 the sender computed while sending, or None
+            if the file was aborted.
+        """
+        data = self._recv_exact(conn, ProtocolHandler.HEADER_SIZE)
+        if not data:
+            logger.warning(f"Connection lost before the checksum of '{filename}'.")
+            reason = AbortReason.CONNECTION_LOST
+            self._emit_aborted(filename, reason, "peer")
+        else:
+            header = ProtocolHandler.unpack_header(data)
+            name_bytes = self._recv_exact(conn, header.name_len)
+            hash_bytes = self._recv_exact(conn, header.hash_len)
+            if header.op_code == ProtocolHandler.ABORT_OP_CODE:
+                abort = ProtocolHandler.unpack_abort(name_bytes, hash_bytes)
+                logger.warning(f"Sender {addr} aborted: {abort.reason}")
+                reason = abort.reason
+                self._emit_aborted(filename, reason, "peer", abort.message)
+            elif (
+                header.op_code != ProtocolHandler.TRAILER_OP_CODE
+                or header.file_size != size
+            ):
+                message = f"Expected the checksum trailer of '{filename}'"
+                logger.error(f"Invalid file '{filename}' from {addr}: {message}")
+                reason = AbortReason.PROTOCOL_ERROR
+                self._abort_peer(conn, reason, message)
+                self._emit_aborted(filename, reason, "local", message)
+            else:
+                return hash_bytes.decode("utf-8")
+        self._discard_partial(save_path)
+        self.record_audit(addr, filename, size, digest, f"aborted:{reason}")
+        return None
+
+    def _log_rates(self, filename: str, meter: IoMeter):
+        """Log where a file's receive time went, to spot the bottleneck.
+
+        Args:
+            filename: Name of the file.
+            meter: Throughput measured while receiving it.
+
+        Returns:
+            None: No return value.
+        """
+        rates = {
+            stage: f"{rate / 1024 / 1024:.1f} MB/s" if rate is not None else "n/a"
+            for stage, rate in meter.rates().items()
+        }
+        logger.info(
+            f"'{filename}': network {rates['network_rate']}, "
+            f"disk {rates['disk_rate']}"
+        )
+
+    def _check_disk(self, filename: str, size: int, meter: IoMeter):
+        """Warn when storing a file was slower than receiving it.
+
+        Args:
+            filename: Name of the file.
+            size: Bytes received.
+            meter: Throughput measured while receiving it.
+
+        Returns:
+            None: No return value.
+        """
+        rates = meter.rates()
+        network, disk = rates["network_rate"], rates["disk_rate"]
+        if size < SLOW_DISK_MIN_BYTES or not network or not disk or disk >= network:
+            return
+        message = (
+            f"Disk writes ({disk / 1024 / 1024:.1f} MB/s) were slower than the "
+            f"network ({network / 1024 / 1024:.1f} MB/s)"
+        )
+        logger.warning(f"'{filename}': {message}.")
+        PacketLogger.emit_event(
+            {
+                "type": "TRANSFER_WARNING",
+                "code": "slow_disk",
+                "filename": filename,
+                "message": message,
+                **rates,
+            }
+        )
+
+    def _emit_stream_progress(
+        self, filename: str, received_bytes: int, meter: IoMeter | None = None
+    ):
+        """Emit a progress event for a stream whose size is not known yet.
+
+        Args:
+            filename: Name of the stream being written.
+            received_bytes: Bytes received so far.
+            meter: Optional network and disk throughput to include.
+
+        Returns:
+            None: No return value.
+        """
+        event: dict[str, Any] = {
+            "type": "TRANSFER_UPDATE",
+            "status": "progress",
+            "filename": filename,
+            "current": received_bytes,
+            "total": None,
+        }
+        if meter:
+            event |= meter.rates()
+        PacketLogger.emit_event(event)
+
+    def _receive_manifest(
+        self, conn: Any, store_dir: Path, size: int, manifest_hash: str
+    ) -> dict[str, str] | None:
+        """Receive, check and store the batch manifest sent before the files.
+
+        Args:
+            conn: The socket object.
+            store_dir: Directory where the manifest is stored.
+            size: Manifest length announced in the header.
+            manifest_hash: SHA-256 of the manifest announced in the header.
+
+        Returns:
+            dict[str, str] | None: Expected digest per file name, or None if
+            the session must end (connection lost or invalid manifest).
+        """
+        content = self._recv_exact(conn, size)
+        if len(content) < size:
+            logger.warning(f"Connection lost while receiving {MANIFEST_NAME}.")
+            return None
+
+        try:
+            if hashlib.sha256(content).hexdigest() != manifest_hash:
+                raise ValueError("checksum mismatch")
+            entries = parse_manifest(content.decode("utf-8"))
+        except ValueError as e:
+            message = f"Invalid {MANIFEST_NAME}: {e}"
+            logger.error(message)
+            self._abort_peer(conn, AbortReason.PROTOCOL_ERROR, message)
+            self._emit_aborted(
+                MANIFEST_NAME, AbortReason.PROTOCOL_ERROR, "local", message
+            )
+            return None
+
+        if not self.audit:
+            (store_dir / MANIFEST_NAME).write_bytes(content)
+
+        logger.info(f"Received {MANIFEST_NAME} with {len(entries)} entries.")
+        PacketLogger.emit_event(
+            {
+                "type": "MANIFEST",
+                "status": "received",
+                "filename": MANIFEST_NAME,
+                "entries": len(entries),
+            }
+        )
+        return entries
+
+    def _receive_bundle(
+        self, conn: Any, addr: Any, size: int, bundle_hash: str
+    ) -> bytes | None:
+        """Receive a bundle of small files and check it as a whole.
+
+        Args:
+            conn: The socket object.
+            addr: The client address tuple (IP, Port).
+            size: Payload length announced in the header.
+            bundle_hash: SHA-256 of the payload announced in the header.
+
+        Returns:
+            bytes | None: The verified payload, or None if the session must
+            end (connection lost, oversized or corrupted bundle).
+        """
+        if size > BUNDLE_MAX_BYTES:
+            message = f"Bundle of {size} bytes exceeds {BUNDLE_MAX_BYTES}"
+            logger.warning(f"Rejected bundle from {addr}: {message}.")
+            self._abort_peer(conn, AbortReason.PROTOCOL_ERROR, message)
+            self._emit_aborted(None, AbortReason.PROTOCOL_ERROR, "local", message)
+            return None
+        payload = self._recv_exact(conn, size)
+        if len(payload) < size:
+            logger.warning(f"Connection lost while receiving bundle from {addr}.")
+            self._emit_aborted(None, AbortReason.CONNECTION_LOST, "peer")
+            return None
+        if hashlib.sha256(payload).hexdigest() != bundle_hash:
+            message = "Bundle checksum mismatch"
+            logger.error(f"Rejected bundle from {addr}: {message}.")
+            self._abort_peer(conn, AbortReason.PROTOCOL_ERROR, message)
+            self._emit_aborted(None, AbortReason.PROTOCOL_ERROR, "local", message)
+            return None
+        logger.debug(f"Unpacking bundle of {size} bytes from {addr}.")
+        return payload
+
+    def _receive_batch_preview(
+        self, conn: Any, addr: Any, store_dir: Path, size: int
+    ) -> dict[str, int] | None:
+        """Receive the list of files a batch will send and check it fits.
+
+        Args:
+            conn: The socket object.
+            addr: The client address tuple (IP, Port).
+            store_dir: Directory where the batch will be written.
+            size: Length of the listing announced in the header.
+
+        Returns:
+            dict[str, int] | None: Announced size per file name, or None if
+            the session must end (connection lost, invalid listing or not
+            enough free space).
+        """
+        if size > BATCH_PREVIEW_MAX_BYTES:
+            detail = f"Batch preview of {size} bytes exceeds {BATCH_PREVIEW_MAX_BYTES}"
+            logger.warning(f"Rejected batch from {addr}: {detail}.")
+            self._abort_peer(conn, AbortReason.PROTOCOL_ERROR, detail)
+            return None
+        listing = self._recv_exact(conn, size)
+        if len(listing) < size:
+            logger.warning(f"Connection lost while receiving batch from {addr}.")
+            return None
+        try:
+            entries = parse_batch_preview(listing.decode("utf-8"))
+        except (UnicodeDecodeError, ValueError) as e:
+            logger.warning(f"Rejected batch from {addr}: {e}.")
+            self._abort_peer(conn, AbortReason.PROTOCOL_ERROR, str(e))
+            return None
+
+        total = sum(entries.values())
+        if not self.audit:
+            free = shutil.disk_usage(store_dir).free
+            if total > free:
+                message = f"Batch needs {total} bytes, only {free} free"
+                logger.error(f"Rejected batch from {addr}: {message}.")
+                self._abort_peer(conn, AbortReason.DISK_FULL, message)
+                self._emit_aborted(None, AbortReason.DISK_FULL, "local", message)
+                return None
+
+        logger.info(f"Batch of {len(entries)} file(s), {total} bytes announced.")
+        PacketLogger.emit_event(
+            {
+                "type": "BATCH_UPDATE",
+                "status": "announced",
+                "files": list(entries),
+                "sizes": list(entries.values()),
+                "count": len(entries),
+                "total_bytes": total,
+            }
+        )
+        return entries
+
+    def _discard_partial(self, save_path: Path):
+        """Remove a partially received file (never touches disk in audit mode).
+
+        Args:
+            save_path: Path of the partial file.
+
+        Returns:
+            None: No return value.
+        """
+        if not self.audit:
+            save_path.unlink(missing_ok=True)
+
+    def _abort_peer(self, conn: Any, reason: AbortReason, message: str):
+        """Tell the sender the transfer is aborted, then drain its data.
+
+        Draining until the sender closes (or a timeout) avoids resetting the
+        connection before the sender has read the abort message.
+
+        Args:
+            conn: The socket object.
+            reason: Why the transfer is being aborted.
+            message: Human-readable detail for the sender.
+
+        Returns:
+            None: No return value.
+        """
+        try:
+            conn.sendall(ProtocolHandler.pack_abort(reason, message))
+            conn.shutdown(socket.SHUT_WR)
+            conn.settimeout(ABORT_DRAIN_TIMEOUT)
+            while conn.recv(CHUNK_SIZE):
+                pass
+        except OSError as e:
+            logger.debug(f"Could not notify sender of abort: {e}")
+
+    def _emit_aborted(
+        self, filename: str | None, reason: str, initiator: str, message: str = ""
+    ):
+        """Emit a TRANSFER_UPDATE event for an aborted transfer.
+
+        Args:
+            filename: The file being transferred, if known.
+            reason: An `AbortReason` value.
+            initiator: `"local"` or `"peer"`.
+            message: Optional human-readable detail.
+
+        Returns:
+            None: No return value.
+        """
+        event: dict[str, Any] = {
+            "type": "TRANSFER_UPDATE",
+            "status": "aborted",
+            "reason": reason,
+            "initiator": initiator,
+            "message": message,
+        }
+        if filename:
+            event["filename"] = filename
+        PacketLogger.emit_event(event)
 
     def _recv_exact(self, conn: Any, n: int) -> bytes:
         """Receive exactly n bytes from the socket.
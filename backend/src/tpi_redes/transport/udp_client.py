@@ -1,12 +1,36 @@
-import json
 import logging
 import socket
 import time
 from pathlib import Path
+from typing import Any
 
-from tpi_redes.config import UDP_PAYLOAD_SIZE
-from tpi_redes.core.protocol import ProtocolHandler
-from tpi_redes.transfer.integrity import IntegrityVerifier
+from tpi_redes.config import (
+    RECEIVER_STATUS_TIMEOUT,
+    UDP_DISABLED,
+    UDP_PACING_RATE,
+    UDP_PAYLOAD_SIZE,
+)
+from tpi_redes.core.errors import (
+    ProtocolDisabledError,
+    SourceModifiedError,
+    TransferAbortedError,
+)
+from tpi_redes.core.protocol import AbortReason, ProtocolHandler, ReceiverStatus
+from tpi_redes.observability.audit_log import AuditLog
+from tpi_redes.observability.environment import environment_snapshot
+from tpi_redes.observability.packet_logger import PacketLogger
+from tpi_redes.services.pause_control import PauseControl
+from tpi_redes.transfer.buffer_pool import BufferPool
+from tpi_redes.transfer.checksum_cache import ChecksumCache, file_checksum
+from tpi_redes.transfer.fec import FecEncoder
+from tpi_redes.transfer.file_validator import FileValidator
+from tpi_redes.transfer.readahead import ReadaheadReader
+from tpi_redes.transfer.sla import SlaMonitor
+from tpi_redes.transfer.source_watch import SourceWatcher
+from tpi_redes.transfer.speed_alert import SpeedMonitor
+from tpi_redes.transfer.summary import SummaryRecorder, TransferSummary
+from tpi_redes.transport.debug_hooks import ChunkShaper, DebugHooks
+from tpi_redes.transport.pacing import Pacer
 
 logger = logging.getLogger("tpi-redes")
 
@@ -16,7 +40,12 @@ class UDPClient:
 
     Uses a stateless "fire-and-forget" approach to send file datagrams.
     No retransmission or reliability guarantees are implemented at this layer,
-    relying on the local network reliability.
+    relying on the local network reliability. Content datagrams are paced to a
+    target rate by default so a transfer does not saturate the LAN.
+    Status reports sent back by the receiver are folded into progress events
+    as `delivered`, the number of bytes actually written on the remote side.
+    With forward error correction, a parity datagram follows every group of
+    content datagrams so the receiver can repair single losses itself.
     """
 
     def send_files(
@@ -26,7 +55,17 @@ class UDPClient:
         port: int,
         delay: float = 0.0,
         chunk_size: int = UDP_PAYLOAD_SIZE,
-    ):
+        pacing_rate: int | None = UDP_PACING_RATE,
+        max_read_bytes: int | None = None,
+        audit_log: AuditLog | None = None,
+        sla: float | None = None,
+        checksum_cache: ChecksumCache | None = None,
+        socket_buffer: int | None = None,
+        fec_group: int | None = None,
+        speed_monitor: SpeedMonitor | None = None,
+        cancel_when_slow: bool = False,
+        pause: PauseControl | None = None,
+    ) -> TransferSummary:
         """Send multiple files to a remote UDP server.
 
         Files are sent sequentially using specific datagrams for:
@@ -38,35 +77,86 @@ class UDPClient:
             port: Destination port number.
             delay: Optional delay (seconds) between packets for flow control.
             chunk_size: Size of data payload per packet (default: UDP_PAYLOAD_SIZE).
+            pacing_rate: Target send rate in bytes per second for content
+                datagrams. `None` disables pacing entirely.
+            max_read_bytes: Optional cap on the bytes sent per file.
+            audit_log: Optional journal recording every sent file.
+            sla: Expected duration of the whole batch in seconds; an
+                `SLA_WARNING` event is emitted if it is likely to be missed.
+            checksum_cache: Optional cache of checksums of unchanged files.
+            socket_buffer: Optional send buffer size (`SO_SNDBUF`).
+            fec_group: Send one parity datagram per this many content
+                datagrams; None sends no redundancy.
+            speed_monitor: Optional monitor emitting `SLOW_TRANSFER` and
+                `SPEED_SATURATED` events for the batch.
+            cancel_when_slow: Stop sending when the monitor reports the
+                transfer as too slow.
+            pause: Optional control that holds the transfer between chunks
+                while paused.
 
         Raises:
+            ProtocolDisabledError: If UDP is disabled by configuration.
             FileNotFoundError: If no valid existing files are provided.
+            InvalidFileError: If a file is not a regular file.
+            SourceModifiedError: If a file changes while being sent.
+            TransferAbortedError: If the transfer is too slow and
+                `cancel_when_slow` is set.
 
         Returns:
-            None: No return value.
+            TransferSummary: Timings, speeds, loss and per-file results of
+            the batch.
         """
+        if UDP_DISABLED:
+            raise ProtocolDisabledError("udp")
+
         valid_files = [f for f in files if f.exists()]
         if not valid_files:
             raise FileNotFoundError("No valid files to send")
-
-        from tpi_redes.observability.packet_logger import PacketLogger
+        file_sizes = {
+            f: FileValidator(f).transfer_size(max_read_bytes) for f in valid_files
+        }
 
         logger.info(f"Sending {len(valid_files)} files to {ip}:{port} via UDP...")
 
+        pacer = Pacer(pacing_rate) if pacing_rate else None
+        hooks = DebugHooks.from_config()
+        shaper = ChunkShaper(hooks) if hooks.active else None
+        sla_monitor = SlaMonitor(sla, sum(file_sizes.values())) if sla else None
+        batch_sent = 0
+        batch_delivered = 0
+        summary = SummaryRecorder()
+        pool = BufferPool(chunk_size)
+        PacketLogger.reset_errors()
+        settings = {
+            "chunk_size": chunk_size,
+            "socket_buffer": socket_buffer,
+            "pacing_rate": pacing_rate,
+            "fec_group": fec_group,
+        }
+
         with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as s:
+            if socket_buffer:
+                s.setsockopt(socket.SOL_SOCKET, socket.SO_SNDBUF, socket_buffer)
             addr = (ip, port)
             local_ip, local_port = s.getsockname()
 
             for file_path in valid_files:
+                summary.enter("data")
                 logger.info(f"Calculating hash for {file_path}...")
-                verifier = IntegrityVerifier(file_path)
-                file_hash = verifier.calculate_hash()
+                file_size = file_sizes[file_path]
+                watcher = SourceWatcher(
+                    file_path, file_size, prefix_only=max_read_bytes is not None
+                )
+                file_hash = file_checksum(file_path, file_size, checksum_cache)
 
-                file_size = file_path.stat().st_size
                 filename = file_path.name
 
+                encoder = FecEncoder(fec_group) if fec_group else None
                 header = ProtocolHandler.pack_header(
-                    b"F", filename, file_size, file_hash
+                    ProtocolHandler.FEC_FILE_OP_CODE if encoder else b"F",
+                    filename,
+                    file_size,
+                    file_hash,
                 )
 
                 logger.info(f"Processing {filename}...")
@@ -98,25 +188,39 @@ class UDPClient:
                 )
                 time.sleep(0.001)
 
-                print(
-                    json.dumps(
-                        {
-                            "type": "TRANSFER_UPDATE",
-                            "status": "start",
-                            "filename": filename,
-                            "total": file_size,
-                        }
-                    ),
-                    flush=True,
+                PacketLogger.emit_event(
+                    {
+                        "type": "TRANSFER_UPDATE",
+                        "status": "start",
+                        "filename": filename,
+                        "total": file_size,
+                    }
                 )
 
                 sent_bytes = 0
+                receiver_status: ReceiverStatus | None = None
                 start_transfer = time.time()
                 last_stats_time = start_transfer
 
-                with open(file_path, "rb") as f:
-                    while chunk := f.read(chunk_size):
-                        s.sendto(chunk, addr)
+                with ReadaheadReader(
+                    file_path, file_size, chunk_size, pool=pool
+                ) as reader:
+                    while chunk := reader.read():
+                        if pause and pause.paused:
+                            summary.enter("paused")
+                            self._wait_resumed(pause, filename, sent_bytes)
+                            summary.enter("data")
+                            if speed_monitor:
+                                speed_monitor.skip(batch_sent + sent_bytes)
+                        payload = shaper.shape(chunk) if shaper else chunk
+                        datagrams = [payload]
+                        if encoder:
+                            datagrams = encoder.encode(sent_bytes, payload)
+                        for datagram in datagrams:
+                            if pacer:
+                                pacer.wait(len(datagram))
+                            if not (shaper and shaper.should_drop()):
+                                s.sendto(datagram, addr)
                         sent_bytes += len(chunk)
 
                         if delay > 0:
@@ -133,30 +237,296 @@ class UDPClient:
                             info=f"Chunk ({len(chunk)}B) - {sent_bytes}/{file_size}",
                         )
 
+                        receiver_status = (
+                            self._poll_receiver_status(s) or receiver_status
+                        )
+                        if watcher.changed():
+                            self._source_modified(file_path, audit_log, f"{ip}:{port}")
+
                         current_time = time.time()
                         if current_time - last_stats_time >= 0.1:
-                            print(
-                                json.dumps(
-                                    {
-                                        "type": "TRANSFER_UPDATE",
-                                        "status": "progress",
-                                        "filename": filename,
-                                        "current": sent_bytes,
-                                        "total": file_size,
-                                    }
-                                ),
-                                flush=True,
+                            PacketLogger.emit_event(
+                                {
+                                    "type": "TRANSFER_UPDATE",
+                                    "status": "progress",
+                                    "filename": filename,
+                                    "current": sent_bytes,
+                                    "total": file_size,
+                                    "delivered": (
+                                        receiver_status.bytes_written
+                                        if receiver_status
+                                        else 0
+                                    ),
+                                    "buffer_fill": round(reader.fill, 2),
+                                }
                             )
                             last_stats_time = current_time
+                            self._check_sla(sla_monitor, batch_sent + sent_bytes)
+                            self._check_speed(
+                                speed_monitor,
+                                cancel_when_slow,
+                                filename,
+                                batch_sent + sent_bytes,
+                            )
+                            summary.sample(batch_sent + sent_bytes)
+
+                # The last group's parity is paced too: a burst at the end of
+                # the file is where losses would hit it.
+                for datagram in encoder.flush() if encoder else []:
+                    if pacer:
+                        pacer.wait(len(datagram))
+                    if not (shaper and shaper.should_drop()):
+                        s.sendto(datagram, addr)
+
+                if watcher.changed(force=True):
+                    self._source_modified(file_path, audit_log, f"{ip}:{port}")
+
+                summary.enter("verify")
+                receiver_status = self._await_final_status(
+                    s, file_size, receiver_status
+                )
+                delivered = receiver_status.bytes_written if receiver_status else 0
+                if delivered < file_size:
+                    logger.warning(
+                        f"Receiver confirmed {delivered}/{file_size} bytes "
+                        f"of {filename}."
+                    )
+
+                result = "sent" if delivered >= file_size else "unconfirmed"
+                if audit_log:
+                    audit_log.record(
+                        "sent", f"{ip}:{port}", filename, sent_bytes, file_hash, result
+                    )
+                summary.file(filename, sent_bytes, result)
+
+                batch_sent += sent_bytes
+                batch_delivered += min(delivered, sent_bytes)
+                self._check_sla(sla_monitor, batch_sent)
 
                 logger.info(f"UDP Transfer finished for {filename}.")
-                print(
-                    json.dumps(
-                        {
-                            "type": "TRANSFER_UPDATE",
-                            "status": "complete",
-                            "filename": filename,
-                        }
-                    ),
-                    flush=True,
+                # Bound by the first send; before that it reads 0.0.0.0:0.
+                local_ip, local_port = s.getsockname()
+                complete_event: dict[str, Any] = {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "complete",
+                    "filename": filename,
+                    "delivered": delivered,
+                    "environment": {
+                        **environment_snapshot(
+                            "udp",
+                            f"{local_ip}:{local_port}",
+                            f"{ip}:{port}",
+                            None,
+                            settings,
+                        ),
+                        "retries": 0,
+                    },
+                }
+                if sla_monitor:
+                    complete_event["sla_missed"] = sla_monitor.missed
+                if speed_monitor:
+                    complete_event["speed_alerts"] = speed_monitor.alerts
+                if encoder:
+                    complete_event["fec"] = self._fec_stats(encoder, receiver_status)
+                complete_event["buffer_pool"] = pool.stats()
+                PacketLogger.emit_event(complete_event)
+
+        loss = 1 - batch_delivered / batch_sent if batch_sent else 0.0
+        return summary.finish(
+            "udp",
+            f"{ip}:{port}",
+            settings,
+            loss=loss,
+            errors=PacketLogger.error_stats(),
+            warnings=PacketLogger.warning_stats(),
+        )
+
+    def _fec_stats(
+        self, encoder: FecEncoder, status: ReceiverStatus | None
+    ) -> dict[str, Any]:
+        """Summarize redundancy and loss repair of a file sent with FEC.
+
+        Args:
+            encoder: The file's encoder.
+            status: The receiver's last report, if any arrived.
+
+        Returns:
+            dict[str, Any]: Group size, datagrams and parity datagrams sent,
+            and how many lost datagrams the receiver repaired from parity.
+        """
+        repaired = status.repaired if status else 0
+        if repaired:
+            logger.info(f"Receiver repaired {repaired} lost datagram(s) with FEC.")
+        return {
+            "group": encoder.group_size,
+            "datagrams_sent": encoder.data_frames + encoder.parity_frames,
+            "parity_sent": encoder.parity_frames,
+            "repaired": repaired,
+        }
+
+    def _source_modified(self, file_path: Path, audit_log: AuditLog | None, peer: str):
+        """Stop sending a file that changed while being sent.
+
+        UDP has no abort message; the receiver's session simply never
+        completes.
+
+        Args:
+            file_path: The file that changed.
+            audit_log: Optional journal recording the aborted file.
+            peer: Receiver as `ip:port`.
+
+        Raises:
+            SourceModifiedError: Always.
+        """
+        error = SourceModifiedError(file_path)
+        logger.error(f"Aborting: {error.detail}.")
+        if audit_log:
+            audit_log.record(
+                "sent", peer, file_path.name, 0, "", f"aborted:{error.reason}"
+            )
+        PacketLogger.emit_event(
+            {
+                "type": "TRANSFER_UPDATE",
+                "status": "aborted",
+                "filename": file_path.name,
+                "reason": AbortReason.SOURCE_MODIFIED,
+                "initiator": "local",
+                "message": error.detail,
+            }
+        )
+        raise error
+
+    def _check_sla(self, monitor: SlaMonitor | None, done_bytes: int):
+        """Emit an SLA_WARNING event if the batch is falling behind its SLA.
+
+        Args:
+            monitor: The batch's SLA monitor, if an SLA was set.
+            done_bytes: Bytes sent so far across the batch.
+
+        Returns:
+            None: No return value.
+        """
+        if monitor and (warning := monitor.check(done_bytes)):
+            logger.warning(
+                f"Transfer SLA {warning['status']}: {warning['elapsed']}s elapsed "
+                f"of {warning['deadline']}s."
+            )
+            PacketLogger.emit_event(warning)
+
+    def _wait_resumed(self, pause: PauseControl, filename: str, current: int):
+        """Hold the transfer while paused, emitting `paused` and `resumed`.
+
+        The receiver reaps a session silent for longer than its session
+        timeout, so long pauses of a UDP transfer end in `stalled`.
+
+        Args:
+            pause: The pause control.
+            filename: The file being sent.
+            current: Bytes of the file sent so far.
+
+        Returns:
+            None: No return value.
+        """
+        event = {"type": "TRANSFER_UPDATE", "filename": filename, "current": current}
+        PacketLogger.emit_event({**event, "status": "paused"})
+        logger.info(f"Transfer of '{filename}' paused.")
+        paused_for = pause.wait_resumed()
+        PacketLogger.emit_event(
+            {**event, "status": "resumed", "paused_for": round(paused_for, 3)}
+        )
+
+    def _check_speed(
+        self,
+        monitor: SpeedMonitor | None,
+        cancel_when_slow: bool,
+        filename: str,
+        done_bytes: int,
+    ):
+        """Emit speed alerts and stop a too-slow transfer if asked to.
+
+        Args:
+            monitor: The batch's speed monitor, if thresholds were set.
+            cancel_when_slow: Whether a too-slow transfer is cancelled.
+            filename: The file being sent.
+            done_bytes: Bytes sent so far across the batch.
+
+        Raises:
+            TransferAbortedError: If the transfer is too slow and
+                `cancel_when_slow` is set.
+
+        Returns:
+            None: No return value.
+        """
+        if not monitor or not (alert := monitor.check(done_bytes)):
+            return
+        logger.warning(
+            f"Transfer speed {alert['rate']:.0f} B/s vs {alert['threshold']} B/s "
+            f"for {alert['duration']}s."
+        )
+        PacketLogger.emit_event(alert)
+        if cancel_when_slow and monitor.slow:
+            message = f"Slower than {monitor.min_rate} B/s for {alert['duration']}s"
+            PacketLogger.emit_event(
+                {
+                    "type": "TRANSFER_UPDATE",
+                    "status": "aborted",
+                    "filename": filename,
+                    "reason": AbortReason.CANCELLED,
+                    "initiator": "local",
+                    "message": message,
+                }
+            )
+            raise TransferAbortedError(AbortReason.CANCELLED, "local", message)
+
+    def _poll_receiver_status(self, s: socket.socket) -> ReceiverStatus | None:
+        """Drain pending receiver status datagrams without blocking.
+
+        Args:
+            s: The sending socket, which also receives the receiver's reports.
+
+        Returns:
+            ReceiverStatus | None: The most recent valid report, if any arrived.
+        """
+        latest: ReceiverStatus | None = None
+        while True:
+            try:
+                data, _ = s.recvfrom(
+                    ProtocolHandler.FEC_STATUS_SIZE, socket.MSG_DONTWAIT
                 )
+            except OSError:
+                return latest
+
+            try:
+                latest = ProtocolHandler.unpack_status(data)
+            except ValueError:
+                logger.debug("Ignoring malformed receiver status datagram.")
+
+    def _await_final_status(
+        self,
+        s: socket.socket,
+        file_size: int,
+        latest: ReceiverStatus | None,
+    ) -> ReceiverStatus | None:
+        """Wait briefly for the receiver to confirm the whole file was written.
+
+        Gives up after `RECEIVER_STATUS_TIMEOUT` seconds, since datagrams may
+        have been lost or the receiver may not send reports at all.
+
+        Args:
+            s: The sending socket.
+            file_size: Total size of the file just sent.
+            latest: The most recent report seen during the transfer.
+
+        Returns:
+            ReceiverStatus | None: The most recent report after waiting.
+        """
+        deadline = time.monotonic() + RECEIVER_STATUS_TIMEOUT
+        while (
+            latest is None or latest.bytes_written < file_size
+        ) and time.monotonic() < deadline:
+            status = self._poll_receiver_status(s)
+            if status:
+                latest = status
+            else:
+                time.sleep(0.01)
+        return latest
@@ -1,12 +1,28 @@
+import hashlib
 import logging
 import socket
-from dataclasses import dataclass
+import time
+from dataclasses import dataclass, field
 from pathlib import Path
-
-from tpi_redes.config import MAX_UDP_PACKET_SIZE
+from typing import Any
+
+from tpi_redes import __version__
+from tpi_redes.config import (
+    MAX_UDP_PACKET_SIZE,
+    PROGRESS_REPORT_INTERVAL_BYTES,
+    UDP_DISABLED,
+    UDP_REAP_INTERVAL,
+    UDP_SESSION_TIMEOUT,
+)
 from tpi_redes.core.base import BaseServer
-from tpi_redes.core.protocol import Header, ProtocolHandler
+from tpi_redes.core.errors import ProtocolDisabledError
+from tpi_redes.core.protocol import AbortReason, Header, ProtocolHandler
+from tpi_redes.observability.audit_log import AuditLog
 from tpi_redes.observability.packet_logger import PacketLogger
+from tpi_redes.services.knock import KnockGate
+from tpi_redes.services.sleep_inhibitor import SleepInhibitor
+from tpi_redes.transfer.fec import FecDecoder
+from tpi_redes.transfer.routing import RouteRule
 
 logger = logging.getLogger("tpi-redes")
 
@@ -27,6 +43,12 @@ class UDPSession:
     file_hash: str | None = None
     received_bytes: int = 0
     file_path: Path | None = None
+    datagrams_received: int = 0
+    reported_bytes: int = 0
+    hasher: Any = field(default_factory=hashlib.sha256)
+    fec: FecDecoder | None = None
+    last_seen: float = field(default_factory=time.monotonic)
+    """`time.monotonic()` of the session's latest datagram (its heartbeat)."""
 
 
 class UDPServer(BaseServer):
@@ -35,17 +57,52 @@ class UDPServer(BaseServer):
     Manages multiple concurrent uploads using a state machine per client address.
     Not reliable (no ACKs/Retries implemented in this basic version),
     but follows the project's header/metadata/content protocol structure.
+    Periodically reports bytes written back to the sender so it can show
+    delivery progress instead of bytes pushed to the socket. Files sent with
+    forward error correction are reassembled in order, rebuilding single lost
+    datagrams per parity group.
+    In audit mode content is hashed and verified but never written to disk.
+    Sessions whose sender goes silent for `session_timeout` seconds (e.g. the
+    last datagrams were lost) are aborted as stalled and their partial file
+    removed, so they stop counting as active transfers.
     """
 
-    def __init__(self, host: str, port: int, save_dir: str):
-        super().__init__(host, port, save_dir)
+    def __init__(
+        self,
+        host: str,
+        port: int,
+        save_dir: str,
+        per_peer_dirs: bool = False,
+        audit: bool = False,
+        sleep_inhibitor: SleepInhibitor | None = None,
+        audit_log: AuditLog | None = None,
+        routes: list[RouteRule] | None = None,
+        knock_gate: KnockGate | None = None,
+    ):
+        if UDP_DISABLED:
+            raise ProtocolDisabledError("udp")
+        super().__init__(
+            host,
+            port,
+            save_dir,
+            per_peer_dirs,
+            audit,
+            sleep_inhibitor,
+            audit_log,
+            routes,
+            knock_gate,
+        )
         self.sessions: dict[tuple[str, int], UDPSession] = {}
         self.sock: socket.socket | None = None
+        self.session_timeout = UDP_SESSION_TIMEOUT
 
     def start(self):
         """Start listening for UDP packets.
 
-        Binds to the socket and enters a loop receiving datagrams up to 65535 bytes.
+        Binds to the socket and enters a loop receiving datagrams up to 65535 bytes,
+        until interrupted, until `max_files` files completed or until
+        `window_ends` if set. Sessions still in progress at the end of the
+        window are dropped.
 
         Returns:
             None: No return value.
@@ -56,9 +113,28 @@ class UDPServer(BaseServer):
             self.sock.bind((self.host, self.port))
             logger.info(f"UDP Server listening on {self.host}:{self.port}")
 
+            last_reap = time.monotonic()
             try:
                 while True:
-                    data, addr = self.sock.recvfrom(MAX_UDP_PACKET_SIZE)
+                    if time.monotonic() - last_reap >= UDP_REAP_INTERVAL:
+                        self.reap_stale_sessions()
+                        last_reap = time.monotonic()
+                    remaining = self.window_remaining()
+                    if remaining is not None and remaining <= 0:
+                        logger.info("Receive window closed; stopping.")
+                        break
+                    timeouts = [remaining] if remaining is not None else []
+                    if self.sessions:
+                        timeouts.append(UDP_REAP_INTERVAL)
+                    self.sock.settimeout(min(timeouts) if timeouts else None)
+                    try:
+                        data, addr = self.sock.recvfrom(MAX_UDP_PACKET_SIZE)
+                    except TimeoutError:
+                        continue
+                    if not self.is_authorized(addr):
+                        logger.debug(f"Dropped datagram from unauthorized {addr}")
+                        self.peer_stats.auth_failed(addr[0])
+                        continue
 
                     local_ip, local_port = self.sock.getsockname()
                     PacketLogger.emit_packet(
@@ -72,6 +148,9 @@ class UDPServer(BaseServer):
                     )
 
                     self.process_datagram(data, addr)
+                    if self.limit_reached:
+                        logger.info(f"Received {self.max_files} file(s); stopping.")
+                        break
             except KeyboardInterrupt:
                 logger.info("Server stopping...")
         except Exception as e:
@@ -98,14 +177,22 @@ class UDPServer(BaseServer):
             None: No return value.
         """
         session = self.sessions.get(addr)
+        if session:
+            session.last_seen = time.monotonic()
 
         if not session:
             if len(data) == ProtocolHandler.HEADER_SIZE:
                 try:
                     header = ProtocolHandler.unpack_header(data)
+                    if header.op_code == ProtocolHandler.PING_OP_CODE:
+                        self._send_pong(addr)
+                        return
                     self.sessions[addr] = UDPSession(
                         state="WAITING_METADATA", header=header
                     )
+                    self.active_transfers = len(self.sessions)
+                    if self.sleep_inhibitor:
+                        self.sleep_inhibitor.acquire()
                     logger.info(
                         f"[{addr}] New UDP session. Expecting {header.file_size} bytes."
                     )
@@ -121,7 +208,7 @@ class UDPServer(BaseServer):
                     logger.error(
                         f"[{addr}] Session missing header in WAITING_METADATA state."
                     )
-                    del self.sessions[addr]
+                    self._end_session(addr)
                     return
 
                 expected_len = session.header.name_len + session.header.hash_len
@@ -132,46 +219,205 @@ class UDPServer(BaseServer):
                     session.filename = name_bytes.decode("utf-8")
                     session.file_hash = hash_bytes.decode("utf-8")
 
-                    save_path = Path(self.save_dir) / session.filename
-                    save_path.parent.mkdir(parents=True, exist_ok=True)
+                    save_path = self.output_dir_for(addr) / session.filename
                     session.file_path = save_path
 
-                    with open(save_path, "wb") as _:
-                        pass
+                    if not self.audit:
+                        save_path.parent.mkdir(parents=True, exist_ok=True)
+                        with open(save_path, "wb") as _:
+                            pass
 
+                    if session.header.op_code == ProtocolHandler.FEC_FILE_OP_CODE:
+                        session.fec = FecDecoder()
                     session.state = "RECEIVING_CONTENT"
                     logger.info(f"[{addr}] Metadata received: {session.filename}")
+                    if route := self.matched_route(addr):
+                        logger.info(f"[{addr}] Routed by rule '{route}' to {save_path}")
                 else:
                     logger.warning(f"[{addr}] Invalid metadata length. Resetting.")
-                    del self.sessions[addr]
+                    self._end_session(addr)
 
             elif session.state == "RECEIVING_CONTENT":
                 if not session.file_path:
                     logger.error(f"[{addr}] No file path for session.")
-                    del self.sessions[addr]
+                    self._end_session(addr)
                     return
 
-                with open(session.file_path, "ab") as f:
-                    f.write(data)
-                    session.received_bytes += len(data)
+                try:
+                    chunks = session.fec.add(data) if session.fec else [data]
+                except ValueError as e:
+                    logger.debug(f"[{addr}] Dropped malformed FEC frame: {e}")
+                    return
+                if not self.audit:
+                    with open(session.file_path, "ab") as f:
+                        f.writelines(chunks)
+                for chunk in chunks:
+                    session.hasher.update(chunk)
+                    session.received_bytes += len(chunk)
+                session.datagrams_received += 1
 
                 logger.debug(
                     f"[{addr}] Chunk {len(data)} bytes. Total: {session.received_bytes}"
                 )
 
-                if (
+                complete = bool(
                     session.header
                     and session.received_bytes >= session.header.file_size
+                )
+                if (
+                    complete
+                    or session.received_bytes - session.reported_bytes
+                    >= PROGRESS_REPORT_INTERVAL_BYTES
                 ):
-                    if session.file_hash and session.file_path:
+                    self._send_status(session, addr)
+
+                if complete:
+                    verified = session.hasher.hexdigest() == session.file_hash
+                    if self.audit:
+                        logger.info(
+                            f"[{addr}] [audit] Would store '{session.file_path}' "
+                            f"({session.received_bytes} bytes, verified={verified})."
+                        )
+                    elif session.file_hash:
                         hash_path = Path(f"{session.file_path}.sha256")
                         with open(hash_path, "w") as f:
                             f.write(session.file_hash)
 
+                    validation: list[dict[str, Any]] = []
+                    if verified and not self.audit:
+                        validation = self.validate(session.file_path)
+                    self.record_audit(
+                        addr,
+                        session.filename or "",
+                        session.received_bytes,
+                        session.hasher.hexdigest(),
+                        "verified" if verified else "mismatch",
+                        validation,
+                    )
+                    if session.fec:
+                        logger.info(
+                            f"[{addr}] Repaired {session.fec.repaired} lost "
+                            "datagram(s) with FEC."
+                        )
                     logger.info(f"[{addr}] Transfer complete: {session.filename}")
-                    del self.sessions[addr]
+                    self._end_session(addr)
 
         except Exception as e:
-            logger.error(f"[{addr}] Error processing datagram: {e}")
+            message = f"{type(e).__name__}: {e}"
+            logger.exception(f"[{addr}] Error processing datagram: {message}")
             if addr in self.sessions:
-                del self.sessions[addr]
+                self._abort_session(addr, AbortReason.INTERNAL_ERROR, "local", message)
+
+    def _send_pong(self, addr: tuple[str, int]):
+        """Answer a liveness probe with this receiver's version.
+
+        Args:
+            addr: The prober's (IP, Port) tuple.
+
+        Returns:
+            None: No return value.
+        """
+        logger.debug(f"[{addr}] Ping received.")
+        if not self.sock:
+            return
+        try:
+            self.sock.sendto(ProtocolHandler.pack_pong(__version__), addr)
+        except OSError as e:
+            logger.debug(f"[{addr}] Could not send pong: {e}")
+
+    def reap_stale_sessions(self, now: float | None = None) -> int:
+        """Abort sessions whose sender has been silent for `session_timeout`.
+
+        Each one is reported as an aborted transfer with reason `stalled`,
+        recorded in the audit log and its partial file removed.
+
+        Args:
+            now: Current `time.monotonic()` value; read if not given.
+
+        Returns:
+            int: Number of sessions reaped.
+        """
+        now = time.monotonic() if now is None else now
+        stale = [
+            (addr, session)
+            for addr, session in self.sessions.items()
+            if now - session.last_seen >= self.session_timeout
+        ]
+        for addr, session in stale:
+            message = f"No datagrams for {now - session.last_seen:.1f}s"
+            logger.warning(f"[{addr}] Session for '{session.filename}' stalled.")
+            self._abort_session(addr, AbortReason.STALLED, "peer", message)
+        return len(stale)
+
+    def _abort_session(
+        self, addr: tuple[str, int], reason: AbortReason, initiator: str, message: str
+    ):
+        """End a session that cannot complete and report it as aborted.
+
+        The partial file is removed and the abort recorded in the audit log.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+            reason: Why the session is aborted.
+            initiator: `"local"` or `"peer"`.
+            message: Human-readable detail.
+
+        Returns:
+            None: No return value.
+        """
+        session = self.sessions[addr]
+        filename = session.filename or ""
+        if session.file_path and not self.audit:
+            session.file_path.unlink(missing_ok=True)
+        self.record_audit(
+            addr, filename, session.received_bytes, "", f"aborted:{reason}"
+        )
+        PacketLogger.emit_event(
+            {
+                "type": "TRANSFER_UPDATE",
+                "status": "aborted",
+                "filename": filename,
+                "reason": reason,
+                "initiator": initiator,
+                "message": message,
+            }
+        )
+        self._end_session(addr)
+
+    def _end_session(self, addr: tuple[str, int]):
+        """Forget a session and release its hold on the sleep inhibitor.
+
+        Args:
+            addr: The sender's (IP, Port) tuple.
+
+        Returns:
+            None: No return value.
+        """
+        del self.sessions[addr]
+        self.active_transfers = len(self.sessions)
+        if self.sleep_inhibitor:
+            self.sleep_inhibitor.release()
+
+    def _send_status(self, session: UDPSession, addr: tuple[str, int]):
+        """Report the session's write progress back to the sender (best effort).
+
+        Args:
+            session: The session whose progress is reported.
+            addr: The sender's (IP, Port) tuple.
+
+        Returns:
+            None: No return value.
+        """
+        session.reported_bytes = session.received_bytes
+        if not self.sock:
+            return
+
+        status = ProtocolHandler.pack_status(
+            session.received_bytes,
+            session.datagrams_received,
+            session.fec.repaired if session.fec else None,
+        )
+        try:
+            self.sock.sendto(status, addr)
+        except OSError as e:
+            logger.debug(f"[{addr}] Could not send receiver status: {e}")
@@ -0,0 +1,251 @@
+import socket
+from typing import Any
+
+import pytest
+
+from tpi_redes.core.protocol import ProtocolHandler
+
+
+class StreamSocket:
+    """In-memory stand-in for a connected TCP socket.
+
+    `recv` hands out `incoming` in order, leaving it in place for
+    `MSG_PEEK`, and every `sendall` is kept in `sends`. Once `incoming` is
+    consumed the peer has hung up and `recv` returns b""; with
+    `hang_up=False` it raises `BlockingIOError` instead, like an open
+    connection with nothing to read yet.
+    """
+
+    def __init__(self, incoming: bytes = b"", hang_up: bool = True):
+        """Queue the bytes the peer will send.
+
+        Args:
+            incoming: Bytes returned by `recv`, in order.
+            hang_up: Whether the peer closes once `incoming` is consumed.
+        """
+        self.incoming = incoming
+        self.hang_up = hang_up
+        self.sends: list[bytes] = []
+        self.timeouts: list[float | None] = []
+        self.timeout: float | None = None
+        self.closed = False
+
+    @property
+    def sent(self) -> bytes:
+        """Return everything written to the socket so far.
+
+        Returns:
+            bytes: The concatenated `sendall` payloads.
+        """
+        return b"".join(self.sends)
+
+    def recv(self, bufsize: int, flags: int = 0) -> bytes:
+        """Read queued bytes, keeping them when peeking.
+
+        Args:
+            bufsize: Maximum number of bytes to return.
+            flags: `socket.MSG_*` flags; only `MSG_PEEK` is honored.
+
+        Returns:
+            bytes: Up to `bufsize` queued bytes, or b"" once hung up.
+
+        Raises:
+            BlockingIOError: If nothing is queued and the peer stays connected.
+        """
+        if not self.incoming and not self.hang_up:
+            raise BlockingIOError
+        data = self.incoming[:bufsize]
+        if not flags & socket.MSG_PEEK:
+            self.incoming = self.incoming[bufsize:]
+        return data
+
+    def sendall(self, data: bytes):
+        """Record a write, copied since senders reuse their buffers.
+
+        Args:
+            data: The bytes written.
+
+        Returns:
+            None: No return value.
+        """
+        self.sends.append(bytes(data))
+
+    def settimeout(self, t: float | None):
+        """Record a timeout change.
+
+        Args:
+            t: The new timeout.
+
+        Returns:
+            None: No return value.
+        """
+        self.timeouts.append(t)
+        self.timeout = t
+
+    def connect(self, addr: tuple[str, int]):
+        pass
+
+    def shutdown(self, how: int):
+        pass
+
+    def close(self):
+        self.closed = True
+
+    def getsockname(self) -> tuple[str, int]:
+        return ("127.0.0.1", 12345)
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+
+class DatagramSocket:
+    """In-memory stand-in for a UDP socket.
+
+    `recvfrom` hands out the queued `incoming` datagrams, raising
+    `BlockingIOError` once none are left, and every `sendto` is kept in
+    `sent` with its address.
+    """
+
+    def __init__(self, incoming: list[bytes] | None = None, peer=("127.0.0.1", 9999)):
+        """Queue the datagrams the peer will send.
+
+        Args:
+            incoming: Datagrams returned by `recvfrom`, in order.
+            peer: Address the queued datagrams come from.
+        """
+        self.incoming = list(incoming or [])
+        self.peer = peer
+        self.sent: list[tuple[bytes, tuple[str, int]]] = []
+
+    def recvfrom(self, bufsize: int, flags: int = 0) -> tuple[bytes, tuple[str, int]]:
+        """Read the next queued datagram.
+
+        Args:
+            bufsize: Maximum number of bytes to return.
+            flags: Ignored.
+
+        Returns:
+            tuple[bytes, tuple[str, int]]: The datagram and the peer address.
+
+        Raises:
+            BlockingIOError: If no datagram is queued.
+        """
+        if not self.incoming:
+            raise BlockingIOError
+        return self.incoming.pop(0)[:bufsize], self.peer
+
+    def sendto(self, data: bytes, addr: tuple[str, int]):
+        """Record a datagram, copied since senders reuse their buffers.
+
+        Args:
+            data: The datagram.
+            addr: Its destination.
+
+        Returns:
+            None: No return value.
+        """
+        self.sent.append((bytes(data), addr))
+
+    def close(self):
+        pass
+
+    def getsockname(self) -> tuple[str, int]:
+        return ("127.0.0.1", 12345)
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+
+
+class FakeClock:
+    """Time source that only moves when a test advances `now`."""
+
+    def __init__(self, now: float = 1_700_000_000.0):
+        """Start the clock.
+
+        Args:
+            now: Initial time; a plausible wall-clock value, so the clock
+                also stands in for `time.time`.
+        """
+        self.now = now
+
+    def __call__(self) -> float:
+        return self.now
+
+
+def _decode_replies(data: bytes) -> list[Any]:
+    """Split what a receiver sent into the messages it is made of.
+
+    Args:
+        data: The bytes the receiver wrote to the connection.
+
+    Returns:
+        list[Any]: A `Preamble`, `FileAck` or `AbortMessage` per message.
+
+    Raises:
+        ValueError: If a message is not one a receiver sends.
+    """
+    messages: list[Any] = []
+    while data:
+        if data.startswith(ProtocolHandler.PREAMBLE_MAGIC):
+            size = ProtocolHandler.PREAMBLE_SIZE
+            messages.append(ProtocolHandler.unpack_preamble(data[:size]))
+            data = data[size:]
+            continue
+        header = ProtocolHandler.unpack_header(data[: ProtocolHandler.HEADER_SIZE])
+        name_end = ProtocolHandler.HEADER_SIZE + header.name_len
+        end = name_end + header.hash_len
+        if header.op_code == ProtocolHandler.ACK_OP_CODE:
+            messages.append(ProtocolHandler.unpack_ack(header, data[name_end:end]))
+        elif header.op_code == ProtocolHandler.ABORT_OP_CODE:
+            reason = data[ProtocolHandler.HEADER_SIZE : name_end]
+            messages.append(ProtocolHandler.unpack_abort(reason, data[name_end:end]))
+        else:
+            raise ValueError(f"Unexpected reply {header.op_code!r}")
+        data = data[end:]
+    return messages
+
+
+@pytest.fixture
+def stream_socket() -> type[StreamSocket]:
+    """Provide the fake socket class, to build one per connection.
+
+    Returns:
+        type[StreamSocket]: The class; tests may subclass it.
+    """
+    return StreamSocket
+
+
+@pytest.fixture
+def datagram_socket() -> type[DatagramSocket]:
+    """Provide the fake UDP socket class.
+
+    Returns:
+        type[DatagramSocket]: The class; tests may subclass it.
+    """
+    return DatagramSocket
+
+
+@pytest.fixture
+def decode_replies():
+    """Provide the decoder for the messages a receiver sends back.
+
+    Returns:
+        Callable[[bytes], list[Any]]: Splits a reply stream into messages.
+    """
+    return _decode_replies
+
+
+@pytest.fixture
+def fake_clock() -> FakeClock:
+    """Provide a fake clock for components that take a `clock` callable.
+
+    Returns:
+        FakeClock: A clock tests advance by adding to `now`.
+    """
+    return FakeClock()
@@ -0,0 +1,37 @@
+import socket
+
+from tpi_redes.core.errors import (
+    ChecksumMismatchError,
+    NotAFileTransferPeerError,
+    ProtocolDisabledError,
+    TransferAbortedError,
+)
+from tpi_redes.core.exit_codes import ExitCode, exit_code_for
+from tpi_redes.core.protocol import AbortReason
+from tpi_redes.transfer.file_validator import InvalidFileError
+
+
+class TestExitCodes:
+    def test_mapping(self):
+        """Test that failure causes map to their documented exit codes.
+
+        Returns:
+            None: No return value.
+        """
+        cases = [
+            (ConnectionRefusedError(), ExitCode.CONNECTION),
+            (socket.gaierror(), ExitCode.CONNECTION),
+            (TimeoutError(), ExitCode.TIMEOUT),
+            (NotAFileTransferPeerError(b"HTTP/1.1"), ExitCode.CONNECTION),
+            (KeyboardInterrupt(), ExitCode.CANCELLED),
+            (TransferAbortedError(AbortReason.CANCELLED, "peer"), ExitCode.CANCELLED),
+            (TransferAbortedError(AbortReason.DISK_FULL, "peer"), ExitCode.ABORTED),
+            (ChecksumMismatchError(["a.bin"]), ExitCode.INTEGRITY),
+            (InvalidFileError("not a regular file"), ExitCode.USAGE),
+            (FileNotFoundError(), ExitCode.USAGE),
+            (ProtocolDisabledError("udp"), ExitCode.USAGE),
+            (RuntimeError(), ExitCode.FAILURE),
+        ]
+
+        for exc, expected in cases:
+            assert exit_code_for(exc) == expected, exc
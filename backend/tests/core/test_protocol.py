@@ -2,7 +2,7 @@ import struct
 
 import pytest
 
-from tpi_redes.core.protocol import ProtocolHandler
+from tpi_redes.core.protocol import AbortReason, ProtocolHandler
 
 
 class TestProtocolHandler:
@@ -62,3 +62,104 @@ class TestProtocolHandler:
     def test_unpack_invalid_size(self):
         with pytest.raises(ValueError):
             ProtocolHandler.unpack_header(b"too_short")
+
+    def test_pack_unpack_status(self):
+        """Test round-tripping a UDP receiver status report.
+
+        Returns:
+            None: No return value.
+        """
+        packed = ProtocolHandler.pack_status(bytes_written=8192, datagrams_received=2)
+
+        assert len(packed) == ProtocolHandler.STATUS_SIZE
+        status = ProtocolHandler.unpack_status(packed)
+        assert status.bytes_written == 8192
+        assert status.datagrams_received == 2
+        assert status.repaired == 0
+
+        packed = ProtocolHandler.pack_status(8192, 3, repaired=1)
+        assert len(packed) == ProtocolHandler.FEC_STATUS_SIZE
+        assert ProtocolHandler.unpack_status(packed).repaired == 1
+
+    def test_unpack_status_rejects_other_messages(self):
+        with pytest.raises(ValueError):
+            ProtocolHandler.unpack_status(b"X" + b"\x00" * 12)
+
+    def test_pack_abort(self):
+        """Test that abort messages reuse the header with reason and detail.
+
+        Returns:
+            None: No return value.
+        """
+        frame = ProtocolHandler.pack_abort(AbortReason.CANCELLED, "user stop")
+
+        header = ProtocolHandler.unpack_header(frame[: ProtocolHandler.HEADER_SIZE])
+        assert header.op_code == ProtocolHandler.ABORT_OP_CODE
+        payload = frame[ProtocolHandler.HEADER_SIZE :]
+        abort = ProtocolHandler.unpack_abort(
+            payload[: header.name_len], payload[header.name_len :]
+        )
+        assert abort.reason == AbortReason.CANCELLED
+        assert abort.message == "user stop"
+
+    def test_pack_pong(self):
+        """Test that pongs carry the receiver version after the header.
+
+        Returns:
+            None: No return value.
+        """
+        ping = ProtocolHandler.unpack_header(ProtocolHandler.pack_ping())
+        assert ping.op_code == ProtocolHandler.PING_OP_CODE
+        assert ping.name_len == ping.hash_len == 0
+
+        frame = ProtocolHandler.pack_pong("1.2.3")
+        header = ProtocolHandler.unpack_header(frame[: ProtocolHandler.HEADER_SIZE])
+        assert header.op_code == ProtocolHandler.PONG_OP_CODE
+        assert frame[ProtocolHandler.HEADER_SIZE :] == b"1.2.3"
+
+    def test_pack_unpack_ack(self):
+        """Test that acknowledgements carry the stored size and checksum.
+
+        Returns:
+            None: No return value.
+        """
+        frame = ProtocolHandler.pack_ack(1234, "abcd")
+
+        header = ProtocolHandler.unpack_header(frame[: ProtocolHandler.HEADER_SIZE])
+        assert header.op_code == ProtocolHandler.ACK_OP_CODE
+        ack = ProtocolHandler.unpack_ack(header, frame[ProtocolHandler.HEADER_SIZE :])
+        assert ack.bytes_received == 1234
+        assert ack.sha256 == "abcd"
+
+    def test_pack_unpack_chunk(self):
+        """Test that stream chunks carry their running offset and length.
+
+        Returns:
+            None: No return value.
+        """
+        frame = ProtocolHandler.pack_chunk(4096, b"abc")
+
+        chunk = ProtocolHandler.unpack_chunk(frame[: ProtocolHandler.CHUNK_HEADER_SIZE])
+        assert chunk.offset == 4096
+        assert chunk.length == 3
+        assert frame[ProtocolHandler.CHUNK_HEADER_SIZE :] == b"abc"
+
+        with pytest.raises(ValueError):
+            ProtocolHandler.unpack_chunk(ProtocolHandler.pack_status(1, 1))
+
+    def test_pack_unpack_preamble(self):
+        """Test that preambles carry the protocol and tpi-redes versions.
+
+        Returns:
+            None: No return value.
+        """
+        data = ProtocolHandler.pack_preamble("1.2.3")
+
+        assert len(data) == ProtocolHandler.PREAMBLE_SIZE
+        assert data.startswith(ProtocolHandler.PREAMBLE_MAGIC)
+        preamble = ProtocolHandler.unpack_preamble(data)
+        assert preamble.protocol_version == ProtocolHandler.PROTOCOL_VERSION
+        assert preamble.version == "1.2.3"
+
+        with pytest.raises(ValueError):
+            ProtocolHandler.unpack_preamble(b"GET / HTTP/1.1\r\n")
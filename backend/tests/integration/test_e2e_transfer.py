@@ -44,10 +44,16 @@ class TestEndToEndTransfer:
 
         try:
             client = TCPClient()
-            client.send_files([send_file], "127.0.0.1", server_port)
+            summary = client.send_files([send_file], "127.0.0.1", server_port)
 
             time.sleep(0.5)
 
+            assert summary.files == [
+                {"filename": "test_file.bin", "size": 11000, "result": "sent"}
+            ]
+            assert summary.total_bytes == 11000
+            assert set(summary.phases) == {"connect", "handshake", "data"}
+
             received_file = receive_dir / "test_file.bin"
             assert received_file.exists()
             assert received_file.read_bytes() == original_data
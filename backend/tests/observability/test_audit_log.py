@@ -0,0 +1,68 @@
+import json
+
+from tpi_redes.observability.audit_log import GENESIS_HASH, AuditLog
+
+
+class TestAuditLog:
+    def test_entries_are_chained(self, tmp_path):
+        """Test that each entry links to the previous one and verifies.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        log = AuditLog(tmp_path / "audit.log")
+
+        first = log.record("sent", "10.0.0.2:8080", "a.txt", 3, "ab" * 32, "sent")
+        second = log.record(
+            "received", "10.0.0.3:5000", "b.txt", 5, "cd" * 32, "verified"
+        )
+
+        assert first["prev"] == GENESIS_HASH
+        assert second["prev"] == first["hash"]
+        assert second["seq"] == 2
+
+        result = log.verify()
+        assert result.valid
+        assert result.entries == 2
+
+    def test_tampering_is_detected(self, tmp_path):
+        """Test that editing an earlier entry breaks verification.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        path = tmp_path / "audit.log"
+        log = AuditLog(path)
+        for name in ("a.txt", "b.txt", "c.txt"):
+            log.record("received", "10.0.0.3:5000", name, 1, "00" * 32, "verified")
+
+        lines = path.read_text().splitlines()
+        entry = json.loads(lines[1])
+        entry["result"] = "mismatch"
+        lines[1] = json.dumps(entry)
+        path.write_text("\n".join(lines) + "\n")
+
+        result = AuditLog(path).verify()
+        assert not result.valid
+        assert result.error_line == 2
+        assert result.entries == 1
+
+    def test_missing_log_is_empty(self, tmp_path):
+        """Test that a journal that was never written verifies as empty.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        result = AuditLog(tmp_path / "none.log").verify()
+
+        assert result.valid
+        assert result.entries == 0
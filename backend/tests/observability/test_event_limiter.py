@@ -0,0 +1,112 @@
+import threading
+
+from tpi_redes.observability.event_limiter import ErrorEventLimiter
+
+
+def _restarting(ts: float) -> dict:
+    return {
+        "type": "TRANSFER_UPDATE",
+        "status": "restarting",
+        "filename": "a.bin",
+        "ts": ts,
+    }
+
+
+class TestErrorEventLimiter:
+    def test_repeats_are_suppressed_and_counted(self, fake_clock):
+        """Test that repeats within the interval are dropped and reported.
+
+        The first event passes, the next two are dropped, and the first one
+        after the interval carries their count and timestamps.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        limiter = ErrorEventLimiter(interval=1.0, clock=fake_clock)
+
+        assert limiter.admit(_restarting(1.0)) == _restarting(1.0)
+        fake_clock.now += 0.2
+        assert limiter.admit(_restarting(1.2)) is None
+        fake_clock.now += 0.2
+        assert limiter.admit(_restarting(1.4)) is None
+        fake_clock.now += 1.0
+        event = limiter.admit(_restarting(2.4))
+
+        assert event is not None
+        assert event["repeated"] == 2
+        assert (event["first_ts"], event["last_ts"]) == (1.2, 1.4)
+        stats = limiter.stats(warnings=True)["TRANSFER_UPDATE/restarting"]
+        assert stats == {"count": 4, "suppressed": 2, "first_ts": 1.0, "last_ts": 2.4}
+
+    def test_other_events_pass(self, fake_clock):
+        """Test that progress events and other files are never suppressed.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        limiter = ErrorEventLimiter(interval=1.0, clock=fake_clock)
+        progress = {"type": "TRANSFER_UPDATE", "status": "progress", "ts": 1.0}
+
+        assert limiter.admit(progress) == progress
+        assert limiter.admit(progress) == progress
+        assert limiter.admit(_restarting(1.0)) is not None
+        assert limiter.admit({**_restarting(1.0), "filename": "b.bin"}) is not None
+        assert list(limiter.stats(warnings=True)) == ["TRANSFER_UPDATE/restarting"]
+
+    def test_warnings_are_counted_apart(self, fake_clock):
+        """Test that warnings and errors are reported separately.
+
+        Warnings of different codes are also told apart.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        limiter = ErrorEventLimiter(interval=1.0, clock=fake_clock)
+        aborted = {"type": "TRANSFER_UPDATE", "status": "aborted", "reason": "stalled"}
+        warning = {"type": "TRANSFER_WARNING", "code": "slow_disk", "filename": "a"}
+
+        assert limiter.admit(aborted) is not None
+        assert limiter.admit(warning) is not None
+        assert limiter.admit({**warning, "code": "other"}) is not None
+
+        assert list(limiter.stats()) == ["TRANSFER_UPDATE/aborted/stalled"]
+        assert list(limiter.stats(warnings=True)) == [
+            "TRANSFER_WARNING/slow_disk",
+            "TRANSFER_WARNING/other",
+        ]
+
+    def test_shared_between_threads(self, fake_clock):
+        """Test that listener threads sharing one limiter lose no counts.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        limiter = ErrorEventLimiter(interval=60.0, clock=fake_clock)
+        emitted = []
+
+        def worker():
+            for _ in range(2000):
+                if limiter.admit(_restarting(1.0)) is not None:
+                    emitted.append(1)
+
+        threads = [threading.Thread(target=worker) for _ in range(8)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+
+        stats = limiter.stats(warnings=True)["TRANSFER_UPDATE/restarting"]
+        assert len(emitted) == 1
+        assert (stats["count"], stats["suppressed"]) == (16000, 15999)
@@ -0,0 +1,27 @@
+import json
+
+from tpi_redes.observability.packet_logger import PacketLogger
+
+
+class TestPacketLogger:
+    def test_events_carry_wall_and_monotonic_time(self, capsys):
+        """Test that emitted and buffered events are stamped in order.
+
+        Args:
+            capsys: Pytest fixture capturing stdout events.
+
+        Returns:
+            None: No return value.
+        """
+        event = {"type": "TRANSFER_UPDATE", "status": "start"}
+        PacketLogger.emit_event(event)
+        PacketLogger.log_progress({"type": "TRANSFER_UPDATE", "status": "complete"})
+        PacketLogger.flush()
+
+        lines = capsys.readouterr().out.splitlines()
+        first = json.loads(lines[0])
+        second = json.loads(lines[1])[0]
+        assert "ts" not in event
+        assert first["status"] == "start"
+        assert first["ts"] > 1_000_000_000
+        assert 0 <= first["mono"] <= second["mono"]
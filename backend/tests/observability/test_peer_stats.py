@@ -0,0 +1,51 @@
+from tpi_redes.observability.peer_stats import PeerStats
+
+
+class TestPeerStats:
+    def test_counts_per_peer(self, fake_clock):
+        """Test that each sender IP gets its own counters.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        stats = PeerStats(fake_clock)
+        stats.connected("10.0.0.5")
+        stats.connected("10.0.0.5")
+        stats.disconnected("10.0.0.5")
+        stats.handshake_failed("10.0.0.5")
+        stats.auth_failed("10.0.0.9")
+        stats.received("10.0.0.5", 1000)
+
+        snapshot = stats.snapshot()
+        assert snapshot["10.0.0.5"] == {
+            "connections": 2,
+            "current_connections": 1,
+            "handshakes_failed": 1,
+            "auth_failures": 0,
+            "bytes_total": 1000,
+            "bytes_last_hour": 1000,
+        }
+        assert snapshot["10.0.0.9"]["auth_failures"] == 1
+        assert snapshot["10.0.0.9"]["connections"] == 0
+
+    def test_recent_bytes_expire_after_an_hour(self, fake_clock):
+        """Test that old bytes leave the hourly count but not the total.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        stats = PeerStats(fake_clock)
+        stats.received("10.0.0.5", 500)
+        fake_clock.now += 1800
+        stats.received("10.0.0.5", 200)
+        fake_clock.now += 1900
+
+        peer = stats.snapshot()["10.0.0.5"]
+        assert peer["bytes_total"] == 700
+        assert peer["bytes_last_hour"] == 200
@@ -0,0 +1,76 @@
+import pytest
+
+from tpi_redes.observability.receive_report import (
+    build_receive_report,
+    parse_window,
+    render_receive_report,
+)
+
+
+def _outcome(filename: str, peer: str, size: int, result: str) -> dict:
+    """Build an outcome as recorded by the server.
+
+    Args:
+        filename: Name of the file.
+        peer: Sender as `ip:port`.
+        size: Bytes received.
+        result: Outcome of the file.
+
+    Returns:
+        dict: The outcome.
+    """
+    return {
+        "filename": filename,
+        "peer": peer,
+        "size": size,
+        "result": result,
+        "validation": [],
+    }
+
+
+class TestReceiveReport:
+    def test_parse_window(self):
+        """Test that windows parse with and without units and bad ones fail.
+
+        Returns:
+            None: No return value.
+        """
+        assert parse_window("90") == 90
+        assert parse_window("45s") == 45
+        assert parse_window("10m") == 600
+        assert parse_window("1.5h") == 5400
+
+        for spec in ("", "0", "10d", "m", "-5m"):
+            with pytest.raises(ValueError):
+                parse_window(spec)
+
+    def test_report_groups_by_sender_ip(self):
+        """Test the totals, failures and per-sender breakdown.
+
+        Returns:
+            None: No return value.
+        """
+        outcomes = [
+            _outcome("a.txt", "10.0.0.5:50001", 100, "verified"),
+            _outcome("b.txt", "10.0.0.5:50002", 200, "verified"),
+            _outcome("c.txt", "10.0.0.6:40000", 50, "mismatch"),
+            _outcome("d.txt", "10.0.0.6:40001", 0, "aborted:cancelled"),
+        ]
+
+        report = build_receive_report(outcomes, 1000.0, 1600.0)
+
+        assert report["duration"] == 600.0
+        assert (report["files"], report["received"], report["failed"]) == (4, 2, 2)
+        assert report["total_bytes"] == 300
+        assert report["peers"] == {
+            "10.0.0.5": {"files": 2, "received": 2, "failed": 0},
+            "10.0.0.6": {"files": 2, "received": 0, "failed": 2},
+        }
+        assert [f["filename"] for f in report["failures"]] == ["c.txt", "d.txt"]
+
+        text = render_receive_report(report)
+        assert text.splitlines()[0] == (
+            "Receive window of 600 s: 2/4 file(s) received, 300 bytes, 2 failed"
+        )
+        assert "  10.0.0.6: 0 received, 2 failed" in text
+        assert "c.txt from 10.0.0.6:40000: mismatch" in text
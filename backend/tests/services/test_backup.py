@@ -0,0 +1,113 @@
+import json
+import zipfile
+from pathlib import Path
+
+import pytest
+
+from tpi_redes.services.backup import create_backup, restore_backup
+
+
+def _items(root: Path) -> dict[str, Path]:
+    """Place every backed-up item under a directory.
+
+    Args:
+        root: Directory standing in for a machine's state.
+
+    Returns:
+        dict[str, Path]: Location per item name.
+    """
+    return {
+        "settings": root / "settings.json",
+        "presets": root / "presets.json",
+        "received_index": root / "index.jsonl",
+    }
+
+
+def _index_line(transfer_id: str, timestamp: float) -> str:
+    """Build a received index line.
+
+    Args:
+        transfer_id: Id of the entry.
+        timestamp: When the file was received.
+
+    Returns:
+        str: The JSON line.
+    """
+    return json.dumps({"transfer_id": transfer_id, "timestamp": timestamp}) + "\n"
+
+
+class TestBackup:
+    def test_round_trip_into_empty_state(self, tmp_path):
+        """Test that a backup restores every item on a fresh machine.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        old = _items(tmp_path / "old")
+        old["settings"].parent.mkdir()
+        old["settings"].write_text('{"output_dir": "/data"}')
+        old["received_index"].write_text(_index_line("a", 1.0))
+        archive = tmp_path / "state.zip"
+
+        created = create_backup(archive, old)
+        new = _items(tmp_path / "new")
+        restored = restore_backup(archive, new)
+
+        assert created["items"] == ["settings", "received_index"]
+        assert restored["items"]["settings"] == {"action": "created", "added": 1}
+        assert json.loads(new["settings"].read_text()) == {"output_dir": "/data"}
+        assert new["received_index"].read_text() == _index_line("a", 1.0)
+        assert not new["presets"].exists()
+
+    def test_merge_keeps_newer_local_state(self, tmp_path):
+        """Test that merging adds missing entries without clobbering local ones.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        items = _items(tmp_path)
+        items["presets"].write_text('{"lab": {"ip": "10.0.0.1"}}')
+        items["received_index"].write_text(_index_line("a", 1.0))
+        archive = tmp_path / "state.zip"
+        create_backup(archive, items)
+
+        items["presets"].write_text('{"lab": {"ip": "10.0.0.2"}, "nas": {"ip": "n"}}')
+        items["received_index"].write_text(_index_line("b", 2.0))
+        result = restore_backup(archive, items)
+
+        assert result["items"]["presets"] == {"action": "merged", "added": 0}
+        assert json.loads(items["presets"].read_text())["lab"]["ip"] == "10.0.0.2"
+        assert result["items"]["received_index"] == {"action": "merged", "added": 1}
+        assert items["received_index"].read_text() == (
+            _index_line("a", 1.0) + _index_line("b", 2.0)
+        )
+
+        restore_backup(archive, items, overwrite=True)
+        assert json.loads(items["presets"].read_text()) == {"lab": {"ip": "10.0.0.1"}}
+
+    def test_rejects_newer_format_and_foreign_archives(self, tmp_path):
+        """Test that unknown archives are refused before touching local files.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        newer = tmp_path / "newer.zip"
+        with zipfile.ZipFile(newer, "w") as zf:
+            zf.writestr("backup.json", json.dumps({"format": 99, "items": []}))
+        foreign = tmp_path / "foreign.zip"
+        with zipfile.ZipFile(foreign, "w") as zf:
+            zf.writestr("readme.txt", "hello")
+
+        with pytest.raises(ValueError, match="format 99"):
+            restore_backup(newer, _items(tmp_path))
+        with pytest.raises(ValueError, match="not a tpi-redes backup"):
+            restore_backup(foreign, _items(tmp_path))
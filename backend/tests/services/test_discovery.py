@@ -39,6 +39,40 @@ class TestDiscoveryService:
         sent_data = json.loads(args[0].decode("utf-8"))
         assert sent_data["type"] == "PING"
 
+    @patch("socket.socket")
+    def test_scan_includes_capacity(self, mock_socket_cls):
+        """Test that receiver capacity advertised in PONGs reaches the peer list.
+
+        Returns:
+            None: No return value.
+        """
+        mock_socket = MagicMock()
+        mock_socket_cls.return_value.__enter__.return_value = mock_socket
+        pong = {
+            "type": "PONG",
+            "hostname": "Lab",
+            "port": 8080,
+            "protocols": ["tcp", "udp"],
+            "listeners": {"tcp": 8080, "udp": 8081},
+            "free_bytes": 1024,
+            "auth_required": True,
+            "active_transfers": 1,
+            "unrelated": "ignored",
+        }
+        mock_socket.recvfrom.side_effect = [
+            (json.dumps(pong).encode("utf-8"), ("192.168.1.10", 37020)),
+            TimeoutError,
+        ]
+
+        peers = DiscoveryService().scan(timeout=0.1)
+
+        assert peers[0]["protocols"] == ["tcp", "udp"]
+        assert peers[0]["listeners"] == {"tcp": 8080, "udp": 8081}
+        assert peers[0]["free_bytes"] == 1024
+        assert peers[0]["auth_required"] is True
+        assert peers[0]["active_transfers"] == 1
+        assert "unrelated" not in peers[0]
+
     @patch("socket.socket")
     def test_scan_no_peers(self, mock_socket_cls):
         """Test scan with no peers.
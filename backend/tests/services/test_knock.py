@@ -0,0 +1,97 @@
+from tpi_redes.core.base import BaseServer
+from tpi_redes.services.knock import KnockGate, build_knock
+
+SECRET = b"correct horse battery staple"
+
+
+class TestKnockGate:
+    def test_valid_knock_admits_for_window(self, fake_clock):
+        """Test that a signed knock admits its sender until the window ends.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        gate = KnockGate(SECRET, window=30.0, clock=fake_clock)
+
+        assert not gate.is_allowed("203.0.113.7")
+        assert gate.verify(build_knock(SECRET, now=fake_clock.now), "203.0.113.7")
+        assert gate.is_allowed("203.0.113.7")
+        assert not gate.is_allowed("203.0.113.8")
+
+        fake_clock.now += 31.0
+        assert not gate.is_allowed("203.0.113.7")
+
+    def test_rejects_bad_knocks(self, fake_clock):
+        """Test that forged, stale, replayed and malformed knocks are refused.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        gate = KnockGate(SECRET, clock=fake_clock)
+
+        assert not gate.verify(build_knock(b"wrong", now=fake_clock.now), "10.0.0.1")
+        stale = build_knock(SECRET, now=fake_clock.now - 600)
+        assert not gate.verify(stale, "10.0.0.1")
+        assert not gate.verify(b"knock", "10.0.0.1")
+
+        knock = build_knock(SECRET, now=fake_clock.now)
+        assert gate.verify(knock, "10.0.0.1")
+        assert not gate.verify(knock, "10.0.0.2")
+        assert not gate.is_allowed("10.0.0.2")
+
+    def test_rejects_replay_from_sender_clock_ahead(self, fake_clock):
+        """Test that a knock dated ahead of the receiver cannot be replayed.
+
+        Its nonce is remembered for as long as its timestamp is accepted, and
+        knocks without a finite timestamp are refused.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.config import KNOCK_MAX_SKEW
+
+        gate = KnockGate(SECRET, clock=fake_clock)
+        knock = build_knock(SECRET, now=fake_clock.now + KNOCK_MAX_SKEW)
+
+        assert gate.verify(knock, "1.1.1.1")
+        fake_clock.now += KNOCK_MAX_SKEW + 1
+        assert not gate.verify(knock, "6.6.6.6")
+        fake_clock.now += KNOCK_MAX_SKEW
+        assert not gate.verify(knock, "6.6.6.6")
+        assert not gate.is_allowed("6.6.6.6")
+
+        assert not gate.verify(build_knock(SECRET, now=float("nan")), "6.6.6.6")
+
+    def test_server_authorization(self, fake_clock):
+        """Test that servers only admit knocked senders once a gate is set.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+
+        class Server(BaseServer):
+            def start(self):
+                pass
+
+            def stop(self):
+                pass
+
+        gate = KnockGate(SECRET, clock=fake_clock)
+        gated = Server("127.0.0.1", 0, "/tmp", knock_gate=gate)
+
+        assert Server("127.0.0.1", 0, "/tmp").is_authorized(("10.0.0.1", 1))
+        assert not gated.is_authorized(("10.0.0.1", 1))
+        gate.verify(build_knock(SECRET, now=fake_clock.now), "10.0.0.1")
+        assert gated.is_authorized(("10.0.0.1", 1))
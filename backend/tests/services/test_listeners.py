@@ -0,0 +1,33 @@
+from pathlib import Path
+
+import pytest
+
+from tpi_redes.services.listeners import ListenerSpec, parse_listener
+
+
+class TestParseListener:
+    def test_parses_protocol_port_and_directory(self):
+        """Test that the optional directory is kept per listener.
+
+        Returns:
+            None: No return value.
+        """
+        assert parse_listener("udp:8081", ["tcp", "udp"]) == ListenerSpec("udp", 8081)
+        assert parse_listener("tcp:9000=/srv/drop", ["tcp", "udp"]) == ListenerSpec(
+            "tcp", 9000, Path("/srv/drop")
+        )
+
+    @pytest.mark.parametrize(
+        "spec", ["8081", "udp:", "udp:http", "udp:70000", "udp:0", "ws:8443"]
+    )
+    def test_rejects_malformed(self, spec):
+        """Test that bad ports and disabled protocols are refused.
+
+        Args:
+            spec: Listener specification.
+
+        Returns:
+            None: No return value.
+        """
+        with pytest.raises(ValueError):
+            parse_listener(spec, ["tcp", "udp"])
@@ -0,0 +1,56 @@
+import json
+from pathlib import Path
+
+from tpi_redes.services.mirror import Mirror
+
+
+class TestMirror:
+    def test_copies_into_relative_path(self, tmp_path, capsys):
+        """Test that a queued file is copied under its relative path.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "report.csv"
+        source.write_bytes(b"a,b\n")
+        mirror = Mirror(tmp_path / "mirror")
+
+        mirror.submit(source, Path("peer") / "r.csv")
+        stats = mirror.close()
+
+        copy = tmp_path / "mirror" / "peer" / "r.csv"
+        assert copy.read_bytes() == b"a,b\n"
+        assert stats == {"target": str(tmp_path / "mirror"), "mirrored": 1, "failed": 0}
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "mirrored"
+        assert event["path"] == str(copy)
+
+    def test_retries_then_reports_failure(self, tmp_path, capsys):
+        """Test that an unavailable destination is retried, then reported.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "report.csv"
+        source.write_bytes(b"a,b\n")
+        blocker = tmp_path / "mirror"
+        blocker.write_text("not a directory")
+        waits: list[float] = []
+        mirror = Mirror(blocker, retries=2, retry_delay=5.0, sleep=waits.append)
+
+        mirror.submit(source, Path("report.csv"))
+        stats = mirror.close()
+
+        assert (stats["mirrored"], stats["failed"]) == (0, 1)
+        assert waits == [5.0, 5.0]
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "failed"
+        assert event["attempts"] == 3
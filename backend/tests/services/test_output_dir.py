@@ -0,0 +1,63 @@
+import json
+
+from tpi_redes.services.output_dir import save_output_dir, validate_output_dir
+
+
+class TestValidateOutputDir:
+    def test_usable_directory(self, tmp_path):
+        """Test that an existing writable directory passes with its free space.
+
+        Args:
+            tmp_path: Pytest fixture used as the candidate directory.
+
+        Returns:
+            None: No return value.
+        """
+        check = validate_output_dir(tmp_path, min_free_bytes=0)
+
+        assert check.ok
+        assert check.errors == []
+        assert check.free_bytes is not None
+        assert check.path == str(tmp_path.resolve())
+
+    def test_structured_errors(self, tmp_path):
+        """Test that missing paths, files and low space report error codes.
+
+        Args:
+            tmp_path: Pytest fixture for the candidate paths.
+
+        Returns:
+            None: No return value.
+        """
+        a_file = tmp_path / "file.txt"
+        a_file.write_text("x")
+
+        missing = validate_output_dir(tmp_path / "missing")
+        not_dir = validate_output_dir(a_file)
+        full = validate_output_dir(tmp_path, min_free_bytes=2**62)
+
+        assert [e["code"] for e in missing.errors] == ["NOT_FOUND"]
+        assert [e["code"] for e in not_dir.errors] == ["NOT_A_DIRECTORY"]
+        assert [e["code"] for e in full.errors] == ["LOW_SPACE"]
+        assert not (missing.ok or not_dir.ok or full.ok)
+
+
+class TestSaveOutputDir:
+    def test_preserves_other_settings(self, tmp_path):
+        """Test that saving the directory keeps unrelated settings.
+
+        Args:
+            tmp_path: Pytest fixture for the settings file.
+
+        Returns:
+            None: No return value.
+        """
+        settings = tmp_path / "settings.json"
+        settings.write_text(json.dumps({"theme": "dark"}))
+
+        save_output_dir(tmp_path / "inbox", settings)
+
+        assert json.loads(settings.read_text()) == {
+            "theme": "dark",
+            "output_dir": str(tmp_path / "inbox"),
+        }
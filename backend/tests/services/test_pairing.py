@@ -0,0 +1,83 @@
+from unittest.mock import patch
+
+import pytest
+
+from tpi_redes.services.pairing import (
+    PairingInfo,
+    connect_from_pairing,
+    decode_pairing,
+    encode_pairing,
+)
+from tpi_redes.transport.ping import PingResult
+
+
+class TestPairing:
+    def test_round_trip(self):
+        """Test that a pairing code decodes to the info it was made from.
+
+        Returns:
+            None: No return value.
+        """
+        info = PairingInfo(
+            addresses=["192.168.1.10", "10.0.0.5"],
+            port=9000,
+            protocol="udp",
+            hostname="lab",
+            knock=True,
+            secret="s3cret",
+        )
+
+        payload = encode_pairing(info)
+
+        assert payload.startswith("tpir1:")
+        assert "=" not in payload
+        assert decode_pairing(payload) == info
+
+    def test_rejects_malformed_codes(self):
+        """Test that foreign or damaged codes raise ValueError.
+
+        Returns:
+            None: No return value.
+        """
+        for payload in ("http://example.com", "tpir1:!!!", "tpir1:e30"):
+            with pytest.raises(ValueError):
+                decode_pairing(payload)
+
+    def test_connect_picks_first_reachable_address(self):
+        """Test that candidates are probed in order until one answers.
+
+        Returns:
+            None: No return value.
+        """
+        payload = encode_pairing(
+            PairingInfo(addresses=["10.0.0.1", "10.0.0.2"], port=8080)
+        )
+
+        def probe(addr, protocol, timeout):
+            return PingResult(addr[0], addr[1], protocol, addr[0] == "10.0.0.2")
+
+        with patch("tpi_redes.services.pairing.ping_peer", side_effect=probe):
+            ip, info = connect_from_pairing(payload)
+
+        assert ip == "10.0.0.2"
+        assert info.port == 8080
+
+    def test_connect_knocks_and_fails_when_unreachable(self):
+        """Test that gated receivers are knocked and no answer raises.
+
+        Returns:
+            None: No return value.
+        """
+        payload = encode_pairing(
+            PairingInfo(addresses=["10.0.0.1"], port=8080, knock=True, secret="k")
+        )
+        unreachable = PingResult("10.0.0.1", 8080, "tcp", False, error="timeout")
+
+        with (
+            patch("tpi_redes.services.pairing.send_knock") as knock,
+            patch("tpi_redes.services.pairing.ping_peer", return_value=unreachable),
+            pytest.raises(ConnectionError),
+        ):
+            connect_from_pairing(payload)
+
+        knock.assert_called_once_with("10.0.0.1", b"k")
@@ -0,0 +1,44 @@
+import os
+import signal
+import threading
+
+import pytest
+
+from tpi_redes.services.pause_control import PauseControl
+
+
+class TestPauseControl:
+    def test_wait_blocks_until_resumed(self):
+        """Test that a paused control blocks waiters until resumed.
+
+        Returns:
+            None: No return value.
+        """
+        pause = PauseControl()
+        assert pause.wait_resumed() < 0.1
+
+        pause.pause()
+        threading.Timer(0.2, pause.resume).start()
+
+        assert pause.paused
+        assert pause.wait_resumed() >= 0.2
+        assert not pause.paused
+
+    @pytest.mark.skipif(not hasattr(signal, "SIGUSR1"), reason="Unix signals")
+    def test_signals_pause_and_resume(self):
+        """Test that SIGUSR1 pauses and SIGUSR2 resumes inside the context.
+
+        Handlers are restored on exit.
+
+        Returns:
+            None: No return value.
+        """
+        previous = signal.getsignal(signal.SIGUSR1)
+
+        with PauseControl() as pause:
+            os.kill(os.getpid(), signal.SIGUSR1)
+            assert pause.paused
+            os.kill(os.getpid(), signal.SIGUSR2)
+            assert not pause.paused
+
+        assert signal.getsignal(signal.SIGUSR1) is previous
@@ -0,0 +1,45 @@
+import json
+
+from tpi_redes.services.presets import Preset, PresetStore
+
+
+class TestPresetStore:
+    def test_save_load_delete(self, tmp_path):
+        """Test the full lifecycle of presets in the JSON store.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        store = PresetStore(tmp_path / "presets.json")
+        assert store.load() == []
+
+        store.save(Preset("lab-server", "10.0.0.5", protocol="udp", no_pacing=True))
+        store.save(Preset("backup", "10.0.0.9", port=9000, emit_manifest=True))
+
+        assert [p.name for p in store.load()] == ["backup", "lab-server"]
+        lab = store.get("lab-server")
+        assert (lab.ip, lab.protocol, lab.no_pacing) == ("10.0.0.5", "udp", True)
+
+        store.save(Preset("lab-server", "10.0.0.6"))
+        assert store.get("lab-server").ip == "10.0.0.6"
+
+        store.delete("backup")
+        assert [p.name for p in store.load()] == ["lab-server"]
+        assert list(tmp_path.iterdir()) == [tmp_path / "presets.json"]
+
+    def test_ignores_unknown_fields(self, tmp_path):
+        """Test that presets written by newer versions still load.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        path = tmp_path / "presets.json"
+        path.write_text(json.dumps({"lab": {"ip": "10.0.0.5", "compression": "zstd"}}))
+
+        assert PresetStore(path).get("lab") == Preset("lab", "10.0.0.5")
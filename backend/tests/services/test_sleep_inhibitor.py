@@ -0,0 +1,65 @@
+import json
+from unittest.mock import MagicMock, patch
+
+from tpi_redes.services.sleep_inhibitor import SleepInhibitor
+
+
+class TestSleepInhibitor:
+    """Tests for the SleepInhibitor class."""
+
+    @patch("tpi_redes.services.sleep_inhibitor.sys.platform", "linux")
+    @patch("tpi_redes.services.sleep_inhibitor.shutil.which")
+    @patch("tpi_redes.services.sleep_inhibitor.subprocess.Popen")
+    def test_reference_counted(
+        self, mock_popen: MagicMock, mock_which: MagicMock, capsys
+    ):
+        """Test that overlapping transfers share one inhibitor process.
+
+        Args:
+            mock_popen: Mock for subprocess.Popen.
+            mock_which: Mock for shutil.which.
+            capsys: Pytest fixture capturing stdout events.
+
+        Returns:
+            None: No return value.
+        """
+        mock_which.return_value = "/usr/bin/systemd-inhibit"
+        inhibitor = SleepInhibitor()
+
+        inhibitor.acquire()
+        inhibitor.acquire()
+        assert inhibitor.active
+        assert mock_popen.call_count == 1
+        assert mock_popen.call_args[0][0][0] == "systemd-inhibit"
+
+        inhibitor.release()
+        assert inhibitor.active
+        mock_popen.return_value.terminate.assert_not_called()
+
+        inhibitor.release()
+        assert not inhibitor.active
+        mock_popen.return_value.terminate.assert_called_once()
+
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        assert [e["active"] for e in events] == [True, False]
+        assert all(e["type"] == "SLEEP_INHIBIT" for e in events)
+
+    @patch("tpi_redes.services.sleep_inhibitor.sys.platform", "linux")
+    @patch("tpi_redes.services.sleep_inhibitor.shutil.which", return_value=None)
+    @patch("tpi_redes.services.sleep_inhibitor.subprocess.Popen")
+    def test_unsupported_host(self, mock_popen: MagicMock, _which: MagicMock, capsys):
+        """Test that a missing mechanism is tolerated without emitting events.
+
+        Args:
+            mock_popen: Mock for subprocess.Popen.
+            _which: Mock for shutil.which.
+            capsys: Pytest fixture capturing stdout events.
+
+        Returns:
+            None: No return value.
+        """
+        with SleepInhibitor() as inhibitor:
+            assert not inhibitor.active
+
+        mock_popen.assert_not_called()
+        assert capsys.readouterr().out == ""
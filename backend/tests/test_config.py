@@ -0,0 +1,52 @@
+import stat
+
+from tpi_redes import config
+
+
+class TestDefaultOutputDir:
+    def test_creates_private_directory(self, tmp_path, monkeypatch):
+        """Test that the default output directory is created owner-only.
+
+        Args:
+            tmp_path: Pytest fixture.
+            monkeypatch: Pytest fixture to override the configured directory.
+
+        Returns:
+            None: No return value.
+        """
+        target = tmp_path / "inbox"
+        monkeypatch.setattr(config, "DEFAULT_SAVE_DIR", str(target))
+
+        output_dir = config.get_default_output_dir()
+
+        assert output_dir == target.resolve()
+        assert output_dir.is_dir()
+        assert stat.S_IMODE(output_dir.stat().st_mode) == config.OUTPUT_DIR_MODE
+
+    def test_per_peer_subdirectory(self, tmp_path, monkeypatch):
+        monkeypatch.setattr(config, "DEFAULT_SAVE_DIR", str(tmp_path))
+
+        output_dir = config.get_default_output_dir("fe80::1")
+
+        assert output_dir == tmp_path.resolve() / "fe80__1"
+        assert output_dir.is_dir()
+
+
+class TestTuningProfiles:
+    def test_profiles_cover_both_protocols(self):
+        """Test that every profile tunes TCP and UDP with known options only.
+
+        Returns:
+            None: No return value.
+        """
+        common = {"chunk_size", "socket_buffer"}
+        allowed = {
+            "tcp": common | {"ack_timeout", "restart_on_change"},
+            "udp": common | {"pacing_rate", "fec"},
+        }
+
+        for name, per_protocol in config.TUNING_PROFILES.items():
+            assert set(per_protocol) == {"tcp", "udp"}, name
+            for protocol, values in per_protocol.items():
+                assert set(values) <= allowed[protocol], (name, protocol)
+                assert all(value > 0 for value in values.values())
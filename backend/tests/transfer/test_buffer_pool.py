@@ -0,0 +1,47 @@
+from tpi_redes.transfer.buffer_pool import BufferPool
+
+
+class TestBufferPool:
+    def test_reuses_released_buffers(self):
+        """Test that released buffers are handed out again.
+
+        Returns:
+            None: No return value.
+        """
+        pool = BufferPool(16, capacity=2)
+
+        first = pool.acquire()
+        pool.release(first)
+        second = pool.acquire()
+
+        assert second is first
+        assert len(second) == 16
+        stats = pool.stats()
+        assert (stats["allocated"], stats["reused"], stats["in_use"]) == (1, 1, 1)
+
+    def test_overflow_allocates_and_is_dropped(self):
+        """Test that a full pool still lends buffers but keeps only capacity.
+
+        Returns:
+            None: No return value.
+        """
+        pool = BufferPool(16, capacity=1)
+
+        buffers = [pool.acquire() for _ in range(3)]
+        for buffer in buffers:
+            pool.release(buffer)
+        pool.acquire()
+        pool.acquire()
+
+        stats = pool.stats()
+        assert stats["allocated"] == 4
+        assert stats["reused"] == 1
+        assert stats["peak_in_use"] == 3
+
+    def test_default_capacity_covers_readahead(self):
+        """Test that the default pool holds the read-ahead window.
+
+        Returns:
+            None: No return value.
+        """
+        assert BufferPool(1024, capacity=0, budget=8192).capacity == 10
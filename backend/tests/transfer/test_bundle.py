@@ -0,0 +1,53 @@
+from pathlib import Path
+
+from tpi_redes.config import BUNDLE_MAX_BYTES
+from tpi_redes.transfer.bundle import BundleReader, bundle_prefix
+
+
+class TestBundlePrefix:
+    def test_takes_leading_small_files(self):
+        """Test that the run of small files stops at the first large one.
+
+        Returns:
+            None: No return value.
+        """
+        files = [Path("a"), Path("b"), Path("big"), Path("c")]
+        sizes = dict(zip(files, [10, 20, 5000, 10], strict=True))
+
+        assert bundle_prefix(files, sizes, 100) == files[:2]
+
+    def test_lone_or_oversized_files_are_not_bundled(self):
+        """Test that a single small file or a full bundle is left alone.
+
+        Returns:
+            None: No return value.
+        """
+        files = [Path("a"), Path("big")]
+        sizes = {files[0]: 10, files[1]: 5000}
+        assert bundle_prefix(files, sizes, 100) == []
+
+        many = [Path(str(i)) for i in range(3)]
+        half = {f: BUNDLE_MAX_BYTES // 2 for f in many}
+        assert bundle_prefix(many, half, BUNDLE_MAX_BYTES) == many[:2]
+
+
+class TestBundleReader:
+    def test_reads_payload_before_connection(self, stream_socket):
+        """Test that fed payloads are read first, then the connection.
+
+        Args:
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        sock = stream_socket(b"socket")
+        sock.settimeout(5.0)
+        reader = BundleReader(sock)
+        reader.feed(b"abc")
+        reader.feed(b"de")
+
+        assert reader.recv(4) == b"abcd"
+        assert reader.recv(4) == b"e"
+        assert reader.recv(6) == b"socket"
+        assert reader.timeout == 5.0
@@ -0,0 +1,123 @@
+import hashlib
+import os
+import time
+from unittest.mock import MagicMock, patch
+
+from tpi_redes.transfer.checksum_cache import ChecksumCache
+
+
+def _age(path, seconds: float = 60.0):
+    """Backdate a file's mtime so it is not considered racy.
+
+    Args:
+        path: File to backdate.
+        seconds: How far in the past to set the mtime.
+
+    Returns:
+        None: No return value.
+    """
+    past = time.time() - seconds
+    os.utime(path, (past, past))
+
+
+class TestChecksumCache:
+    def test_reuses_checksum_of_unchanged_file(self, tmp_path):
+        """Test that a second lookup of an unchanged file skips hashing.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "big.bin"
+        source.write_bytes(b"payload")
+        _age(source)
+        cache_path = tmp_path / "cache.json"
+
+        cache = ChecksumCache(cache_path)
+        digest = cache.checksum(source)
+        assert digest == hashlib.sha256(b"payload").hexdigest()
+        cache.save()
+
+        reloaded = ChecksumCache(cache_path)
+        with patch(
+            "tpi_redes.transfer.checksum_cache.IntegrityVerifier"
+        ) as mock_verifier:
+            assert reloaded.checksum(source) == digest
+            mock_verifier.assert_not_called()
+        assert reloaded.stats() == {
+            "hits": 1,
+            "misses": 0,
+            "invalidated": 0,
+            "entries": 1,
+        }
+
+    def test_invalidates_on_change(self, tmp_path):
+        """Test that size, mtime or limit changes force a re-hash.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "log.txt"
+        source.write_bytes(b"first")
+        _age(source, 120)
+        cache = ChecksumCache(tmp_path / "cache.json")
+        cache.checksum(source)
+
+        source.write_bytes(b"second")
+        _age(source, 60)
+        assert cache.checksum(source) == hashlib.sha256(b"second").hexdigest()
+        assert cache.checksum(source, limit=3) == hashlib.sha256(b"sec").hexdigest()
+        assert cache.stats()["invalidated"] == 2
+
+    def test_skips_recently_modified_files(self, tmp_path):
+        """Test that files modified within the racy window are not cached.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "fresh.txt"
+        source.write_bytes(b"fresh")
+        cache = ChecksumCache(tmp_path / "cache.json")
+
+        cache.checksum(source)
+        cache.save()
+
+        assert cache.stats()["entries"] == 0
+        assert not (tmp_path / "cache.json").exists()
+
+    def test_evicts_least_recently_used(self, tmp_path):
+        """Test that the cache keeps at most `max_entries` files.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        files = []
+        for name in ("a", "b", "c"):
+            path = tmp_path / name
+            path.write_bytes(name.encode())
+            _age(path)
+            files.append(path)
+        cache = ChecksumCache(tmp_path / "cache.json", max_entries=2)
+
+        cache.checksum(files[0])
+        cache.checksum(files[1])
+        cache.checksum(files[0])
+        cache.checksum(files[2])
+
+        verifier = MagicMock()
+        with patch("tpi_redes.transfer.checksum_cache.IntegrityVerifier", verifier):
+            cache.checksum(files[0])
+            verifier.assert_not_called()
+            cache.checksum(files[1])
+            verifier.assert_called_once()
@@ -0,0 +1,97 @@
+import random
+
+from tpi_redes.transfer.fec import FecDecoder, FecEncoder
+
+
+def _frames(content: bytes, chunk_size: int, group_size: int) -> list[bytes]:
+    """Encode content the way the UDP sender does.
+
+    Args:
+        content: The file content.
+        chunk_size: Content bytes per data datagram.
+        group_size: Data datagrams per parity datagram.
+
+    Returns:
+        list[bytes]: Every datagram, parity included, in sending order.
+    """
+    encoder = FecEncoder(group_size)
+    frames: list[bytes] = []
+    for offset in range(0, len(content), chunk_size):
+        frames += encoder.encode(offset, content[offset : offset + chunk_size])
+    return frames + encoder.flush()
+
+
+def _decode(frames: list[bytes]) -> tuple[bytes, FecDecoder]:
+    """Feed datagrams to a decoder and join what it releases.
+
+    Args:
+        frames: Datagrams in arrival order.
+
+    Returns:
+        tuple[bytes, FecDecoder]: The released content and the decoder.
+    """
+    decoder = FecDecoder()
+    return b"".join(c for f in frames for c in decoder.add(f)), decoder
+
+
+class TestFec:
+    def test_parity_per_group(self):
+        """Test that one parity frame follows each group, including the last.
+
+        Returns:
+            None: No return value.
+        """
+        encoder = FecEncoder(4)
+        for offset in range(0, 10):
+            encoder.encode(offset, b"x")
+        encoder.flush()
+
+        assert encoder.data_frames == 10
+        assert encoder.parity_frames == 3
+
+    def test_repairs_one_loss_per_group(self):
+        """Test that one lost data frame per group is rebuilt, even the last one.
+
+        Returns:
+            None: No return value.
+        """
+        content = random.Random(7).randbytes(1000)
+        frames = _frames(content, 100, 4)
+        # Frames are d0-d3 p0 d4-d7 p1 d8 d9 p2: lose d1, d7 and d9.
+        lost = {1, 8, 11}
+
+        received, decoder = _decode([f for i, f in enumerate(frames) if i not in lost])
+
+        assert received == content
+        assert decoder.repaired == 3
+
+    def test_two_losses_in_a_group_leave_a_gap(self):
+        """Test that content after an unrepairable gap is held back.
+
+        Returns:
+            None: No return value.
+        """
+        content = bytes(range(200))
+        frames = _frames(content, 20, 4)
+
+        kept = [f for i, f in enumerate(frames) if i not in (1, 2)]
+        received, decoder = _decode(kept)
+
+        assert received == content[:20]
+        assert decoder.repaired == 0
+        assert decoder.buffered_bytes > 0
+
+    def test_reordered_and_duplicated_frames(self):
+        """Test that content is released in order whatever the arrival order.
+
+        Returns:
+            None: No return value.
+        """
+        content = bytes(range(256)) * 4
+        frames = _frames(content, 64, 5)
+        shuffled = frames + frames[:3]
+        random.Random(1).shuffle(shuffled)
+
+        received, _decoder = _decode(shuffled)
+
+        assert received == content
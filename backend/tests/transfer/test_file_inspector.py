@@ -0,0 +1,88 @@
+import codecs
+import struct
+import wave
+
+from tpi_redes.transfer.file_inspector import inspect_file
+
+
+class TestFileInspector:
+    def test_png_dimensions(self, tmp_path):
+        """Test that PNG files are identified by magic bytes with their size.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        path = tmp_path / "picture.dat"
+        ihdr = struct.pack(">II", 640, 480) + b"\x08\x06\x00\x00\x00"
+        path.write_bytes(b"\x89PNG\r\n\x1a\n" + struct.pack(">I", 13) + b"IHDR" + ihdr)
+
+        info = inspect_file(path)
+
+        assert info.mime == "image/png"
+        assert (info.width, info.height) == (640, 480)
+        assert info.encoding is None
+
+    def test_jpeg_dimensions(self, tmp_path):
+        """Test that JPEG frame size is read past the leading segments.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        path = tmp_path / "photo.jpg"
+        app0 = b"\xff\xe0" + struct.pack(">H", 16) + b"JFIF\x00" + b"\x00" * 9
+        sof0 = b"\xff\xc0" + struct.pack(">HBHH", 11, 8, 1080, 1920) + b"\x00" * 4
+        path.write_bytes(b"\xff\xd8" + app0 + sof0)
+
+        info = inspect_file(path)
+
+        assert info.mime == "image/jpeg"
+        assert (info.width, info.height) == (1920, 1080)
+
+    def test_wav_duration(self, tmp_path):
+        """Test that WAV duration is computed from the header.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        path = tmp_path / "tone.wav"
+        with wave.open(str(path), "wb") as w:
+            w.setnchannels(1)
+            w.setsampwidth(2)
+            w.setframerate(8000)
+            w.writeframes(b"\x00\x00" * 12000)
+
+        info = inspect_file(path)
+
+        assert info.mime == "audio/wav"
+        assert info.duration == 1.5
+
+    def test_text_encoding(self, tmp_path):
+        """Test text detection for UTF-8, BOM-marked and binary content.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        utf8 = tmp_path / "notes"
+        utf8.write_text("año\n", encoding="utf-8")
+        utf16 = tmp_path / "export.csv"
+        utf16.write_bytes(codecs.BOM_UTF16_LE + "a,b\n".encode("utf-16-le"))
+        binary = tmp_path / "blob.bin"
+        binary.write_bytes(b"\x00\x01\x02\xff")
+
+        info = inspect_file(utf8)
+        assert (info.mime, info.encoding) == ("text/plain", "utf-8")
+        assert inspect_file(utf16).encoding == "utf-16-le"
+        assert inspect_file(binary).encoding is None
+        assert inspect_file(binary).mime == "application/octet-stream"
@@ -0,0 +1,67 @@
+import os
+from pathlib import Path
+
+import pytest
+
+from tpi_redes.transfer.file_validator import FileValidator, InvalidFileError
+
+
+class TestFileValidator:
+    def test_regular_file_size(self, tmp_path):
+        """Test that regular files are accepted and report their size.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        f = tmp_path / "regular.bin"
+        f.write_bytes(b"x" * 100)
+
+        assert FileValidator(f).transfer_size() == 100
+
+    def test_max_read_bytes_caps_size(self, tmp_path):
+        f = tmp_path / "growing.log"
+        f.write_bytes(b"x" * 100)
+
+        assert FileValidator(f).transfer_size(max_read_bytes=40) == 40
+        assert FileValidator(f).transfer_size(max_read_bytes=500) == 100
+
+    def test_rejects_fifo(self, tmp_path):
+        """Test that FIFOs are refused instead of being read forever.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        fifo = tmp_path / "pipe"
+        os.mkfifo(fifo)
+
+        with pytest.raises(InvalidFileError, match="FIFO"):
+            FileValidator(fifo).validate()
+
+    def test_rejects_character_device(self):
+        with pytest.raises(InvalidFileError, match="character device"):
+            FileValidator(Path("/dev/zero")).validate()
+
+    def test_capped_special_files_announce_the_cap(self, tmp_path):
+        """Test that FIFOs and devices are accepted once a cap is given.
+
+        Directories are refused even then, since they cannot be read.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        fifo = tmp_path / "pipe"
+        os.mkfifo(fifo)
+
+        assert FileValidator(fifo).transfer_size(max_read_bytes=64) == 64
+        assert FileValidator(Path("/dev/zero")).transfer_size(max_read_bytes=0) == 0
+        with pytest.raises(InvalidFileError, match="directory"):
+            FileValidator(tmp_path).validate(max_read_bytes=64)
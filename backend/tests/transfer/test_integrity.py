@@ -1,3 +1,4 @@
+import hashlib
 import tempfile
 from pathlib import Path
 
@@ -56,3 +57,18 @@ class TestIntegrityVerifier:
             hash_file.unlink()
         finally:
             tmp_path.unlink()
+
+    def test_calculate_hash_with_limit(self, tmp_path):
+        """Test hashing only the leading bytes of a file.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        f = tmp_path / "limited.bin"
+        f.write_bytes(b"A" * 5000 + b"B" * 5000)
+
+        expected = hashlib.sha256(b"A" * 5000).hexdigest()
+        assert IntegrityVerifier(f).calculate_hash(limit=5000) == expected
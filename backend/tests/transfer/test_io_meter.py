@@ -0,0 +1,30 @@
+from tpi_redes.transfer.io_meter import IoMeter
+
+
+class TestIoMeter:
+    def test_separates_network_and_disk_rates(self):
+        """Test that each stage's rate uses only the time spent in it.
+
+        Returns:
+            None: No return value.
+        """
+        now = [0.0]
+        meter = IoMeter(clock=lambda: now[0])
+
+        for _ in range(4):
+            started = meter.clock()
+            now[0] += 0.5
+            meter.network(1000, started)
+            started = meter.clock()
+            now[0] += 0.01
+            meter.disk(1000, started)
+
+        assert meter.rates() == {"network_rate": 2000, "disk_rate": 100000}
+
+    def test_no_time_measured(self):
+        """Test that rates are unknown before anything was measured.
+
+        Returns:
+            None: No return value.
+        """
+        assert IoMeter().rates() == {"network_rate": None, "disk_rate": None}
@@ -0,0 +1,79 @@
+import hashlib
+
+import pytest
+
+from tpi_redes.transfer.manifest import (
+    build_batch_preview,
+    build_manifest,
+    parse_batch_preview,
+    parse_manifest,
+    verify_tree,
+)
+
+
+class TestManifest:
+    def test_round_trip(self):
+        """Test that a built manifest parses back to the same entries.
+
+        Returns:
+            None: No return value.
+        """
+        hashes = {"a.txt": "0" * 64, "b.bin": "f" * 64}
+
+        text = build_manifest(hashes)
+
+        assert text.splitlines()[0] == f"{'0' * 64}  a.txt"
+        assert parse_manifest(text) == hashes
+
+    def test_rejects_paths(self):
+        """Test that entries pointing outside the directory are refused.
+
+        Returns:
+            None: No return value.
+        """
+        with pytest.raises(ValueError):
+            parse_manifest(f"{'0' * 64}  ../etc/passwd\n")
+
+    def test_batch_preview_round_trip(self):
+        """Test that a batch preview keeps names, sizes and order.
+
+        Returns:
+            None: No return value.
+        """
+        sizes = {"b report.pdf": 1024, "a.txt": 0}
+
+        text = build_batch_preview(sizes)
+
+        assert text == "1024 b report.pdf\n0 a.txt\n"
+        assert list(parse_batch_preview(text).items()) == list(sizes.items())
+        for bad in ("12\n", "x a.txt\n", "5 ../a.txt\n"):
+            with pytest.raises(ValueError):
+                parse_batch_preview(bad)
+
+    def test_verify_tree(self, tmp_path):
+        """Test re-verification of a received directory.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        (tmp_path / "good.txt").write_bytes(b"good")
+        (tmp_path / "bad.txt").write_bytes(b"tampered")
+        (tmp_path / "SHA256SUMS").write_text(
+            build_manifest(
+                {
+                    "good.txt": hashlib.sha256(b"good").hexdigest(),
+                    "bad.txt": hashlib.sha256(b"original").hexdigest(),
+                    "gone.txt": hashlib.sha256(b"gone").hexdigest(),
+                }
+            )
+        )
+
+        report = verify_tree(tmp_path)
+
+        assert report.ok == ["good.txt"]
+        assert report.mismatched == ["bad.txt"]
+        assert report.missing == ["gone.txt"]
+        assert not report.valid
@@ -0,0 +1,118 @@
+import io
+import struct
+import tarfile
+import zipfile
+import zlib
+
+import pytest
+
+from tpi_redes.transfer.post_validation import parse_validators, validate_file
+
+
+def _png(width: int = 1, height: int = 1) -> bytes:
+    """Build a minimal grayscale PNG.
+
+    Args:
+        width: Image width in pixels.
+        height: Image height in pixels.
+
+    Returns:
+        bytes: The encoded image.
+    """
+
+    def chunk(kind: bytes, body: bytes) -> bytes:
+        crc = struct.pack(">I", zlib.crc32(kind + body))
+        return struct.pack(">I", len(body)) + kind + body + crc
+
+    ihdr = struct.pack(">IIBBBBB", width, height, 8, 0, 0, 0, 0)
+    pixels = zlib.compress(b"\x00" + b"\x80" * width * height)
+    return (
+        b"\x89PNG\r\n\x1a\n"
+        + chunk(b"IHDR", ihdr)
+        + chunk(b"IDAT", pixels)
+        + chunk(b"IEND", b"")
+    )
+
+
+class TestPostValidation:
+    def test_parse_validators(self):
+        """Test that validator lists parse and unknown names are rejected.
+
+        Returns:
+            None: No return value.
+        """
+        assert parse_validators("") == ()
+        assert parse_validators("json, CSV") == ("json", "csv")
+        assert set(parse_validators("all")) == {"zip", "tar", "json", "csv", "image"}
+
+        with pytest.raises(ValueError):
+            parse_validators("zip,pdf")
+
+    def test_valid_files_pass(self, tmp_path):
+        """Test that well-formed files of each type pass their validator.
+
+        Args:
+            tmp_path: Pytest fixture for the files.
+
+        Returns:
+            None: No return value.
+        """
+        archive = tmp_path / "bundle.zip"
+        with zipfile.ZipFile(archive, "w") as z:
+            z.writestr("a.txt", "hello")
+        tarball = tmp_path / "bundle.tar.gz"
+        with tarfile.open(tarball, "w:gz") as t:
+            info = tarfile.TarInfo("a.txt")
+            info.size = 5
+            t.addfile(info, io.BytesIO(b"hello"))
+        (tmp_path / "data.json").write_text('{"a": [1, 2]}')
+        (tmp_path / "rows.csv").write_text("a,b\n1,2\n3,4\n")
+        (tmp_path / "pixel.png").write_bytes(_png())
+        names = parse_validators("all")
+
+        for path in sorted(tmp_path.iterdir()):
+            results = validate_file(path, names)
+            assert len(results) == 1, path.name
+            assert results[0].ok, (path.name, results[0].error)
+
+    def test_corrupt_files_fail(self, tmp_path):
+        """Test that truncated or malformed files are reported with a reason.
+
+        Args:
+            tmp_path: Pytest fixture for the files.
+
+        Returns:
+            None: No return value.
+        """
+        archive = io.BytesIO()
+        with zipfile.ZipFile(archive, "w") as z:
+            z.writestr("a.txt", "hello" * 100)
+        (tmp_path / "bundle.zip").write_bytes(archive.getvalue()[:-40])
+        (tmp_path / "data.json").write_text('{"a": [1, 2')
+        (tmp_path / "rows.csv").write_text("a,b\n1,2\n3\n")
+        (tmp_path / "pixel.png").write_bytes(_png(8, 8)[:-20])
+        names = parse_validators("all")
+
+        for path in sorted(tmp_path.iterdir()):
+            results = validate_file(path, names)
+            assert len(results) == 1, path.name
+            assert not results[0].ok, path.name
+            assert results[0].error
+
+    def test_only_selected_and_applicable_validators_run(self, tmp_path):
+        """Test that validators run only when selected and matching the file.
+
+        Args:
+            tmp_path: Pytest fixture for the files.
+
+        Returns:
+            None: No return value.
+        """
+        broken = tmp_path / "data.json"
+        broken.write_text("{")
+        notes = tmp_path / "notes.txt"
+        notes.write_text("{")
+
+        assert validate_file(broken, ("csv",)) == []
+        assert validate_file(broken, ()) == []
+        assert validate_file(notes, parse_validators("all")) == []
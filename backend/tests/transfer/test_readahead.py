@@ -0,0 +1,94 @@
+import time
+
+from tpi_redes.transfer.buffer_pool import BufferPool
+from tpi_redes.transfer.readahead import ReadaheadReader
+
+
+class TestReadaheadReader:
+    def test_buffers_ahead_up_to_limit(self, tmp_path):
+        """Test that chunks are read ahead and stop at the byte limit.
+
+        Args:
+            tmp_path: Pytest fixture for the source file.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "data.bin"
+        source.write_bytes(bytes(range(100)))
+
+        with ReadaheadReader(source, 90, 10, budget=100) as reader:
+            for _ in range(50):
+                if reader.fill >= 0.9:
+                    break
+                time.sleep(0.01)
+            assert reader.fill == 0.9
+            chunks = []
+            while chunk := reader.read():
+                chunks.append(chunk)
+
+        assert b"".join(chunks) == bytes(range(90))
+        assert all(len(c) == 10 for c in chunks)
+        assert reader.fill == 0.0
+        assert reader.read() == b""
+
+    def test_small_budget_reads_inline(self, tmp_path):
+        """Test that a budget below one chunk disables the background reader.
+
+        Args:
+            tmp_path: Pytest fixture for the source file.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "data.bin"
+        source.write_bytes(b"abcdefgh")
+
+        with ReadaheadReader(source, 8, 3, budget=0) as reader:
+            chunks = [reader.read(), reader.read(), reader.read(), reader.read()]
+
+        assert chunks == [b"abc", b"def", b"gh", b""]
+        assert reader.fill == 0.0
+
+    def test_close_before_end_stops_reader(self, tmp_path):
+        """Test that closing mid-file does not hang on a full buffer.
+
+        Args:
+            tmp_path: Pytest fixture for the source file.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "data.bin"
+        source.write_bytes(b"x" * 1000)
+
+        reader = ReadaheadReader(source, 1000, 10, budget=20)
+        assert reader.read() == b"x" * 10
+        reader.close()
+
+        assert reader._thread is not None
+        assert not reader._thread.is_alive()
+
+    def test_reads_into_pooled_buffers(self, tmp_path):
+        """Test that inline reads reuse a single pooled buffer.
+
+        Args:
+            tmp_path: Pytest fixture for the source file.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "data.bin"
+        source.write_bytes(bytes(range(100)))
+        pool = BufferPool(10, capacity=2)
+
+        with ReadaheadReader(source, 95, 10, budget=0, pool=pool) as reader:
+            data = b""
+            while chunk := reader.read():
+                data += chunk
+
+        assert data == bytes(range(95))
+        stats = pool.stats()
+        assert stats["allocated"] == 1
+        assert stats["reused"] == 10
+        assert stats["in_use"] == 0
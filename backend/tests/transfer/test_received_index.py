@@ -0,0 +1,48 @@
+from tpi_redes.transfer.received_index import ReceivedIndex, find_received
+
+
+class TestReceivedIndex:
+    def test_find_by_checksum_and_name(self, tmp_path):
+        """Test lookups by checksum prefix and by case-insensitive name glob.
+
+        Args:
+            tmp_path: Pytest fixture for the index and files.
+
+        Returns:
+            None: No return value.
+        """
+        index = ReceivedIndex(tmp_path / "index.jsonl")
+        iso = tmp_path / "Debian.ISO"
+        iso.write_bytes(b"iso")
+        notes = tmp_path / "notes.txt"
+        notes.write_bytes(b"notes")
+        first = index.add(iso, "Debian.ISO", 3, "ab" * 32, "10.0.0.2:5000")
+        index.add(notes, "notes.txt", 5, "cd" * 32, "10.0.0.3:5000")
+
+        by_hash = find_received(index.path, "ABABABAB")
+        assert [m["transfer_id"] for m in by_hash] == [first["transfer_id"]]
+        assert by_hash[0]["path"] == str(iso.resolve())
+        assert by_hash[0]["exists"] is True
+        assert [m["filename"] for m in index.find("*.iso")] == ["Debian.ISO"]
+        assert len(index.find("*")) == 2
+        assert index.find("cdcd") == []
+
+    def test_reports_moved_files_and_skips_bad_lines(self, tmp_path):
+        """Test that missing files are flagged and corrupt lines ignored.
+
+        Args:
+            tmp_path: Pytest fixture for the index and files.
+
+        Returns:
+            None: No return value.
+        """
+        index = ReceivedIndex(tmp_path / "nested" / "index.jsonl")
+        gone = tmp_path / "gone.bin"
+        index.add(gone, "gone.bin", 1, "ef" * 32, "10.0.0.2:5000")
+        with open(index.path, "a", encoding="utf-8") as f:
+            f.write("{not json\n")
+
+        assert ReceivedIndex(tmp_path / "missing.jsonl").entries() == []
+        matches = index.find("gone.*")
+        assert len(matches) == 1
+        assert matches[0]["exists"] is False
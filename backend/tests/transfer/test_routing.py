@@ -0,0 +1,73 @@
+import json
+from pathlib import Path
+
+import pytest
+
+from tpi_redes.transfer.routing import OutputRouter, load_routes, parse_route
+
+
+class TestOutputRouter:
+    def test_first_matching_rule_wins(self, tmp_path):
+        """Test that rules are evaluated in order with a default fallback.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        router = OutputRouter(
+            [parse_route("10.0.0.5=anna"), parse_route("10.0.0.0/24=lab")], tmp_path
+        )
+
+        directory, rule = router.resolve("10.0.0.5")
+        assert directory == tmp_path / "anna"
+        assert rule is not None and rule.match == "10.0.0.5"
+
+        assert router.resolve("10.0.0.9")[0] == tmp_path / "lab"
+        assert router.resolve("192.168.1.2") == (tmp_path, None)
+
+    def test_absolute_directory_kept(self, tmp_path):
+        """Test that absolute rule directories are not nested in the default.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        target = tmp_path / "elsewhere"
+        router = OutputRouter([parse_route(f"::1={target}")], tmp_path / "default")
+
+        assert router.resolve("::1")[0] == target
+
+    def test_parse_route_invalid(self):
+        """Test that malformed rules are rejected.
+
+        Returns:
+            None: No return value.
+        """
+        with pytest.raises(ValueError):
+            parse_route("10.0.0.5")
+        with pytest.raises(ValueError):
+            parse_route("not-an-ip=dir")
+
+    def test_load_routes(self, tmp_path):
+        """Test loading rules from a JSON file.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        routes_file = tmp_path / "routes.json"
+        routes_file.write_text(json.dumps([{"match": "10.0.0.0/8", "dir": "lan"}]))
+
+        rules = load_routes(routes_file)
+
+        assert [(r.match, r.directory) for r in rules] == [("10.0.0.0/8", Path("lan"))]
+
+        routes_file.write_text(json.dumps({"match": "10.0.0.0/8"}))
+        with pytest.raises(ValueError):
+            load_routes(routes_file)
@@ -0,0 +1,58 @@
+from tpi_redes.transfer.sla import SlaMonitor
+
+
+class TestSlaMonitor:
+    def test_on_track(self, fake_clock):
+        """Test that a transfer projected to finish in time raises no warning.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SlaMonitor(10.0, 1000, clock=fake_clock)
+
+        fake_clock.now += 2.0
+        assert monitor.check(500) is None
+        assert not monitor.missed
+
+    def test_at_risk_then_overrun(self, fake_clock):
+        """Test that each warning is emitted once, at risk before overrun.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SlaMonitor(10.0, 1000, clock=fake_clock)
+
+        fake_clock.now += 4.0
+        warning = monitor.check(200)
+        assert warning is not None
+        assert warning["type"] == "SLA_WARNING"
+        assert warning["status"] == "at_risk"
+        assert warning["eta"] == 16.0
+        assert monitor.missed
+
+        fake_clock.now += 1.0
+        assert monitor.check(250) is None
+
+        fake_clock.now += 6.0
+        warning = monitor.check(600)
+        assert warning is not None and warning["status"] == "overrun"
+
+    def test_no_projection_before_sample(self, fake_clock):
+        """Test that a slow start is not projected before enough time passes.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SlaMonitor(10.0, 1000, clock=fake_clock)
+
+        fake_clock.now += 0.5
+        assert monitor.check(1) is None
@@ -0,0 +1,64 @@
+import os
+
+from tpi_redes.transfer.source_watch import SourceWatcher
+
+
+class TestSourceWatcher:
+    def test_detects_rewrite_after_interval(self, tmp_path):
+        """Test that a rewrite is reported once the check interval elapses.
+
+        Args:
+            tmp_path: Pytest fixture for the source file.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "data.bin"
+        source.write_bytes(b"original")
+        now = [0.0]
+        watcher = SourceWatcher(source, 8, interval=1.0, clock=lambda: now[0])
+
+        source.write_bytes(b"replaced")
+        stat = source.stat()
+        os.utime(source, ns=(stat.st_atime_ns, stat.st_mtime_ns + 10**9))
+
+        assert not watcher.changed()
+        now[0] = 1.5
+        assert watcher.changed()
+
+    def test_unchanged_and_removed(self, tmp_path):
+        """Test that an untouched file passes and a removed one does not.
+
+        Args:
+            tmp_path: Pytest fixture for the source file.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "data.bin"
+        source.write_bytes(b"original")
+        watcher = SourceWatcher(source, 8)
+
+        assert not watcher.changed(force=True)
+        source.unlink()
+        assert watcher.changed(force=True)
+
+    def test_prefix_only_allows_appends(self, tmp_path):
+        """Test that growing logs only count as changed when truncated.
+
+        Args:
+            tmp_path: Pytest fixture for the source file.
+
+        Returns:
+            None: No return value.
+        """
+        log = tmp_path / "app.log"
+        log.write_bytes(b"line 1\n")
+        watcher = SourceWatcher(log, 7, prefix_only=True)
+
+        with open(log, "ab") as f:
+            f.write(b"line 2\n")
+        assert not watcher.changed(force=True)
+
+        log.write_bytes(b"x")
+        assert watcher.changed(force=True)
@@ -0,0 +1,121 @@
+from typing import Any
+
+from tpi_redes.transfer.speed_alert import SpeedMonitor
+
+
+def _run(monitor: SpeedMonitor, clock: Any, rate: int, seconds: int, done: int):
+    """Advance the clock one second at a time at a steady speed.
+
+    Args:
+        monitor: The monitor under test.
+        clock: Its fake clock, advanced through `now`.
+        rate: Bytes sent per second.
+        seconds: How many seconds to advance.
+        done: Bytes sent before the first step.
+
+    Returns:
+        tuple[int, list]: Bytes sent afterwards and the alerts returned.
+    """
+    alerts = []
+    for _ in range(seconds):
+        clock.now += 1.0
+        done += rate
+        if alert := monitor.check(done):
+            alerts.append(alert)
+    return done, alerts
+
+
+class TestSpeedMonitor:
+    def test_slow_alert_after_hold(self, fake_clock):
+        """Test that a slow speed alerts once it lasts the hold time.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SpeedMonitor(1000, None, 2.0, clock=fake_clock)
+
+        done, alerts = _run(monitor, fake_clock, 100, 2, 0)
+        assert alerts == []
+        assert not monitor.slow
+
+        done, alerts = _run(monitor, fake_clock, 100, 3, done)
+        assert len(alerts) == 1
+        assert alerts[0]["type"] == "SLOW_TRANSFER"
+        assert alerts[0]["threshold"] == 1000
+        assert alerts[0]["duration"] == 2.0
+        assert monitor.slow
+        assert monitor.alerts == ["slow"]
+
+    def test_alert_rearms_after_recovery(self, fake_clock):
+        """Test that a new slow episode alerts again after the speed recovers.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SpeedMonitor(1000, None, 1.0, clock=fake_clock)
+
+        done, _ = _run(monitor, fake_clock, 100, 3, 0)
+        assert monitor.slow
+        done, alerts = _run(monitor, fake_clock, 10_000, 2, done)
+        assert alerts == []
+        assert not monitor.slow
+
+        done, alerts = _run(monitor, fake_clock, 0, 12, done)
+        assert [a["type"] for a in alerts] == ["SLOW_TRANSFER"]
+        assert monitor.alerts == ["slow", "slow"]
+
+    def test_saturated_alert(self, fake_clock):
+        """Test that holding the speed cap reports saturation, not slowness.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SpeedMonitor(100, 1000, 2.0, clock=fake_clock)
+
+        _, alerts = _run(monitor, fake_clock, 980, 4, 0)
+        assert [a["type"] for a in alerts] == ["SPEED_SATURATED"]
+        assert alerts[0]["threshold"] == 1000
+        assert not monitor.slow
+
+    def test_samples_are_rate_limited(self, fake_clock):
+        """Test that checks closer together than the sample period are ignored.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SpeedMonitor(1000, None, 0.0, clock=fake_clock)
+
+        fake_clock.now += 0.1
+        assert monitor.check(1) is None
+        assert monitor.rate is None
+
+    def test_skip_ignores_pause(self, fake_clock):
+        """Test that skipping after a pause does not report it as slow.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        monitor = SpeedMonitor(1000, None, 2.0, clock=fake_clock)
+        done, _ = _run(monitor, fake_clock, 5000, 3, 0)
+
+        fake_clock.now += 60.0
+        monitor.skip(done)
+        _, alerts = _run(monitor, fake_clock, 5000, 3, done)
+
+        assert alerts == []
+        assert not monitor.slow
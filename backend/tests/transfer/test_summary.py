@@ -0,0 +1,100 @@
+import json
+
+from tpi_redes.transfer.summary import SummaryRecorder
+
+
+class TestSummaryRecorder:
+    def test_phases_and_speeds(self, fake_clock):
+        """Test that phases are timed back to back and speeds are derived.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        recorder = SummaryRecorder(clock=fake_clock)
+
+        recorder.enter("connect")
+        fake_clock.now += 0.5
+        recorder.enter("data")
+        fake_clock.now += 1.0
+        recorder.sample(1000)
+        fake_clock.now += 1.0
+        recorder.sample(4000)
+        recorder.enter("verify")
+        fake_clock.now += 0.5
+        recorder.file("a.bin", 4000, "confirmed")
+
+        summary = recorder.finish("tcp", "10.0.0.2:9000", {"chunk_size": 4096})
+        assert summary.phases == {"connect": 0.5, "data": 2.0, "verify": 0.5}
+        assert summary.elapsed == 3.0
+        assert summary.total_bytes == 4000
+        assert summary.avg_speed == round(4000 / 3, 1)
+        assert summary.peak_speed == 3000.0
+        assert summary.loss is None
+
+    def test_short_batch_peak_is_average(self, fake_clock):
+        """Test that a batch too short to sample reports its average as peak.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        recorder = SummaryRecorder(clock=fake_clock)
+        recorder.enter("data")
+        fake_clock.now += 0.1
+        recorder.sample(500)
+        recorder.file("a.bin", 500, "sent")
+
+        summary = recorder.finish("udp", "10.0.0.2:9000", {}, loss=0.25)
+        assert summary.peak_speed == summary.avg_speed == 5000.0
+        assert summary.loss == 0.25
+
+    def test_rendering(self, fake_clock):
+        """Test that the summary serializes to JSON and renders as text.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        recorder = SummaryRecorder(clock=fake_clock)
+        recorder.enter("data")
+        fake_clock.now += 2.0
+        recorder.file("a.bin", 3_000_000, "sent")
+        recorder.file("b.bin", 1_000_000, "unconfirmed")
+
+        summary = recorder.finish("udp", "10.0.0.2:9000", {"fec_group": 8}, loss=0.1)
+        data = json.loads(json.dumps(summary.to_dict()))
+        assert data["files"][1]["result"] == "unconfirmed"
+
+        text = str(summary)
+        assert text.startswith("Sent 2 file(s), 4.0 MB to 10.0.0.2:9000 over UDP")
+        assert "avg 2.0 MB/s" in text
+        assert "loss: 10.0%" in text
+        assert "fec_group=8" in text
+        assert "b.bin: 1.0 MB, unconfirmed" in text
+
+    def test_warnings_render_apart_from_errors(self, fake_clock):
+        """Test that warnings get their own line in the summary.
+
+        Args:
+            fake_clock: Fixture providing the fake clock.
+
+        Returns:
+            None: No return value.
+        """
+        recorder = SummaryRecorder(clock=fake_clock)
+        counts = {"count": 2, "suppressed": 1, "first_ts": 1.0, "last_ts": 2.0}
+
+        summary = recorder.finish(
+            "tcp", "10.0.0.2:9000", {}, warnings={"SLA_WARNING/at_risk": counts}
+        )
+
+        text = str(summary)
+        assert "Errors" not in text
+        assert "  Warnings: SLA_WARNING/at_risk x2" in text
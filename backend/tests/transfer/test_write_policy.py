@@ -0,0 +1,84 @@
+from unittest.mock import patch
+
+import pytest
+
+from tpi_redes.transfer.write_policy import (
+    SyncedWriter,
+    parse_fsync_policy,
+    write_atomic,
+)
+
+
+class TestWritePolicy:
+    def test_parse_policies(self):
+        """Test that each policy form parses and invalid ones are rejected.
+
+        Returns:
+            None: No return value.
+        """
+        assert not parse_fsync_policy("never").syncs
+        assert parse_fsync_policy("on-complete").fsync_every_bytes == 0
+        assert parse_fsync_policy("every:2").fsync_every_bytes == 2 * 1024 * 1024
+
+        for spec in ("always", "every:", "every:0", "every:x"):
+            with pytest.raises(ValueError):
+                parse_fsync_policy(spec)
+
+    def test_syncs_every_interval_and_on_complete(self, tmp_path):
+        """Test that a periodic policy syncs per interval and at completion.
+
+        Args:
+            tmp_path: Pytest fixture for the destination file.
+
+        Returns:
+            None: No return value.
+        """
+        policy = parse_fsync_policy("every:1", buffer_bytes=4096)
+
+        with patch("tpi_redes.transfer.write_policy.os.fsync") as fsync:
+            with open(tmp_path / "out.bin", "wb") as f:
+                writer = SyncedWriter(f, policy)
+                for _ in range(3):
+                    writer.write(b"x" * (512 * 1024))
+                assert fsync.call_count == 1
+                writer.complete()
+
+        assert fsync.call_count == 2
+        assert (tmp_path / "out.bin").stat().st_size == 3 * 512 * 1024
+
+    def test_never_does_not_sync(self, tmp_path):
+        """Test that the default policy leaves flushing to the OS.
+
+        Args:
+            tmp_path: Pytest fixture for the destination file.
+
+        Returns:
+            None: No return value.
+        """
+        with patch("tpi_redes.transfer.write_policy.os.fsync") as fsync:
+            with open(tmp_path / "out.bin", "wb") as f:
+                writer = SyncedWriter(f, parse_fsync_policy("never"))
+                writer.write(b"data")
+                writer.complete()
+
+        fsync.assert_not_called()
+
+    def test_write_atomic_keeps_old_file_on_failure(self, tmp_path):
+        """Test that a failed atomic write leaves the old file and no temp file.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        path = tmp_path / "state" / "settings.json"
+        write_atomic(path, "old")
+        assert path.read_text(encoding="utf-8") == "old"
+
+        with patch("os.replace", side_effect=OSError("disk gone")):
+            with pytest.raises(OSError):
+                write_atomic(path, "new")
+
+        assert path.read_text(encoding="utf-8") == "old"
+        assert list(path.parent.iterdir()) == [path]
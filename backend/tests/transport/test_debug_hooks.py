@@ -0,0 +1,54 @@
+from unittest.mock import patch
+
+from tpi_redes.transport.debug_hooks import ChunkShaper, DebugHooks
+
+
+class TestChunkShaper:
+    def test_corrupts_and_delays_every_nth_chunk(self):
+        """Test that corruption and delays hit the same chunks every run.
+
+        Returns:
+            None: No return value.
+        """
+        pauses: list[float] = []
+        shaper = ChunkShaper(
+            DebugHooks(delay_per_chunk_ms=5, corrupt_every_nth_chunk=2),
+            sleep=pauses.append,
+        )
+
+        shaped = [shaper.shape(b"\x00abc") for _ in range(4)]
+
+        assert shaped == [b"\x00abc", b"\xffabc", b"\x00abc", b"\xffabc"]
+        assert pauses == [0.005] * 4
+
+    def test_drops_every_nth_packet(self):
+        """Test that drops follow the configured period.
+
+        Returns:
+            None: No return value.
+        """
+        shaper = ChunkShaper(DebugHooks(drop_every_nth_packet=3))
+
+        assert [shaper.should_drop() for _ in range(6)] == [
+            False,
+            False,
+            True,
+            False,
+            False,
+            True,
+        ]
+
+    def test_ignored_in_frozen_builds(self):
+        """Test that packaged binaries never honor the hooks.
+
+        Returns:
+            None: No return value.
+        """
+        with (
+            patch("tpi_redes.transport.debug_hooks.DEBUG_CORRUPT_EVERY_NTH_CHUNK", 2),
+            patch("tpi_redes.transport.debug_hooks.sys.frozen", True, create=True),
+        ):
+            assert not DebugHooks.from_config().active
+
+        with patch("tpi_redes.transport.debug_hooks.DEBUG_CORRUPT_EVERY_NTH_CHUNK", 2):
+            assert DebugHooks.from_config().corrupt_every_nth_chunk == 2
@@ -2,6 +2,7 @@ from unittest.mock import MagicMock, call, patch
 
 import pytest
 
+from tpi_redes.core.protocol import ProtocolHandler
 from tpi_redes.transport.tcp_client import TCPClient
 from tpi_redes.transport.udp_client import UDPClient
 
@@ -12,6 +13,15 @@ def mock_socket():
         mock_inst = MagicMock()
         mock_sock.return_value.__enter__.return_value = mock_inst
         mock_inst.getsockname.return_value = ("127.0.0.1", 12345)
+        mock_inst.recvfrom.side_effect = BlockingIOError
+        banner = [ProtocolHandler.pack_preamble("test")]
+
+        def recv(bufsize: int, flags: int = 0) -> bytes:
+            if banner:
+                return banner.pop()
+            raise BlockingIOError
+
+        mock_inst.recv.side_effect = recv
         yield mock_inst
 
 
@@ -0,0 +1,48 @@
+from unittest.mock import patch
+
+import pytest
+
+from tpi_redes.transport.pacing import Pacer
+
+
+class TestPacer:
+    def test_spaces_packets_at_target_rate(self):
+        """Test that consecutive packets are delayed to match the target rate.
+
+        At 1000 B/s, three back-to-back 100 B packets must be spread 0.1 s apart.
+
+        Returns:
+            None: No return value.
+        """
+        with patch("tpi_redes.transport.pacing.time") as mock_time:
+            mock_time.monotonic.return_value = 10.0
+            pacer = Pacer(rate=1000)
+
+            pacer.wait(100)
+            pacer.wait(100)
+            pacer.wait(100)
+
+            sleeps = [c.args[0] for c in mock_time.sleep.call_args_list]
+            assert sleeps == pytest.approx([0.1, 0.2])
+
+    def test_idle_time_does_not_accumulate_credit(self):
+        """Test that a long pause does not allow a burst afterwards.
+
+        Returns:
+            None: No return value.
+        """
+        with patch("tpi_redes.transport.pacing.time") as mock_time:
+            mock_time.monotonic.return_value = 0.0
+            pacer = Pacer(rate=1000)
+            pacer.wait(100)
+
+            mock_time.monotonic.return_value = 60.0
+            pacer.wait(100)
+            pacer.wait(100)
+
+            sleeps = [c.args[0] for c in mock_time.sleep.call_args_list]
+            assert sleeps == pytest.approx([0.1])
+
+    def test_rejects_non_positive_rate(self):
+        with pytest.raises(ValueError):
+            Pacer(rate=0)
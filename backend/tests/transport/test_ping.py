@@ -0,0 +1,124 @@
+import socket
+import threading
+
+import pytest
+
+from tpi_redes import __version__
+from tpi_redes.config import PROBE_MAX_BYTES
+from tpi_redes.transport.ping import ping_peer, probe_bandwidth
+from tpi_redes.transport.tcp_server import TCPServer
+
+
+class TestPingPeer:
+    def test_tcp_ping_reaches_server(self, tmp_path):
+        """Test a TCP probe against a receiver on the loopback interface.
+
+        Args:
+            tmp_path: Pytest fixture used as the server's save directory.
+
+        Returns:
+            None: No return value.
+        """
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as listener:
+            listener.bind(("127.0.0.1", 0))
+            listener.listen()
+            port = listener.getsockname()[1]
+
+            def serve_once():
+                conn, addr = listener.accept()
+                with conn:
+                    server.handle_client(conn, addr)
+
+            thread = threading.Thread(target=serve_once, daemon=True)
+            thread.start()
+
+            result = ping_peer(("127.0.0.1", port), "tcp", timeout=2.0)
+            thread.join(timeout=2.0)
+
+        assert result.reachable
+        assert result.version == __version__
+        assert result.rtt_ms is not None and result.rtt_ms >= 0
+        assert list(tmp_path.iterdir()) == []
+
+    def test_unreachable_peer(self):
+        """Test that a closed port is reported instead of raised.
+
+        Returns:
+            None: No return value.
+        """
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as s:
+            s.bind(("127.0.0.1", 0))
+            port = s.getsockname()[1]
+
+        result = ping_peer(("127.0.0.1", port), "tcp", timeout=0.5)
+
+        assert not result.reachable
+        assert result.error
+        assert result.to_dict()["protocol"] == "tcp"
+
+    def test_foreign_service_reply_is_diagnosed(self):
+        """Test that a non-receiver's reply is reported with its first bytes.
+
+        Returns:
+            None: No return value.
+        """
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as listener:
+            listener.bind(("127.0.0.1", 0))
+            listener.listen()
+            port = listener.getsockname()[1]
+
+            def serve_http():
+                conn, _ = listener.accept()
+                with conn:
+                    conn.recv(1024)
+                    conn.sendall(b"HTTP/1.1 400 Bad Request\r\n\r\n")
+
+            thread = threading.Thread(target=serve_http, daemon=True)
+            thread.start()
+
+            result = ping_peer(("127.0.0.1", port), "tcp", timeout=2.0)
+            thread.join(timeout=2.0)
+
+        assert not result.reachable
+        assert result.error is not None
+        assert "Not a tpi-redes receiver" in result.error
+        assert "HTTP/1.1 400" in result.error
+
+    def test_probe_measures_receiver(self, tmp_path):
+        """Test a bandwidth probe against a receiver, including an oversized one.
+
+        Args:
+            tmp_path: Pytest fixture used as the server's save directory.
+
+        Returns:
+            None: No return value.
+        """
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as listener:
+            listener.bind(("127.0.0.1", 0))
+            listener.listen()
+            port = listener.getsockname()[1]
+
+            def serve(times: int):
+                for _ in range(times):
+                    conn, addr = listener.accept()
+                    with conn:
+                        server.handle_client(conn, addr)
+
+            thread = threading.Thread(target=serve, args=(2,), daemon=True)
+            thread.start()
+
+            result = probe_bandwidth(("127.0.0.1", port), 64 * 1024, timeout=2.0)
+            with pytest.raises(ValueError, match="rejected the probe"):
+                probe_bandwidth(("127.0.0.1", port), PROBE_MAX_BYTES + 1, 2.0)
+            thread.join(timeout=5.0)
+
+        assert result.probe_bytes == 64 * 1024
+        assert result.throughput > 0
+        assert 4096 <= result.chunk_size <= 65536
+        assert result.socket_buffer >= 64 * 1024
+        assert result.eta(10 * 1024 * 1024) > 0
+        assert list(tmp_path.iterdir()) == []
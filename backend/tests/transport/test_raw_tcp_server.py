@@ -0,0 +1,52 @@
+import hashlib
+import json
+
+from tpi_redes.transport.raw_tcp_server import RawTCPServer
+
+
+class TestRawTCPServer:
+    def test_stores_stream_until_eof(self, tmp_path, capsys, stream_socket):
+        """Test that an unframed stream is stored whole with its checksum.
+
+        Args:
+            tmp_path: Pytest fixture for file saving.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        server = RawTCPServer(
+            host="127.0.0.1", port=0, save_dir=str(tmp_path), raw_name="dump.bin"
+        )
+        expected = b"netcat payload " * 1000
+
+        server.handle_client(stream_socket(expected), ("127.0.0.1", 40000))
+
+        digest = hashlib.sha256(expected).hexdigest()
+        assert (tmp_path / "dump.bin").read_bytes() == expected
+        assert (tmp_path / "dump.bin.sha256").read_text() == digest
+
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "complete"
+        assert event["sha256"] == digest
+
+    def test_does_not_overwrite_previous_stream(self, tmp_path, stream_socket):
+        """Test that a second connection gets a suffixed file name.
+
+        Args:
+            tmp_path: Pytest fixture for file saving.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        server = RawTCPServer(
+            host="127.0.0.1", port=0, save_dir=str(tmp_path), raw_name="dump.bin"
+        )
+        (tmp_path / "dump.bin").write_bytes(b"first")
+
+        server.handle_client(stream_socket(b"second"), ("127.0.0.1", 40001))
+
+        assert (tmp_path / "dump.bin").read_bytes() == b"first"
+        assert (tmp_path / "dump-1.bin").read_bytes() == b"second"
@@ -0,0 +1,77 @@
+import asyncio
+import socket
+import threading
+import time
+
+from tpi_redes.transport.receive_once import (
+    receive_single_file,
+    receive_single_file_async,
+)
+from tpi_redes.transport.tcp_client import TCPClient
+
+
+def _free_port() -> int:
+    """Return a loopback port that is currently unused.
+
+    Returns:
+        int: The port number.
+    """
+    with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as s:
+        s.bind(("127.0.0.1", 0))
+        return s.getsockname()[1]
+
+
+class TestReceiveSingleFile:
+    def test_tcp_returns_stored_path(self, tmp_path):
+        """Test that one TCP file is stored and described by the result.
+
+        Args:
+            tmp_path: Pytest fixture for source and output directories.
+
+        Returns:
+            None: No return value.
+        """
+        source = tmp_path / "report.txt"
+        source.write_bytes(b"one-shot")
+        output_dir = tmp_path / "out"
+        port = _free_port()
+
+        def send():
+            for _ in range(50):
+                try:
+                    TCPClient().send_files([source], "127.0.0.1", port)
+                    return
+                except ConnectionRefusedError:
+                    time.sleep(0.05)
+
+        sender = threading.Thread(target=send, daemon=True)
+        sender.start()
+        result = receive_single_file(
+            port, output_dir=output_dir, host="127.0.0.1", deadline=5.0
+        )
+        sender.join(timeout=2.0)
+
+        assert result.ok
+        assert result.status == "verified"
+        assert result.path == output_dir / "report.txt"
+        assert result.path.read_bytes() == b"one-shot"
+        assert result.size == 8
+
+    def test_deadline_without_sender(self, tmp_path):
+        """Test that the awaitable form gives up at the deadline.
+
+        Args:
+            tmp_path: Pytest fixture used as the output directory.
+
+        Returns:
+            None: No return value.
+        """
+        result = asyncio.run(
+            receive_single_file_async(
+                _free_port(), output_dir=tmp_path, host="127.0.0.1", deadline=0.2
+            )
+        )
+
+        assert not result.ok
+        assert result.status == "timeout"
+        assert result.path is None
@@ -1,15 +1,26 @@
-from tpi_redes.core.protocol import ProtocolHandler
+import pytest
+
+from tpi_redes.core.errors import (
+    ChecksumMismatchError,
+    NotAFileTransferPeerError,
+    SourceModifiedError,
+    TransferAbortedError,
+)
+from tpi_redes.core.protocol import AbortReason, ProtocolHandler
 from tpi_redes.transport.tcp_client import TCPClient
 
+BANNER = ProtocolHandler.pack_preamble("test")
+
 
 class TestTCPClient:
-    def test_send_files(self, tmp_path):
+    def test_send_files(self, tmp_path, stream_socket):
         """Test sending multiple files via TCP.
 
         Verifies packetization (header, metadata, content) and concatenation.
 
         Args:
             tmp_path: Pytest fixture for source files.
+            stream_socket: Fixture providing the fake receiver connection.
 
         Returns:
             None: No return value.
@@ -22,78 +33,597 @@ class TestTCPClient:
         content2 = b"Content 2"
         file2.write_bytes(content2)
 
-        sent_data = b""
-
-        class MockSocket:
-            def connect(self, addr: tuple[str, int]):
-                pass
-
-            def sendall(self, data: bytes):
-                nonlocal sent_data
-                sent_data += data
-
-            def close(self):
-                pass
-
-            def getsockname(self):
-                return ("127.0.0.1", 12345)
-
-            def __enter__(self):
-                return self
-
-            def __exit__(self, exc_type, exc_val, exc_tb):
-                pass
-
         import socket
 
+        sock = stream_socket(BANNER, hang_up=False)
         original_socket = socket.socket
-
-        def mock_socket_ctor(*_args, **_kwargs):
-            return MockSocket()
-
-        socket.socket = mock_socket_ctor
+        socket.socket = lambda *_args, **_kwargs: sock
 
         try:
             client = TCPClient()
             client.send_files([file1, file2], "127.0.0.1", 8080)
 
-            offset = 0
+            preamble = ProtocolHandler.unpack_preamble(sock.sent[:16])
+            assert preamble.protocol_version == ProtocolHandler.PROTOCOL_VERSION
+            offset = 16
+
+            # --- Batch preview ---
+            preview = ProtocolHandler.unpack_header(sock.sent[offset : offset + 16])
+            assert preview.op_code == ProtocolHandler.BATCH_OP_CODE
+            offset += 16
+            listing = sock.sent[offset : offset + preview.file_size]
+            assert listing == b"9 file1.txt\n9 file2.txt\n"
+            offset += preview.file_size
 
             # --- File 1 ---
-            header1_data = sent_data[offset : offset + 16]
+            mime = ProtocolHandler.pack_content_type("text/plain")
+            assert sock.sent[offset : offset + len(mime)] == mime
+            offset += len(mime)
+
+            header1_data = sock.sent[offset : offset + 16]
             header1 = ProtocolHandler.unpack_header(header1_data)
             assert header1.op_code == b"F"
             assert header1.file_size == len(content1)
             offset += 16
 
-            fname1 = sent_data[offset : offset + header1.name_len]
+            fname1 = sock.sent[offset : offset + header1.name_len]
             assert fname1.decode() == "file1.txt"
             offset += header1.name_len
 
             offset += header1.hash_len
 
-            c1 = sent_data[offset : offset + len(content1)]
-            assert c1 == content1
-            offset += len(content1)
+            c1 = ProtocolHandler.pack_chunk(0, content1)
+            assert sock.sent[offset : offset + len(c1)] == c1
+            offset += len(c1)
 
             # --- File 2 ---
-            header2_data = sent_data[offset : offset + 16]
+            mime = ProtocolHandler.pack_content_type("text/plain")
+            assert sock.sent[offset : offset + len(mime)] == mime
+            offset += len(mime)
+
+            header2_data = sock.sent[offset : offset + 16]
             header2 = ProtocolHandler.unpack_header(header2_data)
             assert header2.op_code == b"F"
             assert header2.file_size == len(content2)
             offset += 16
 
-            fname2 = sent_data[offset : offset + header2.name_len]
+            fname2 = sock.sent[offset : offset + header2.name_len]
             assert fname2.decode() == "file2.txt"
             offset += header2.name_len
 
             offset += header2.hash_len
 
-            c2 = sent_data[offset : offset + len(content2)]
-            assert c2 == content2
-            offset += len(content2)
+            c2 = ProtocolHandler.pack_chunk(0, content2)
+            assert sock.sent[offset : offset + len(c2)] == c2
+            offset += len(c2)
+
+            assert offset == len(sock.sent)
+
+        finally:
+            socket.socket = original_socket
+
+    def test_small_files_are_bundled(self, tmp_path, stream_socket):
+        """Test that small files go out in one bundle and large ones alone.
 
-            assert offset == len(sent_data)
+        Args:
+            tmp_path: Pytest fixture for source files.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import socket
+
+        files = [tmp_path / "a.txt", tmp_path / "b.txt", tmp_path / "big.bin"]
+        files[0].write_bytes(b"first")
+        files[1].write_bytes(b"second")
+        files[2].write_bytes(b"x" * 1000)
+
+        sock = stream_socket(BANNER, hang_up=False)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+        try:
+            summary = TCPClient().send_files(
+                files, "127.0.0.1", 8080, bundle_threshold=100
+            )
+        finally:
+            socket.socket = original_socket
+
+        # Skip the preamble and the batch preview.
+        offset = 16
+        preview = ProtocolHandler.unpack_header(sock.sent[offset : offset + 16])
+        offset += 16 + preview.file_size
+        bundle = ProtocolHandler.unpack_header(sock.sent[offset : offset + 16])
+        assert bundle.op_code == ProtocolHandler.BUNDLE_OP_CODE
+        payload_start = offset + 16 + bundle.hash_len
+        payload = sock.sent[payload_start : payload_start + bundle.file_size]
+        assert b"a.txt" in payload and b"second" in payload
+        assert b"big.bin" not in payload
+        assert b"big.bin" in sock.sent[payload_start + bundle.file_size :]
+        assert [f["filename"] for f in summary.files] == ["a.txt", "b.txt", "big.bin"]
+        assert summary.settings["bundle_threshold"] == 100
+
+    def test_trailing_hash_follows_content(self, tmp_path, stream_socket):
+        """Test that the checksum is sent in a trailer after the content.
 
+        Args:
+            tmp_path: Pytest fixture for source files.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import socket
+
+        source = tmp_path / "huge.bin"
+        source.write_bytes(b"y" * 5000)
+
+        sock = stream_socket(BANNER, hang_up=False)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+        try:
+            summary = TCPClient().send_files(
+                [source], "127.0.0.1", 8080, chunk_size=1024, trailing_hash=True
+            )
         finally:
             socket.socket = original_socket
+
+        digest = hashlib.sha256(b"y" * 5000).hexdigest()
+        trailer = ProtocolHandler.pack_trailer(5000, digest)
+        last_chunk = ProtocolHandler.pack_chunk(4096, b"y" * 904)
+        assert sock.sent.endswith(last_chunk + trailer)
+        header_start = sock.sent.index(b"huge.bin") - 16
+        header = ProtocolHandler.unpack_header(
+            sock.sent[header_start : header_start + 16]
+        )
+        assert header.op_code == ProtocolHandler.TRAILED_FILE_OP_CODE
+        assert header.hash_len == 0
+        assert summary.settings["trailing_hash"] is True
+
+    def test_device_is_hashed_while_sending(self, stream_socket):
+        """Test that a capped device is read once, with a trailing checksum.
+
+        Args:
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import socket
+        from pathlib import Path
+
+        sock = stream_socket(BANNER, hang_up=False)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+        try:
+            TCPClient().send_files(
+                [Path("/dev/zero")], "127.0.0.1", 8080, max_read_bytes=100
+            )
+        finally:
+            socket.socket = original_socket
+
+        digest = hashlib.sha256(bytes(100)).hexdigest()
+        content = ProtocolHandler.pack_chunk(0, bytes(100))
+        assert sock.sent.endswith(content + ProtocolHandler.pack_trailer(100, digest))
+        header_start = sock.sent.index(b"zero") - 16
+        header = ProtocolHandler.unpack_header(
+            sock.sent[header_start : header_start + 16]
+        )
+        assert header.op_code == ProtocolHandler.TRAILED_FILE_OP_CODE
+        assert header.file_size == 100
+
+    def test_older_receiver_disables_features(self, tmp_path, capsys, stream_socket):
+        """Test that a protocol 1 receiver gets no batch preview or MIME type.
+
+        Args:
+            tmp_path: Pytest fixture for source files.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import json
+        import socket
+
+        files = [tmp_path / "a.txt", tmp_path / "b.txt"]
+        for f in files:
+            f.write_bytes(b"data")
+
+        sock = stream_socket(ProtocolHandler.pack_preamble("0.0.9", 1), hang_up=False)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+        try:
+            summary = TCPClient().send_files(files, "127.0.0.1", 8080)
+        finally:
+            socket.socket = original_socket
+
+        first = ProtocolHandler.unpack_header(sock.sent[16:32])
+        assert first.op_code == ProtocolHandler.FILE_OP_CODE
+        assert summary.peer_version == "0.0.9"
+        assert summary.disabled_features == [
+            "batch_preview",
+            "content_type",
+            "bundle",
+            "trailing_hash",
+            "framed_content",
+        ]
+        warning = json.loads(capsys.readouterr().out.splitlines()[0])
+        assert warning["type"] == "PEER_COMPATIBILITY"
+        assert warning["peer_version"] == "0.0.9"
+
+    def test_receiver_abort_stops_sending(self, tmp_path, stream_socket):
+        """Test that an abort message from the receiver interrupts the send.
+
+        Args:
+            tmp_path: Pytest fixture for source files.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        file1 = tmp_path / "big.bin"
+        file1.write_bytes(b"x" * 10000)
+
+        import socket
+
+        abort_frame = ProtocolHandler.pack_abort(AbortReason.DISK_FULL, "No space")
+        sock = stream_socket(BANNER + abort_frame)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+
+        try:
+            with pytest.raises(TransferAbortedError) as exc_info:
+                TCPClient().send_files([file1], "127.0.0.1", 8080, chunk_size=1000)
+        finally:
+            socket.socket = original_socket
+
+        assert exc_info.value.reason == AbortReason.DISK_FULL
+        assert exc_info.value.initiator == "peer"
+        # preamble + header + metadata + first chunk only
+        assert len(sock.sends) == 4
+        # The short drain timeout must not outlive reading the abort.
+        assert sock.timeouts[-1] is None
+
+    def test_cancel_mid_file_notifies_receiver(self, tmp_path, stream_socket):
+        """Test that Ctrl+C in the middle of a file still sends the abort.
+
+        Args:
+            tmp_path: Pytest fixture for source files.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import socket
+
+        source = tmp_path / "big.bin"
+        source.write_bytes(b"x" * 100)
+
+        class InterruptedSocket(stream_socket):
+            def sendall(self, data: bytes):
+                super().sendall(data)
+                if data == ProtocolHandler.pack_chunk(0, b"x" * 10):
+                    raise KeyboardInterrupt
+
+        sock = InterruptedSocket(BANNER, hang_up=False)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+
+        try:
+            with pytest.raises(KeyboardInterrupt):
+                TCPClient().send_files([source], "127.0.0.1", 8080, chunk_size=10)
+        finally:
+            socket.socket = original_socket
+
+        assert sock.sends[-1] == ProtocolHandler.pack_abort(
+            AbortReason.CANCELLED, "Cancelled by user"
+        )
+
+    def test_foreign_service_fails_fast(self, tmp_path, capsys, stream_socket):
+        """Test that a reply from another service ends the send with a preview.
+
+        Args:
+            tmp_path: Pytest fixture for source files.
+            capsys: Pytest fixture to capture the aborted event.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        file1 = tmp_path / "big.bin"
+        file1.write_bytes(b"x" * 10000)
+
+        import socket
+
+        reply = b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n"
+        sock = stream_socket(reply)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+
+        try:
+            with pytest.raises(NotAFileTransferPeerError) as exc_info:
+                TCPClient().send_files([file1], "127.0.0.1", 80, chunk_size=1000)
+        finally:
+            socket.socket = original_socket
+
+        assert exc_info.value.preview.startswith(b"HTTP/1.1 400 Bad Request")
+        assert exc_info.value.reason == AbortReason.PROTOCOL_ERROR
+        # only the preamble: the reply to it gives the service away
+        assert len(sock.sends) == 1
+        assert "not a tpi-redes receiver" in capsys.readouterr().out
+
+    def test_confirm_waits_for_each_ack(self, tmp_path, capsys, stream_socket):
+        """Test that confirmed sends report the receiver's verdict per file.
+
+        The receiver acknowledges the first file with the right checksum and
+        the second with a wrong one, which fails the batch.
+
+        Args:
+            tmp_path: Pytest fixture for source files.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import json
+
+        good = tmp_path / "good.txt"
+        good.write_bytes(b"good")
+        bad = tmp_path / "bad.txt"
+        bad.write_bytes(b"bad")
+
+        class AckingSocket(stream_socket):
+            def sendall(self, data: bytes):
+                super().sendall(data)
+                if data == ProtocolHandler.pack_chunk(0, b"good"):
+                    digest = hashlib.sha256(b"good").hexdigest()
+                    self.incoming += ProtocolHandler.pack_ack(4, digest)
+                elif data == ProtocolHandler.pack_chunk(0, b"bad"):
+                    self.incoming += ProtocolHandler.pack_ack(3, "0" * 64)
+
+            def recv(self, bufsize: int, flags: int = 0) -> bytes:
+                if flags & socket.MSG_PEEK:
+                    raise AssertionError("polled for abort after the last chunk")
+                return super().recv(bufsize, flags)
+
+        import socket
+
+        sock = AckingSocket(BANNER)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+
+        try:
+            with pytest.raises(ChecksumMismatchError) as exc_info:
+                TCPClient().send_files([good, bad], "127.0.0.1", 8080, confirm=True)
+        finally:
+            socket.socket = original_socket
+
+        assert exc_info.value.filenames == ["bad.txt"]
+        preview = ProtocolHandler.unpack_header(sock.sent[16:32])
+        assert preview.op_code == ProtocolHandler.BATCH_OP_CODE
+        mime = ProtocolHandler.pack_content_type("text/plain")
+        offset = 32 + preview.file_size + len(mime)
+        header = ProtocolHandler.unpack_header(sock.sent[offset : offset + 16])
+        assert header.op_code == ProtocolHandler.CONFIRMED_FILE_OP_CODE
+
+        events = [
+            event
+            for line in capsys.readouterr().out.splitlines()
+            for event in json.loads(line)
+        ]
+        verdicts = [
+            (e["filename"], e["receiver_verified"])
+            for e in events
+            if e["status"] == "complete"
+        ]
+        assert verdicts == [("good.txt", True), ("bad.txt", False)]
+
+    def test_send_stream(self, stream_socket):
+        """Test that streams are sent as offset-framed chunks and a trailer.
+
+        Args:
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import io
+        import socket
+
+        sock = stream_socket(BANNER, hang_up=False)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+
+        try:
+            TCPClient().send_stream(
+                io.BytesIO(b"0123456789"), "digits.txt", "127.0.0.1", 8080, 4
+            )
+        finally:
+            socket.socket = original_socket
+
+        header = ProtocolHandler.unpack_header(sock.sent[16:32])
+        assert header.op_code == ProtocolHandler.STREAM_OP_CODE
+        offset = 32 + header.name_len
+
+        offsets = []
+        while sock.sent[offset : offset + 1] == ProtocolHandler.CHUNK_OP_CODE:
+            chunk = ProtocolHandler.unpack_chunk(
+                sock.sent[offset : offset + ProtocolHandler.CHUNK_HEADER_SIZE]
+            )
+            offsets.append(chunk.offset)
+            offset += ProtocolHandler.CHUNK_HEADER_SIZE + chunk.length
+        assert offsets == [0, 4, 8]
+
+        trailer = ProtocolHandler.unpack_header(sock.sent[offset : offset + 16])
+        assert trailer.op_code == ProtocolHandler.TRAILER_OP_CODE
+        assert trailer.file_size == 10
+        digest = sock.sent[offset + 16 :].decode()
+        assert digest == hashlib.sha256(b"0123456789").hexdigest()
+
+    def test_source_modified_restarts(self, tmp_path, capsys, stream_socket):
+        """Test that a file rewritten mid-send is aborted and sent again.
+
+        Without restarts the send fails with `SourceModifiedError`; with one
+        restart the file is resent on a new connection.
+
+        Args:
+            tmp_path: Pytest fixture for source files.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake receiver connection.
+
+        Returns:
+            None: No return value.
+        """
+        import json
+        import os
+
+        source = tmp_path / "report.csv"
+        source.write_bytes(b"a" * 100)
+        connections = []
+        rewrites = 0
+
+        class RewritingSocket(stream_socket):
+            def sendall(self, data: bytes):
+                nonlocal rewrites
+                super().sendall(data)
+                if data == ProtocolHandler.pack_chunk(0, b"a" * 10) and not rewrites:
+                    rewrites += 1
+                    source.write_bytes(b"b" * 100)
+                    stat = source.stat()
+                    os.utime(source, ns=(stat.st_atime_ns, stat.st_mtime_ns + 10**9))
+
+        def connect(*_args, **_kwargs):
+            connections.append(RewritingSocket(BANNER, hang_up=False))
+            return connections[-1]
+
+        import socket
+
+        original_socket = socket.socket
+        socket.socket = connect
+
+        try:
+            with pytest.raises(SourceModifiedError) as exc_info:
+                TCPClient().send_files([source], "127.0.0.1", 8080, chunk_size=10)
+            assert exc_info.value.reason == AbortReason.SOURCE_MODIFIED
+
+            source.write_bytes(b"a" * 100)
+            rewrites = 0
+            connections.clear()
+            TCPClient().send_files(
+                [source], "127.0.0.1", 8080, chunk_size=10, restarts=1
+            )
+        finally:
+            socket.socket = original_socket
+
+        assert len(connections) == 2
+        assert connections[1].sent.endswith(ProtocolHandler.pack_chunk(90, b"b" * 10))
+        events = [
+            event
+            for line in capsys.readouterr().out.splitlines()
+            for event in json.loads(line)
+        ]
+        statuses = [e["status"] for e in events if e["status"] != "progress"]
+        assert statuses[-4:] == ["aborted", "restarting", "start", "complete"]
+        environment = events[-1]["environment"]
+        assert environment["retries"] == 1
+        assert environment["protocol"] == "tcp"
+        assert environment["peer_version"] == "test"
+        assert environment["settings"]["chunk_size"] == 10
+
+    def test_restart_resends_batch_to_atomic_receiver(self, tmp_path):
+        """Test that a restart resends files an atomic receiver rolled back.
+
+        The receiver stages the connection's files and discards them all when
+        the second file's source changes, so the first one must be sent again.
+
+        Args:
+            tmp_path: Pytest fixture for source and received files.
+
+        Returns:
+            None: No return value.
+        """
+        import functools
+        import os
+        import socket
+        import threading
+        from unittest.mock import patch
+
+        from tpi_redes.transfer.source_watch import SourceWatcher
+        from tpi_redes.transport.tcp_server import TCPServer
+
+        first, second = tmp_path / "a.txt", tmp_path / "b.txt"
+        first.write_bytes(b"a" * 30)
+        second.write_bytes(b"b" * 30)
+        received = tmp_path / "received"
+        received.mkdir()
+        server = TCPServer(
+            host="127.0.0.1", port=0, save_dir=str(received), atomic=True
+        )
+        # Created up front: socketpair() builds its sockets via socket.socket.
+        pairs = [socket.socketpair() for _ in range(2)]
+        threads: list[threading.Thread] = []
+        rewritten = False
+
+        def serve(conn: socket.socket, port: int):
+            with conn:
+                server.handle_client(conn, ("127.0.0.1", port))
+
+        class PairedSocket:
+            """Client end of a socket pair served by the real receiver."""
+
+            def __init__(self):
+                self.sock, server_end = pairs[len(threads)]
+                thread = threading.Thread(
+                    target=serve, args=(server_end, 40000 + len(threads))
+                )
+                thread.start()
+                threads.append(thread)
+
+            def connect(self, addr: tuple[str, int]):
+                pass
+
+            def getsockname(self):
+                return ("127.0.0.1", 12345)
+
+            def sendall(self, data: bytes):
+                nonlocal rewritten
+                self.sock.sendall(data)
+                if data == ProtocolHandler.pack_chunk(0, b"b" * 10) and not rewritten:
+                    rewritten = True
+                    second.write_bytes(b"c" * 30)
+                    stat = second.stat()
+                    os.utime(second, ns=(stat.st_atime_ns, stat.st_mtime_ns + 10**9))
+
+            def __getattr__(self, name: str):
+                return getattr(self.sock, name)
+
+            def __enter__(self):
+                return self
+
+            def __exit__(self, exc_type, exc_val, exc_tb):
+                self.sock.close()
+
+        # Check the source after every chunk so the change is seen mid-file.
+        watcher = functools.partial(SourceWatcher, interval=0)
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: PairedSocket()
+        try:
+            with patch("tpi_redes.transport.tcp_client.SourceWatcher", watcher):
+                summary = TCPClient().send_files(
+                    [first, second], "127.0.0.1", 8080, chunk_size=10, restarts=1
+                )
+        finally:
+            socket.socket = original_socket
+        for thread in threads:
+            thread.join(5)
+
+        assert len(threads) == 2
+        assert (received / "a.txt").read_bytes() == b"a" * 30
+        assert (received / "b.txt").read_bytes() == b"c" * 30
+        assert [f["filename"] for f in summary.files] == ["a.txt", "b.txt"]
@@ -1,3 +1,5 @@
+import json
+
 from tpi_redes.transport.tcp_server import TCPServer
 
 
@@ -8,13 +10,101 @@ class TestTCPServer:
         assert server.port == 9999
         assert server.save_dir == "/tmp"
 
-    def test_handle_client_receive_multiple_files(self, tmp_path):
+    def test_capacity(self, tmp_path):
+        """Test the receiver state advertised through discovery.
+
+        Args:
+            tmp_path: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        capacity = server.capacity()
+
+        assert capacity["free_bytes"] > 0
+        assert capacity["auth_required"] is False
+        assert capacity["active_transfers"] == 0
+        assert capacity["audit"] is False
+
+    def test_start_stops_after_max_files(self, tmp_path):
+        """Test that a server asked for N files returns once they are verified.
+
+        Args:
+            tmp_path: Pytest fixture for source and output directories.
+
+        Returns:
+            None: No return value.
+        """
+        import socket
+        import threading
+        import time
+
+        from tpi_redes.transport.tcp_client import TCPClient
+
+        sources = []
+        for name in ("a.txt", "b.txt"):
+            sources.append(tmp_path / name)
+            sources[-1].write_bytes(name.encode())
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as probe:
+            probe.bind(("127.0.0.1", 0))
+            port = probe.getsockname()[1]
+
+        server = TCPServer("127.0.0.1", port, str(tmp_path / "out"))
+        server.max_files = 2
+
+        def send():
+            for source in sources:
+                for _ in range(50):
+                    try:
+                        TCPClient().send_files([source], "127.0.0.1", port)
+                        break
+                    except ConnectionRefusedError:
+                        time.sleep(0.05)
+
+        sender = threading.Thread(target=send, daemon=True)
+        sender.start()
+        runner = threading.Thread(target=server.start, daemon=True)
+        runner.start()
+        runner.join(timeout=5.0)
+        sender.join(timeout=1.0)
+
+        assert not runner.is_alive()
+        assert server.files_completed == 2
+        assert [o["filename"] for o in server.outcomes] == ["a.txt", "b.txt"]
+
+    def test_start_stops_when_window_closes(self, tmp_path):
+        """Test that a server with a receive window returns once it is over.
+
+        Args:
+            tmp_path: Pytest fixture for the output directory.
+
+        Returns:
+            None: No return value.
+        """
+        import threading
+        import time
+
+        server = TCPServer("127.0.0.1", 0, str(tmp_path))
+        server.window_ends = time.monotonic() + 0.3
+
+        runner = threading.Thread(target=server.start, daemon=True)
+        runner.start()
+        runner.join(timeout=5.0)
+
+        assert not runner.is_alive()
+        assert server.window_remaining() <= 0
+        assert server.outcomes == []
+
+    def test_handle_client_receive_multiple_files(self, tmp_path, stream_socket):
         """Test handling multiple files in a single stream.
 
         Verifies that the server can parse and save multiple concatenated files.
 
         Args:
             tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
 
         Returns:
             None: No return value.
@@ -23,27 +113,6 @@ class TestTCPServer:
         save_dir.mkdir()
         server = TCPServer(host="127.0.0.1", port=0, save_dir=str(save_dir))
 
-        class MockSocket:
-            def __init__(self, data_stream: bytes):
-                self.data_stream = data_stream
-                self.closed = False
-
-            def recv(self, bufsize: int) -> bytes:
-                if not self.data_stream:
-                    return b""
-                chunk = self.data_stream[:bufsize]
-                self.data_stream = self.data_stream[bufsize:]
-                return chunk
-
-            def close(self):
-                self.closed = True
-
-            def settimeout(self, t):
-                pass
-
-            def getsockname(self):
-                return ("127.0.0.1", 12345)
-
         from tpi_redes.core.protocol import ProtocolHandler
 
         # File 1
@@ -62,7 +131,7 @@ class TestTCPServer:
 
         stream_data = payload1 + payload2
 
-        mock_sock = MockSocket(stream_data)
+        mock_sock = stream_socket(stream_data)
 
         server.handle_client(mock_sock, ("127.0.0.1", 12345))
 
@@ -73,3 +142,1098 @@ class TestTCPServer:
         saved_f2 = save_dir / f2_name
         assert saved_f2.exists()
         assert saved_f2.read_bytes() == f2_content
+
+    def test_handle_client_acknowledges_confirmed_files(
+        self, tmp_path, stream_socket, decode_replies
+    ):
+        """Test that files sent with the confirm OpCode are acknowledged in turn.
+
+        Args:
+            tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        stream = b""
+        for name, content in [("a.txt", b"first"), ("b.txt", b"second")]:
+            digest = hashlib.sha256(content).hexdigest()
+            stream += (
+                ProtocolHandler.pack_header(b"C", name, len(content), digest)
+                + name.encode()
+                + digest.encode()
+                + content
+            )
+        sock = stream_socket(stream)
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        assert (tmp_path / "b.txt").read_bytes() == b"second"
+        acks = decode_replies(sock.sent)
+        assert [ack.bytes_received for ack in acks] == [5, 6]
+        assert acks[0].sha256 == hashlib.sha256(b"first").hexdigest()
+
+    def test_handle_client_stream(self, tmp_path, capsys, stream_socket):
+        """Test that a stream of unknown size is verified against its trailer.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        content = b"streamed from stdin"
+        stream = (
+            ProtocolHandler.pack_stream_start("log.txt")
+            + ProtocolHandler.pack_chunk(0, content[:8])
+            + ProtocolHandler.pack_chunk(8, content[8:])
+            + ProtocolHandler.pack_trailer(
+                len(content), hashlib.sha256(content).hexdigest()
+            )
+        )
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        assert (tmp_path / "log.txt").read_bytes() == content
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        assert events[0]["total"] is None
+        assert events[-1]["status"] == "complete"
+        assert events[-1]["verified"] is True
+        assert events[-1]["total"] == len(content)
+
+    def test_handle_client_stream_offset_gap(
+        self, tmp_path, capsys, stream_socket, decode_replies
+    ):
+        """Test that a chunk at the wrong offset aborts the stream.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        stream = (
+            ProtocolHandler.pack_stream_start("gap.bin")
+            + ProtocolHandler.pack_chunk(0, b"abc")
+            + ProtocolHandler.pack_chunk(10, b"def")
+        )
+
+        sock = stream_socket(stream)
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        assert not (tmp_path / "gap.bin").exists()
+        [abort] = decode_replies(sock.sent)
+        assert abort.reason == "protocol_error"
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["reason"] == "protocol_error"
+
+    def test_handle_client_dedup_links_existing(self, tmp_path, capsys, stream_socket):
+        """Test that content already received is hard-linked, not rewritten.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        content = b"shared dataset"
+        digest = hashlib.sha256(content).hexdigest()
+        (tmp_path / "lab").mkdir()
+        (tmp_path / "lab" / "original.csv").write_bytes(content)
+        (tmp_path / "lab" / "original.csv.sha256").write_text(digest)
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path), dedup=True)
+
+        stream = (
+            ProtocolHandler.pack_header(b"F", "copy.csv", len(content), digest)
+            + b"copy.csv"
+            + digest.encode()
+            + content
+        )
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        copy = tmp_path / "copy.csv"
+        assert copy.read_bytes() == content
+        assert copy.stat().st_ino == (tmp_path / "lab" / "original.csv").stat().st_ino
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["verified"] is True
+        assert event["deduplicated_from"] == str(tmp_path / "lab" / "original.csv")
+
+    def test_handle_client_atomic_dedup(self, tmp_path, capsys, stream_socket):
+        """Test that files committed by an atomic batch dedup later batches.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        content = b"shared dataset"
+        digest = hashlib.sha256(content).hexdigest()
+        server = TCPServer(
+            host="127.0.0.1", port=0, save_dir=str(tmp_path), atomic=True, dedup=True
+        )
+
+        for name in ("first.csv", "second.csv"):
+            stream = (
+                ProtocolHandler.pack_header(b"F", name, len(content), digest)
+                + name.encode()
+                + digest.encode()
+                + content
+            )
+            server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        first, second = tmp_path / "first.csv", tmp_path / "second.csv"
+        assert second.read_bytes() == content
+        assert second.stat().st_ino == first.stat().st_ino
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        completed = [e for e in events if e.get("status") == "complete"]
+        assert completed[-1]["deduplicated_from"] == str(first)
+
+    def test_handle_client_per_peer_dirs(self, tmp_path, stream_socket):
+        """Test that files are grouped by sender IP when per_peer_dirs is set.
+
+        Args:
+            tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        server = TCPServer(
+            host="127.0.0.1", port=0, save_dir=str(tmp_path), per_peer_dirs=True
+        )
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        name, content, file_hash = "peer.txt", b"from peer", "hash"
+        stream = (
+            ProtocolHandler.pack_header(b"F", name, len(content), file_hash)
+            + name.encode()
+            + file_hash.encode()
+            + content
+        )
+
+        server.handle_client(stream_socket(stream), ("10.0.0.5", 40000))
+
+        assert (tmp_path / "10.0.0.5" / name).read_bytes() == content
+
+    def test_handle_client_routes(self, tmp_path, capsys, stream_socket):
+        """Test that routing rules pick the directory and are reported.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.transfer.routing import parse_route
+
+        server = TCPServer(
+            host="127.0.0.1",
+            port=0,
+            save_dir=str(tmp_path),
+            routes=[parse_route("10.0.0.0/24=lab")],
+        )
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        name, content, file_hash = "routed.txt", b"routed", "hash"
+        stream = (
+            ProtocolHandler.pack_header(b"F", name, len(content), file_hash)
+            + name.encode()
+            + file_hash.encode()
+            + content
+        )
+
+        server.handle_client(stream_socket(stream), ("10.0.0.5", 40000))
+
+        assert (tmp_path / "lab" / name).read_bytes() == content
+        start = json.loads(capsys.readouterr().out.splitlines()[0])
+        assert start["status"] == "start"
+        assert start["route"] == "10.0.0.0/24"
+
+    def test_handle_client_sender_abort(self, tmp_path, capsys, stream_socket):
+        """Test that an abort message from the sender ends the session cleanly.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        from tpi_redes.core.protocol import AbortReason, ProtocolHandler
+
+        stream = ProtocolHandler.pack_abort(AbortReason.CANCELLED, "bye")
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "aborted"
+        assert event["reason"] == "cancelled"
+        assert event["initiator"] == "peer"
+        assert list(tmp_path.iterdir()) == []
+
+    def test_handle_client_sender_abort_mid_file(
+        self, tmp_path, capsys, stream_socket, decode_replies
+    ):
+        """Test that a sender cancelling mid-file is recorded as cancelled.
+
+        From protocol 5 the content arrives in chunk frames, so the sender's
+        abort takes the place of the next chunk instead of the connection
+        just dropping.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import AbortReason, ProtocolHandler
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        done, cut = b"first", b"second file"
+        done_hash = hashlib.sha256(done).hexdigest()
+        cut_hash = hashlib.sha256(cut).hexdigest()
+        stream = (
+            ProtocolHandler.pack_preamble("test")
+            + ProtocolHandler.pack_header(b"F", "a.txt", len(done), done_hash)
+            + b"a.txt"
+            + done_hash.encode()
+            + ProtocolHandler.pack_chunk(0, done)
+            + ProtocolHandler.pack_header(b"F", "b.txt", len(cut), cut_hash)
+            + b"b.txt"
+            + cut_hash.encode()
+            + ProtocolHandler.pack_chunk(0, cut[:6])
+            + ProtocolHandler.pack_abort(AbortReason.CANCELLED, "Cancelled by user")
+        )
+        sock = stream_socket(stream)
+
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        assert (tmp_path / "a.txt").read_bytes() == done
+        assert not (tmp_path / "b.txt").exists()
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "aborted"
+        assert event["filename"] == "b.txt"
+        assert event["reason"] == "cancelled"
+        assert event["initiator"] == "peer"
+        assert event["message"] == "Cancelled by user"
+        assert server.outcomes[-1]["result"] == "aborted:cancelled"
+        # Only the preamble answer: nothing to abort back to the sender.
+        assert len(decode_replies(sock.sent)) == 1
+
+    def test_handle_client_validates_content(self, tmp_path, capsys, stream_socket):
+        """Test that a verified but malformed file is flagged by its validator.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        server.validators = ("json",)
+        content = b'{"rows": [1, 2'
+        digest = hashlib.sha256(content).hexdigest()
+        header = ProtocolHandler.pack_header(b"F", "rows.json", len(content), digest)
+        stream = header + b"rows.json" + digest.encode() + content
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["verified"] is True
+        assert event["validation"][0]["validator"] == "json"
+        assert event["validation"][0]["ok"] is False
+        assert server.outcomes[0]["validation"] == event["validation"]
+        assert (tmp_path / "rows.json").read_bytes() == content
+
+    def test_handle_client_indexes_verified_files(self, tmp_path, stream_socket):
+        """Test that verified files are added to the received index.
+
+        Args:
+            tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+        from tpi_redes.transfer.received_index import ReceivedIndex
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        server.received_index = ReceivedIndex(tmp_path / "index.jsonl")
+        content = b"indexed content"
+        digest = hashlib.sha256(content).hexdigest()
+        header = ProtocolHandler.pack_header(b"F", "a.txt", len(content), digest)
+        stream = header + b"a.txt" + digest.encode() + content
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        matches = server.received_index.find(digest)
+        assert len(matches) == 1
+        assert matches[0]["path"] == str((tmp_path / "a.txt").resolve())
+        assert matches[0]["peer"] == "127.0.0.1:40000"
+        assert matches[0]["exists"] is True
+
+    def test_handle_client_content_type(self, tmp_path, capsys, stream_socket):
+        """Test that the announced content type is reported and used.
+
+        The sender says the file is a zip, so the zip validator runs on it
+        even though its name has no extension.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import json
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        server.validators = ("zip",)
+        content = b"not really a zip"
+        digest = hashlib.sha256(content).hexdigest()
+        header = ProtocolHandler.pack_header(b"F", "archive", len(content), digest)
+        stream = (
+            ProtocolHandler.pack_content_type("application/zip")
+            + header
+            + b"archive"
+            + digest.encode()
+            + content
+        )
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        assert events[0]["status"] == "start"
+        assert events[0]["mime"] == "application/zip"
+        assert events[-1]["mime"] == "application/zip"
+        assert events[-1]["validation"][0]["validator"] == "zip"
+        assert events[-1]["validation"][0]["ok"] is False
+
+    def test_handle_client_bundle(self, tmp_path, capsys, stream_socket):
+        """Test that bundled files are stored like files sent one by one.
+
+        A file sent normally after the bundle is read from the socket again.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import json
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        def message(name: str, content: bytes) -> bytes:
+            digest = hashlib.sha256(content).hexdigest()
+            header = ProtocolHandler.pack_header(b"F", name, len(content), digest)
+            return header + name.encode() + digest.encode() + content
+
+        payload = message("a.txt", b"first") + message("b.txt", b"second")
+        stream = (
+            ProtocolHandler.pack_bundle(payload)
+            + payload
+            + message("c.txt", b"third")
+        )
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        assert (tmp_path / "a.txt").read_bytes() == b"first"
+        assert (tmp_path / "b.txt").read_bytes() == b"second"
+        assert (tmp_path / "c.txt").read_bytes() == b"third"
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        completed = [e for e in events if e["status"] == "complete"]
+        assert [e["verified"] for e in completed] == [True, True, True]
+
+    def test_handle_client_trailing_hash(
+        self, tmp_path, capsys, stream_socket, decode_replies
+    ):
+        """Test that a b'H' file is verified against its trailer.
+
+        A file whose trailer does not follow its content is discarded.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import json
+
+        from tpi_redes.core.protocol import ProtocolHandler
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        PacketLogger.reset_errors()
+
+        def message(name: str, content: bytes, trailer: bytes) -> bytes:
+            header = ProtocolHandler.pack_header(b"H", name, len(content), "")
+            return header + name.encode() + content + trailer
+
+        digest = hashlib.sha256(b"first").hexdigest()
+        trailer = ProtocolHandler.pack_trailer(5, digest)
+        stream = message("a.txt", b"first", trailer) + message(
+            "b.txt", b"second", ProtocolHandler.pack_ping()
+        )
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        sock = stream_socket(stream)
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        assert (tmp_path / "a.txt").read_bytes() == b"first"
+        assert (tmp_path / "a.txt.sha256").read_text() == digest
+        assert not (tmp_path / "b.txt").exists()
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        assert [e["verified"] for e in events if e["status"] == "complete"] == [True]
+        assert events[-1]["reason"] == "protocol_error"
+        assert [r.reason for r in decode_replies(sock.sent)] == ["protocol_error"]
+
+    def test_handle_client_rejects_corrupted_bundle(
+        self, tmp_path, capsys, stream_socket, decode_replies
+    ):
+        """Test that a bundle not matching its checksum stores nothing.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import json
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        digest = hashlib.sha256(b"first").hexdigest()
+        payload = (
+            ProtocolHandler.pack_header(b"F", "a.txt", 5, digest)
+            + b"a.txt"
+            + digest.encode()
+            + b"first"
+        )
+        stream = ProtocolHandler.pack_bundle(payload) + payload[:-1] + b"X"
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        sock = stream_socket(stream)
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        assert not (tmp_path / "a.txt").exists()
+        assert [r.reason for r in decode_replies(sock.sent)] == ["protocol_error"]
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["reason"] == "protocol_error"
+        assert event["message"] == "Bundle checksum mismatch"
+
+    def test_interrupt_cancels_sender(
+        self, tmp_path, capsys, stream_socket, decode_replies
+    ):
+        """Test that Ctrl+C mid-file tells the sender the transfer was cancelled.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        import json
+
+        import pytest
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        content = b"x" * 10000
+        digest = hashlib.sha256(content).hexdigest()
+        header = ProtocolHandler.pack_header(b"F", "a.bin", len(content), digest)
+        stream = header + b"a.bin" + digest.encode() + content[:5000]
+
+        class InterruptedSocket(stream_socket):
+            def recv(self, bufsize: int, flags: int = 0) -> bytes:
+                # Ctrl+C halfway through; the drain after the abort is timed.
+                if not self.incoming and not self.timeouts:
+                    raise KeyboardInterrupt
+                return super().recv(bufsize, flags)
+
+        sock = InterruptedSocket(stream)
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        with pytest.raises(KeyboardInterrupt):
+            server.handle_client(sock, ("127.0.0.1", 40000))
+
+        [abort] = decode_replies(sock.sent)
+        assert abort.reason == "cancelled"
+        assert not (tmp_path / "a.bin").exists()
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["reason"] == "cancelled"
+        assert event["filename"] == "a.bin"
+        assert event["initiator"] == "local"
+
+    def test_slow_disk_warning(self, tmp_path, capsys):
+        """Test that a file stored slower than it arrived raises a warning.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+
+        Returns:
+            None: No return value.
+        """
+        import json
+
+        from tpi_redes.transfer.io_meter import IoMeter
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        meter = IoMeter()
+        size = 8 * 1024 * 1024
+        meter.network_bytes, meter.network_seconds = size, 0.1
+        meter.disk_bytes, meter.disk_seconds = size, 0.8
+
+        server._check_disk("slow.bin", size, meter)
+        meter.disk_seconds = 0.05
+        server._check_disk("fast.bin", size, meter)
+        server._check_disk("small.bin", 1024, meter)
+
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        assert len(events) == 1
+        assert events[0]["type"] == "TRANSFER_WARNING"
+        assert events[0]["code"] == "slow_disk"
+        assert events[0]["disk_rate"] == 10485760
+
+    def test_max_rate_paces_reads(self, tmp_path, stream_socket):
+        """Test that content reads are paced to the ingress limit.
+
+        At 4096 B/s, three 4096-byte chunks are read a second apart.
+
+        Args:
+            tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        from unittest.mock import patch
+
+        import pytest
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        content = b"x" * 3 * 4096
+        digest = hashlib.sha256(content).hexdigest()
+        header = ProtocolHandler.pack_header(b"F", "a.bin", len(content), digest)
+        stream = header + b"a.bin" + digest.encode() + content
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        server.max_rate = 4096
+        with patch("tpi_redes.transport.pacing.time") as mock_time:
+            mock_time.monotonic.return_value = 10.0
+            server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        sleeps = [c.args[0] for c in mock_time.sleep.call_args_list]
+        assert sleeps == pytest.approx([1.0, 2.0])
+        assert (tmp_path / "a.bin").read_bytes() == content
+
+    def test_handle_client_mirrors_verified_files(self, tmp_path, stream_socket):
+        """Test that verified files are copied to the mirror directory.
+
+        Args:
+            tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+        from tpi_redes.services.mirror import Mirror
+
+        save_dir = tmp_path / "received"
+        save_dir.mkdir()
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(save_dir))
+        server.mirror = Mirror(tmp_path / "mirror")
+        content = b"mirrored content"
+        digest = hashlib.sha256(content).hexdigest()
+        header = ProtocolHandler.pack_header(b"F", "a.txt", len(content), digest)
+        stream = header + b"a.txt" + digest.encode() + content
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        assert server.mirror.close()["mirrored"] == 1
+        assert (tmp_path / "mirror" / "a.txt").read_bytes() == content
+
+    def test_handle_client_preamble(
+        self, tmp_path, capsys, stream_socket, decode_replies
+    ):
+        """Test that preambles are answered with the negotiated version.
+
+        Older senders get their own version back and a compatibility
+        warning, newer ones get ours, and versions below the minimum are
+        rejected.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        from tpi_redes.observability.packet_logger import PacketLogger
+
+        # Earlier tests' aborts would rate-limit the one checked below.
+        PacketLogger.reset_errors()
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        current = ProtocolHandler.PROTOCOL_VERSION
+        answers = {}
+
+        for version in (current, 1, current + 1, 0):
+            sock = stream_socket(ProtocolHandler.pack_preamble("test", version))
+            server.handle_client(sock, ("127.0.0.1", 40000))
+            [answers[version]] = decode_replies(sock.sent)
+
+        assert {v: a.protocol_version for v, a in answers.items() if v} == {
+            current: current,
+            1: 1,
+            current + 1: current,
+        }
+        assert answers[0].reason == "protocol_error"
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        warnings = [e for e in events if e["type"] == "PEER_COMPATIBILITY"]
+        assert len(warnings) == 1
+        assert warnings[0]["protocol_version"] == 1
+        assert warnings[0]["disabled"] == [
+            "batch_preview",
+            "content_type",
+            "bundle",
+            "trailing_hash",
+            "framed_content",
+        ]
+        assert events[-1]["reason"] == "protocol_error"
+        assert "Unsupported protocol version 0" in events[-1]["message"]
+
+    def test_handle_client_rejects_foreign_client(
+        self, tmp_path, capsys, stream_socket
+    ):
+        """Test that a connection opening with foreign bytes is closed unread.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        stream = b"GET / HTTP/1.1\r\nHost: receiver\r\n\r\n"
+
+        sock = stream_socket(stream)
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        assert sock.incoming == b"Host: receiver\r\n\r\n"
+        assert capsys.readouterr().out == ""
+        assert list(tmp_path.iterdir()) == []
+        assert server.peer_stats.snapshot()["127.0.0.1"]["handshakes_failed"] == 1
+
+    def test_handle_client_truncated_stream(self, tmp_path, stream_socket):
+        """Test that a connection dropped mid-file does not leave a partial file.
+
+        Args:
+            tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        name = "partial.bin"
+        stream = (
+            ProtocolHandler.pack_header(b"F", name, 100, "h")
+            + name.encode()
+            + b"h"
+            + b"x" * 40
+        )
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        assert not (tmp_path / name).exists()
+        assert not (tmp_path / f"{name}.sha256").exists()
+
+    def test_handle_client_audit_mode(self, tmp_path, capsys, stream_socket):
+        """Test that audit mode verifies content without writing to disk.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        save_dir = tmp_path / "out"
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(save_dir), audit=True)
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        name, content = "audit.txt", b"audited content"
+        file_hash = hashlib.sha256(content).hexdigest()
+        stream = (
+            ProtocolHandler.pack_header(b"F", name, len(content), file_hash)
+            + name.encode()
+            + file_hash.encode()
+            + content
+        )
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "complete"
+        assert event["verified"] is True
+        assert event["audit"] is True
+        assert event["path"] == str(save_dir / name)
+        assert not save_dir.exists()
+
+    def test_handle_client_manifest(self, tmp_path, capsys, stream_socket):
+        """Test that files are checked against a manifest sent ahead of them.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+        from tpi_redes.transfer.manifest import build_manifest
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        name, content = "data.csv", b"a,b\n1,2\n"
+        file_hash = hashlib.sha256(content).hexdigest()
+        manifest = build_manifest({name: file_hash}).encode()
+        manifest_hash = hashlib.sha256(manifest).hexdigest()
+        stream = (
+            ProtocolHandler.pack_header(
+                ProtocolHandler.MANIFEST_OP_CODE,
+                "SHA256SUMS",
+                len(manifest),
+                manifest_hash,
+            )
+            + b"SHA256SUMS"
+            + manifest_hash.encode()
+            + manifest
+            + ProtocolHandler.pack_header(b"F", name, len(content), file_hash)
+            + name.encode()
+            + file_hash.encode()
+            + content
+        )
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        assert (tmp_path / "SHA256SUMS").read_bytes() == manifest
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "complete"
+        assert event["manifest_verified"] is True
+
+    def test_handle_client_batch_preview(self, tmp_path, capsys, stream_socket):
+        """Test that an announced batch is reported and tracked as a whole.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+        from tpi_redes.transfer.manifest import build_batch_preview
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        files = {"a.txt": b"alpha", "b.txt": b"bravo!"}
+        listing = build_batch_preview({n: len(c) for n, c in files.items()})
+        stream = ProtocolHandler.pack_batch_preview(listing.encode())
+        for name, content in files.items():
+            file_hash = hashlib.sha256(content).hexdigest()
+            stream += (
+                ProtocolHandler.pack_header(b"F", name, len(content), file_hash)
+                + name.encode()
+                + file_hash.encode()
+                + content
+            )
+
+        server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        events = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        announced = events[0]
+        assert announced["type"] == "BATCH_UPDATE"
+        assert announced["status"] == "announced"
+        assert announced["files"] == ["a.txt", "b.txt"]
+        assert (announced["count"], announced["total_bytes"]) == (2, 11)
+        progress = [
+            (e["batch_current"], e["batch_total"])
+            for e in events
+            if e.get("status") == "progress"
+        ]
+        assert progress == [(5, 11), (11, 11)]
+        assert (tmp_path / "b.txt").read_bytes() == b"bravo!"
+
+    def test_handle_client_batch_too_large(
+        self, tmp_path, capsys, monkeypatch, stream_socket, decode_replies
+    ):
+        """Test that a batch that cannot fit is refused before any data.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            monkeypatch: Pytest fixture to fake the free space.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        import shutil
+        from collections import namedtuple
+
+        from tpi_redes.core.protocol import AbortReason, ProtocolHandler
+
+        usage = namedtuple("usage", "total used free")
+        monkeypatch.setattr(shutil, "disk_usage", lambda _p: usage(100, 90, 10))
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        stream = ProtocolHandler.pack_batch_preview(b"8 a.txt\n8 b.txt\n")
+
+        sock = stream_socket(stream)
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        [abort] = decode_replies(sock.sent)
+        assert abort.reason == AbortReason.DISK_FULL
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["reason"] == AbortReason.DISK_FULL
+        assert list(tmp_path.iterdir()) == []
+
+    def test_handle_client_internal_error(
+        self, tmp_path, capsys, monkeypatch, stream_socket, decode_replies
+    ):
+        """Test that an unexpected error is reported to the sender and the UI.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            monkeypatch: Pytest fixture to inject the failure.
+            stream_socket: Fixture providing the fake sender connection.
+            decode_replies: Fixture parsing the receiver's replies.
+
+        Returns:
+            None: No return value.
+        """
+        from tpi_redes.core.protocol import AbortReason, ProtocolHandler
+
+        server = TCPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        def fail(*_args):
+            raise RuntimeError("boom")
+
+        monkeypatch.setattr(server, "_receive_content", fail)
+        name, content = "a.txt", b"alpha"
+        stream = (
+            ProtocolHandler.pack_header(b"F", name, len(content), "h" * 64)
+            + name.encode()
+            + b"h" * 64
+            + content
+        )
+
+        sock = stream_socket(stream)
+        server.handle_client(sock, ("127.0.0.1", 40000))
+
+        [abort] = decode_replies(sock.sent)
+        assert abort.reason == AbortReason.INTERNAL_ERROR
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "aborted"
+        assert event["reason"] == AbortReason.INTERNAL_ERROR
+        assert event["filename"] == name
+        assert event["message"] == "RuntimeError: boom"
+        assert server.outcomes[-1]["result"] == "aborted:internal_error"
+
+    def test_handle_client_atomic_commit_and_rollback(
+        self, tmp_path, capsys, stream_socket
+    ):
+        """Test that atomic batches appear only when every file arrives intact.
+
+        Args:
+            tmp_path: Pytest fixture.
+            capsys: Pytest fixture capturing stdout events.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+        from unittest.mock import MagicMock
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        server = TCPServer(
+            host="127.0.0.1", port=0, save_dir=str(tmp_path), atomic=True
+        )
+        server.received_index = MagicMock()
+
+        def frame(name: str, content: bytes, file_hash: str | None = None) -> bytes:
+            file_hash = file_hash or hashlib.sha256(content).hexdigest()
+            return (
+                ProtocolHandler.pack_header(b"F", name, len(content), file_hash)
+                + name.encode()
+                + file_hash.encode()
+                + content
+            )
+
+        def serve(stream: bytes):
+            server.handle_client(stream_socket(stream), ("127.0.0.1", 40000))
+
+        serve(frame("one.txt", b"1") + frame("two.txt", b"2", "0" * 64))
+        assert list(tmp_path.iterdir()) == []
+        server.received_index.add.assert_not_called()
+        assert server.files_completed == 0
+        assert [o["result"] for o in server.outcomes] == ["rolled_back", "rolled_back"]
+
+        serve(frame("one.txt", b"1") + frame("two.txt", b"2"))
+        assert (tmp_path / "one.txt").read_bytes() == b"1"
+        assert (tmp_path / "two.txt").read_bytes() == b"2"
+        assert not any(p.name.startswith(".tpi-staging-") for p in tmp_path.iterdir())
+        indexed = [c.args[0] for c in server.received_index.add.call_args_list]
+        assert indexed == [tmp_path / "one.txt", tmp_path / "two.txt"]
+
+        batches = [
+            json.loads(line)
+            for line in capsys.readouterr().out.splitlines()
+            if "BATCH_UPDATE" in line
+        ]
+        assert [b["status"] for b in batches] == ["rolled_back", "committed"]
+        assert batches[1]["files"] == ["one.txt", "two.txt"]
+
+    def test_handle_client_records_audit_log(self, tmp_path, stream_socket):
+        """Test that received files are journaled when an audit log is set.
+
+        Args:
+            tmp_path: Pytest fixture.
+            stream_socket: Fixture providing the fake sender connection.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+        from tpi_redes.observability.audit_log import AuditLog
+
+        journal = AuditLog(tmp_path / "audit.log")
+        server = TCPServer(
+            host="127.0.0.1",
+            port=0,
+            save_dir=str(tmp_path / "out"),
+            audit_log=journal,
+        )
+
+        name, content = "lab.csv", b"x,y\n"
+        file_hash = hashlib.sha256(content).hexdigest()
+        stream = (
+            ProtocolHandler.pack_header(b"F", name, len(content), file_hash)
+            + name.encode()
+            + file_hash.encode()
+            + content
+        )
+
+        server.handle_client(stream_socket(stream), ("10.0.0.9", 40000))
+
+        entry = json.loads((tmp_path / "audit.log").read_text())
+        assert entry["direction"] == "received"
+        assert entry["peer"] == "10.0.0.9:40000"
+        assert entry["sha256"] == file_hash
+        assert entry["result"] == "verified"
+        assert journal.verify().valid
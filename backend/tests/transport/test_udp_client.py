@@ -1,15 +1,18 @@
+import json
+
 from tpi_redes.core.protocol import ProtocolHandler
 from tpi_redes.transport.udp_client import UDPClient
 
 
 class TestUDPClient:
-    def test_send_file_udp(self, tmp_path):
+    def test_send_file_udp(self, tmp_path, datagram_socket):
         """Test UDP file sending.
 
         Verifies that header, metadata, and content packets are sent correctly.
 
         Args:
             tmp_path: Pytest fixture for source file.
+            datagram_socket: Fixture providing the fake UDP socket.
 
         Returns:
             None: No return value.
@@ -20,32 +23,12 @@ class TestUDPClient:
 
         file_path.write_bytes(content)
 
-        sent_packets = []
-
-        class MockSocket:
-            def sendto(self, data, addr):
-                sent_packets.append((data, addr))
-
-            def close(self):
-                pass
-
-            def getsockname(self):
-                return ("127.0.0.1", 12345)
-
-            def __enter__(self):
-                return self
-
-            def __exit__(self, exc_type, exc_val, exc_tb, **kwargs):
-                pass
-
         import socket
 
+        sock = datagram_socket()
+        sent_packets = sock.sent
         original_socket = socket.socket
-
-        def mock_socket_ctor(*_args, **_kwargs):
-            return MockSocket()
-
-        socket.socket = mock_socket_ctor
+        socket.socket = lambda *_args, **_kwargs: sock
 
         try:
             client = UDPClient()
@@ -80,3 +63,88 @@ class TestUDPClient:
 
         finally:
             socket.socket = original_socket
+
+    def test_send_file_reports_delivered_bytes(self, tmp_path, capsys, datagram_socket):
+        """Test that receiver status reports feed the completion event.
+
+        Args:
+            tmp_path: Pytest fixture for source file.
+            capsys: Pytest fixture capturing stdout events.
+            datagram_socket: Fixture providing the fake UDP socket.
+
+        Returns:
+            None: No return value.
+        """
+        file_path = tmp_path / "delivered.txt"
+        content = b"Delivered content"
+        file_path.write_bytes(content)
+
+        import socket
+
+        sock = datagram_socket([ProtocolHandler.pack_status(len(content), 1)])
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+
+        try:
+            UDPClient().send_files([file_path], "127.0.0.1", 9999)
+        finally:
+            socket.socket = original_socket
+
+        events: list[dict] = []
+        for line in capsys.readouterr().out.splitlines():
+            parsed = json.loads(line)
+            events.extend(parsed if isinstance(parsed, list) else [parsed])
+        complete = next(e for e in events if e.get("status") == "complete")
+        assert complete["delivered"] == len(content)
+        assert complete["environment"]["remote"] == "127.0.0.1:9999"
+        assert complete["environment"]["retries"] == 0
+
+    def test_fec_parity_is_paced(self, tmp_path, capsys, datagram_socket):
+        """Test that the parity closing a file's last group is paced and counted.
+
+        Args:
+            tmp_path: Pytest fixture for source file.
+            capsys: Pytest fixture capturing stdout events.
+            datagram_socket: Fixture providing the fake UDP socket.
+
+        Returns:
+            None: No return value.
+        """
+        from unittest.mock import patch
+
+        file_path = tmp_path / "fec.bin"
+        content = b"x" * 30
+        file_path.write_bytes(content)
+        paced: list[int] = []
+
+        class RecordingPacer:
+            def __init__(self, rate: int):
+                pass
+
+            def wait(self, nbytes: int):
+                paced.append(nbytes)
+
+        import socket
+
+        sock = datagram_socket([ProtocolHandler.pack_status(len(content), 3)])
+        original_socket = socket.socket
+        socket.socket = lambda *_args, **_kwargs: sock
+        try:
+            with patch("tpi_redes.transport.udp_client.Pacer", RecordingPacer):
+                UDPClient().send_files(
+                    [file_path], "127.0.0.1", 9999, chunk_size=10, fec_group=4
+                )
+        finally:
+            socket.socket = original_socket
+
+        events: list[dict] = []
+        for line in capsys.readouterr().out.splitlines():
+            parsed = json.loads(line)
+            events.extend(parsed if isinstance(parsed, list) else [parsed])
+        complete = next(e for e in events if e.get("status") == "complete")
+        # Three data frames and the parity that closes their group.
+        framed = [d for d, _ in sock.sent if d[:1] in (b"G", b"X")]
+        assert len(framed) == 4
+        assert len(paced) == 4
+        assert complete["fec"]["datagrams_sent"] == 4
+        assert complete["fec"]["parity_sent"] == 1
@@ -44,3 +44,201 @@ class TestUDPServer:
         saved_file = save_dir / filename
         assert saved_file.exists()
         assert saved_file.read_bytes() == content
+
+    def test_reports_progress_to_sender(self, tmp_path, datagram_socket):
+        """Test that completing a file sends a status report back to the sender.
+
+        Args:
+            tmp_path: Pytest fixture for file saving.
+            datagram_socket: Fixture providing the fake UDP socket.
+
+        Returns:
+            None: No return value.
+        """
+        server = UDPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+
+        server.sock = datagram_socket()  # type: ignore[assignment]
+        replies = server.sock.sent
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        filename = "status.txt"
+        content = b"status content"
+        addr = ("127.0.0.1", 55556)
+
+        server.process_datagram(
+            ProtocolHandler.pack_header(b"F", filename, len(content), "h"), addr
+        )
+        server.process_datagram(filename.encode() + b"h", addr)
+        server.process_datagram(content, addr)
+
+        assert len(replies) == 1
+        data, reply_addr = replies[0]
+        assert reply_addr == addr
+        status = ProtocolHandler.unpack_status(data)
+        assert status.bytes_written == len(content)
+        assert status.datagrams_received == 1
+
+    def test_fec_repairs_lost_datagram(self, tmp_path, datagram_socket):
+        """Test that a lost content datagram is rebuilt from the group's parity.
+
+        Args:
+            tmp_path: Pytest fixture for file saving.
+            datagram_socket: Fixture providing the fake UDP socket.
+
+        Returns:
+            None: No return value.
+        """
+        import hashlib
+
+        from tpi_redes.core.protocol import ProtocolHandler
+        from tpi_redes.transfer.fec import FecEncoder
+
+        server = UDPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        server.sock = datagram_socket()  # type: ignore[assignment]
+
+        content = bytes(range(256)) * 10
+        digest = hashlib.sha256(content).hexdigest()
+        addr = ("127.0.0.1", 55557)
+        encoder = FecEncoder(4)
+        datagrams: list[bytes] = []
+        for offset in range(0, len(content), 300):
+            datagrams += encoder.encode(offset, content[offset : offset + 300])
+        datagrams += encoder.flush()
+
+        server.process_datagram(
+            ProtocolHandler.pack_header(
+                ProtocolHandler.FEC_FILE_OP_CODE, "fec.bin", len(content), digest
+            ),
+            addr,
+        )
+        server.process_datagram(b"fec.bin" + digest.encode(), addr)
+        # Lose the second data datagram of the first group.
+        for datagram in datagrams[:1] + datagrams[2:]:
+            server.process_datagram(datagram, addr)
+
+        assert (tmp_path / "fec.bin").read_bytes() == content
+        assert server.outcomes[0]["result"] == "verified"
+        status = ProtocolHandler.unpack_status(server.sock.sent[-1][0])
+        assert status.bytes_written == len(content)
+        assert status.repaired == 1
+
+    def test_audit_mode_writes_nothing(self, tmp_path):
+        """Test that audit mode completes a session without creating files.
+
+        Args:
+            tmp_path: Pytest fixture for file saving.
+
+        Returns:
+            None: No return value.
+        """
+        save_dir = tmp_path / "out"
+        server = UDPServer(host="127.0.0.1", port=0, save_dir=str(save_dir), audit=True)
+
+        from tpi_redes.core.protocol import ProtocolHandler
+
+        filename, content, file_hash = "audit.txt", b"payload", "hash"
+        addr = ("127.0.0.1", 55555)
+
+        server.process_datagram(
+            ProtocolHandler.pack_header(b"F", filename, len(content), file_hash),
+            addr,
+        )
+        server.process_datagram(filename.encode() + file_hash.encode(), addr)
+        server.process_datagram(content, addr)
+
+        assert addr not in server.sessions
+        assert not save_dir.exists()
+
+    def test_reaps_stalled_session(self, tmp_path, capsys):
+        """Test that a session whose sender went silent is aborted as stalled.
+
+        Args:
+            tmp_path: Pytest fixture for file saving.
+            capsys: Pytest fixture capturing stdout events.
+
+        Returns:
+            None: No return value.
+        """
+        import json
+
+        from tpi_redes.core.protocol import AbortReason, ProtocolHandler
+
+        server = UDPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        server.session_timeout = 10.0
+        filename, file_hash = "partial.bin", "hash"
+        addr = ("127.0.0.1", 55555)
+
+        server.process_datagram(
+            ProtocolHandler.pack_header(b"F", filename, 100, file_hash), addr
+        )
+        server.process_datagram(filename.encode() + file_hash.encode(), addr)
+        server.process_datagram(b"x" * 40, addr)
+        last_seen = server.sessions[addr].last_seen
+
+        assert server.reap_stale_sessions(last_seen + 5.0) == 0
+        assert server.active_transfers == 1
+
+        assert server.reap_stale_sessions(last_seen + 10.0) == 1
+        assert addr not in server.sessions
+        assert server.active_transfers == 0
+        assert not (tmp_path / filename).exists()
+        assert server.outcomes[-1]["result"] == f"aborted:{AbortReason.STALLED}"
+        assert server.outcomes[-1]["size"] == 40
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["status"] == "aborted"
+        assert event["reason"] == AbortReason.STALLED
+
+    def test_internal_error_aborts_session(self, tmp_path, capsys, monkeypatch):
+        """Test that an unexpected error ends the session as an aborted transfer.
+
+        Args:
+            tmp_path: Pytest fixture for file saving.
+            capsys: Pytest fixture capturing stdout events.
+            monkeypatch: Pytest fixture to inject the failure.
+
+        Returns:
+            None: No return value.
+        """
+        import json
+
+        from tpi_redes.core.protocol import AbortReason, ProtocolHandler
+
+        server = UDPServer(host="127.0.0.1", port=0, save_dir=str(tmp_path))
+        filename, file_hash = "broken.bin", "hash"
+        addr = ("127.0.0.1", 55555)
+
+        def fail(*_args):
+            raise RuntimeError("boom")
+
+        monkeypatch.setattr(server, "_send_status", fail)
+        server.process_datagram(
+            ProtocolHandler.pack_header(b"F", filename, 4, file_hash), addr
+        )
+        server.process_datagram(filename.encode() + file_hash.encode(), addr)
+        server.process_datagram(b"data", addr)
+
+        assert addr not in server.sessions
+        assert not (tmp_path / filename).exists()
+        event = json.loads(capsys.readouterr().out.splitlines()[-1])
+        assert event["reason"] == AbortReason.INTERNAL_ERROR
+        assert event["message"] == "RuntimeError: boom"
+
+    def test_disabled_by_config(self, monkeypatch):
+        """Test that the UDP receiver refuses to start when UDP is disabled.
+
+        Args:
+            monkeypatch: Pytest fixture.
+
+        Returns:
+            None: No return value.
+        """
+        import pytest
+
+        from tpi_redes.core.errors import ProtocolDisabledError
+        from tpi_redes.transport import udp_server
+
+        monkeypatch.setattr(udp_server, "UDP_DISABLED", True)
+
+        with pytest.raises(ProtocolDisabledError, match="TPI_REDES_DISABLE_UDP"):
+            UDPServer(host="127.0.0.1", port=0, save_dir="/tmp")
@@ -21,7 +21,9 @@ const backendBinaryName =
   process.platform === 'win32' ? 'tpi-redes-backend.exe' : 'tpi-redes-backend';
 const packagedBackendDirName = 'backend-runtime';
 const appDataRoot = path.join(os.homedir(), '.tpi-redes');
-const receivedFilesDir = path.join(appDataRoot, 'received_files');
+// How long a backend gets to shut down cleanly after SIGINT before SIGKILL.
+const backendStopGraceMs = 3000;
+let receivedFilesDir = path.join(appDataRoot, 'received_files');
 
 let mainWindow: BrowserWindow | null = null;
 let backendProcess: ChildProcessWithoutNullStreams | null = null;
@@ -177,6 +179,29 @@ function spawnBackendOnce(commandArgs: string[]): Promise<string> {
   });
 }
 
+interface OutputDirResult {
+  path: string;
+  ok: boolean;
+  free_bytes: number | null;
+  errors: { code: string; message: string }[];
+  saved?: boolean;
+}
+
+async function runOutputDirCommand(commandArgs: string[]): Promise<OutputDirResult | null> {
+  const output = await spawnBackendOnce(['output-dir', ...commandArgs]);
+  const match = output.match(/\{.*"OUTPUT_DIR".*\}/);
+  return match ? JSON.parse(match[0]) : null;
+}
+
+async function loadOutputDirectory() {
+  try {
+    const result = await runOutputDirCommand(['show']);
+    if (result?.ok) receivedFilesDir = result.path;
+  } catch (error) {
+    console.error('Could not load the output directory:', error);
+  }
+}
+
 // IPC Handlers for backend CLI
 ipcMain.handle('start-server', async (_event, args) => {
   const saveDir = getReceivedFilesDir();
@@ -212,6 +237,8 @@ ipcMain.handle('send-files', async (_event, args) => {
   if (args.interface) cmdArgs.push('--interface', String(args.interface));
   if (args.delay) cmdArgs.push('--delay', String(args.delay));
   if (args.chunkSize) cmdArgs.push('--chunk-size', String(args.chunkSize));
+  if (args.sla) cmdArgs.push('--sla', String(args.sla));
+  if (args.preset) cmdArgs.push('--preset', String(args.preset));
 
   return spawnManagedBackendProcess(cmdArgs);
 });
@@ -249,6 +276,47 @@ ipcMain.handle('scan-network', async () => {
   }
 });
 
+ipcMain.handle(
+  'ping-peer',
+  async (_event, args: { ip: string; port: number; protocol: 'tcp' | 'udp' }) => {
+    const fallback = { ip: args.ip, port: args.port, protocol: args.protocol, reachable: false };
+    try {
+      const output = await spawnBackendOnce([
+        'ping-peer',
+        '--ip',
+        args.ip,
+        '--port',
+        args.port.toString(),
+        '--protocol',
+        args.protocol,
+      ]);
+      const match = output.match(/\{.*"PING_RESULT".*\}/);
+      return match ? JSON.parse(match[0]) : { ...fallback, error: 'No reply' };
+    } catch (error) {
+      return { ...fallback, error: String(error) };
+    }
+  },
+);
+
+ipcMain.handle(
+  'get-pairing-code',
+  async (_event, args: { port: number; protocol: 'tcp' | 'udp' }) => {
+    try {
+      const output = await spawnBackendOnce([
+        'pair-code',
+        '--port',
+        args.port.toString(),
+        '--protocol',
+        args.protocol,
+      ]);
+      const match = output.match(/\{.*"PAIRING".*\}/);
+      return match ? JSON.parse(match[0]) : { error: 'No output' };
+    } catch (error) {
+      return { error: String(error) };
+    }
+  },
+);
+
 ipcMain.handle('stop-process', async () => {
   if (backendProcess) {
     console.log('Stopping backend process via IPC...');
@@ -271,6 +339,85 @@ ipcMain.handle('get-interfaces', async () => {
   }
 });
 
+// The backend's ENABLED_PROTOCOLS, read once from `config show` so the UI
+// offers exactly what the CLI accepts.
+let enabledProtocols: Promise<string[]> | null = null;
+
+async function loadProtocols(): Promise<string[]> {
+  const output = await spawnBackendOnce(['config', 'show']);
+  const match = output.match(/\{.*"CONFIG".*\}/);
+  if (!match) throw new Error('No CONFIG event');
+  return JSON.parse(match[0]).settings.protocols;
+}
+
+ipcMain.handle('get-protocols', async () => {
+  if (!enabledProtocols) enabledProtocols = loadProtocols();
+  try {
+    return await enabledProtocols;
+  } catch {
+    // Retry on the next call; until then the CLI still rejects a disabled protocol.
+    enabledProtocols = null;
+    return ['tcp', 'udp'];
+  }
+});
+
+ipcMain.handle('inspect-file', async (_event, filePath: string) => {
+  try {
+    const output = await spawnBackendOnce(['inspect-file', filePath]);
+    const match = output.match(/\{.*"FILE_INFO".*\}/);
+    return match ? JSON.parse(match[0]) : { path: filePath, error: 'No output' };
+  } catch (error) {
+    return { path: filePath, error: String(error) };
+  }
+});
+
+ipcMain.handle('list-presets', async () => {
+  try {
+    const output = await spawnBackendOnce(['preset', 'list']);
+    const match = output.match(/\{.*"PRESETS".*\}/);
+    return match ? JSON.parse(match[0]).presets : [];
+  } catch {
+    return [];
+  }
+});
+
+ipcMain.handle('save-preset', async (_event, preset) => {
+  const cmdArgs = [
+    'preset',
+    'save',
+    String(preset.name),
+    '--ip',
+    String(preset.ip),
+    '--port',
+    String(preset.port),
+    '--protocol',
+    String(preset.protocol),
+  ];
+  if (preset.chunk_size) cmdArgs.push('--chunk-size', String(preset.chunk_size));
+  if (preset.delay) cmdArgs.push('--delay', String(preset.delay));
+  if (preset.no_pacing) cmdArgs.push('--no-pacing');
+  else if (preset.pacing_rate) cmdArgs.push('--pacing-rate', String(preset.pacing_rate));
+  if (preset.emit_manifest) cmdArgs.push('--emit-manifest');
+  if (preset.sla) cmdArgs.push('--sla', String(preset.sla));
+  if (preset.knock) cmdArgs.push('--knock');
+
+  try {
+    await spawnBackendOnce(cmdArgs);
+    return true;
+  } catch {
+    return false;
+  }
+});
+
+ipcMain.handle('delete-preset', async (_event, name: string) => {
+  try {
+    await spawnBackendOnce(['preset', 'delete', name]);
+    return true;
+  } catch {
+    return false;
+  }
+});
+
 ipcMain.handle('get-local-ip', () => {
   const nets = os.networkInterfaces();
   for (const name of Object.keys(nets)) {
@@ -288,6 +435,20 @@ ipcMain.handle('get-downloads-dir', () => {
   return getReceivedFilesDir();
 });
 
+ipcMain.handle('set-output-directory', async (_event, dirPath: string) => {
+  const fallback = { path: dirPath, ok: false, free_bytes: null, saved: false };
+  try {
+    const result = await runOutputDirCommand(['set', dirPath]);
+    if (!result) {
+      return { ...fallback, errors: [{ code: 'NO_OUTPUT', message: 'No output' }] };
+    }
+    if (result.saved) receivedFilesDir = result.path;
+    return result;
+  } catch (error) {
+    return { ...fallback, errors: [{ code: 'BACKEND_ERROR', message: String(error) }] };
+  }
+});
+
 ipcMain.handle('list-files', async (_event, dirPath) => {
   try {
     if (!fs.existsSync(dirPath)) return [];
@@ -353,12 +514,12 @@ ipcMain.handle('verify-file', async (_event, filePath) => {
   }
 });
 
-function spawnManagedBackendProcess(commandArgs: string[]) {
+async function spawnManagedBackendProcess(commandArgs: string[]) {
   if (backendProcess) {
     console.log('Killing existing backend process...');
     const pid = backendProcess.pid;
     if (pid) {
-      killProcessTree(pid);
+      await killProcessTree(pid);
     }
     backendProcess = null;
   }
@@ -441,6 +602,7 @@ app.on('ready', () => {
   if (!isDev) {
     ensurePackagedBackendRuntime();
   }
+  void loadOutputDirectory();
   createWindow();
 });
 
@@ -450,10 +612,32 @@ app.on('window-all-closed', () => {
   }
 });
 
+function isRunning(pid: number) {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
 async function killProcessTree(pid: number) {
-  return new Promise<void>((resolve) => {
-    if (!pid) return resolve();
+  if (!pid) return;
+
+  // SIGINT lets the backend run its Ctrl+C handling (a receiver tells its
+  // sender the transfer was cancelled) before anything is force-killed.
+  try {
+    process.kill(pid, 'SIGINT');
+  } catch {
+    return;
+  }
+  const deadline = Date.now() + backendStopGraceMs;
+  while (isRunning(pid) && Date.now() < deadline) {
+    await new Promise((resolve) => setTimeout(resolve, 100));
+  }
+  if (!isRunning(pid)) return;
 
+  return new Promise<void>((resolve) => {
     exec(`pkill -P ${pid}`, () => {
       try {
         process.kill(pid, 'SIGKILL');
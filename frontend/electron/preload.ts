@@ -20,8 +20,15 @@ contextBridge.exposeInMainWorld('api', {
   },
   startProxy: (config: unknown) => ipcRenderer.invoke('start-proxy', config),
   scanNetwork: () => ipcRenderer.invoke('scan-network'),
+  pingPeer: (args: unknown) => ipcRenderer.invoke('ping-peer', args),
+  getPairingCode: (args: unknown) => ipcRenderer.invoke('get-pairing-code', args),
   stopProcess: () => ipcRenderer.invoke('stop-process'),
   getLocalIp: () => ipcRenderer.invoke('get-local-ip'),
+  getProtocols: () => ipcRenderer.invoke('get-protocols'),
+  inspectFile: (path: string) => ipcRenderer.invoke('inspect-file', path),
+  listPresets: () => ipcRenderer.invoke('list-presets'),
+  savePreset: (preset: unknown) => ipcRenderer.invoke('save-preset', preset),
+  deletePreset: (name: string) => ipcRenderer.invoke('delete-preset', name),
   onPacketCapture: (callback: (data: unknown) => void) => {
     const subscription = (_event: unknown, value: unknown) => callback(value);
     ipcRenderer.on('packet-capture', subscription);
@@ -41,6 +48,7 @@ contextBridge.exposeInMainWorld('api', {
   getFilePath: (file: File) => webUtils.getPathForFile(file),
   // File System
   getDownloadsDir: () => ipcRenderer.invoke('get-downloads-dir'),
+  setOutputDirectory: (path: string) => ipcRenderer.invoke('set-output-directory', path),
   listFiles: (path: string) => ipcRenderer.invoke('list-files', path),
   openPath: (path: string) => ipcRenderer.invoke('open-path', path),
   openFolder: (path: string) => ipcRenderer.invoke('open-folder', path),
@@ -1,4 +1,4 @@
-import { ArrowDown, ArrowUp, Clock, Trash2 } from 'lucide-react';
+import { ArrowDown, ArrowUp, Clock, Download, Trash2 } from 'lucide-react';
 import type React from 'react';
 import { useEffect, useState } from 'react';
 import BaseModal from '../../shared/components/BaseModal';
@@ -34,6 +34,20 @@ const HistoryModal: React.FC<HistoryModalProps> = ({ onClose }) => {
     setHistory([]);
   };
 
+  /**
+   * Download the history as JSON, including each entry's environment snapshot,
+   * so it can be attached to a bug report.
+   */
+  const exportHistory = () => {
+    const blob = new Blob([JSON.stringify(history, null, 2)], { type: 'application/json' });
+    const url = URL.createObjectURL(blob);
+    const link = document.createElement('a');
+    link.href = url;
+    link.download = 'tpi-redes-history.json';
+    link.click();
+    URL.revokeObjectURL(url);
+  };
+
   return (
     <BaseModal
       isOpen={true}
@@ -43,9 +57,14 @@ const HistoryModal: React.FC<HistoryModalProps> = ({ onClose }) => {
       size="lg"
       headerContent={
         history.length > 0 && (
-          <Button variant="danger" size="icon" onClick={clearHistory} title="Clear History">
-            <Trash2 size={18} />
-          </Button>
+          <div className="flex gap-2">
+            <Button variant="secondary" size="icon" onClick={exportHistory} title="Export History">
+              <Download size={18} />
+            </Button>
+            <Button variant="danger" size="icon" onClick={clearHistory} title="Clear History">
+              <Trash2 size={18} />
+            </Button>
+          </div>
         )
       }
     >
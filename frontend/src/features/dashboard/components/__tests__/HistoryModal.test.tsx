@@ -74,4 +74,41 @@ describe('HistoryModal', () => {
     fireEvent.click(clearBtn);
     expect(mockClearHistory).toHaveBeenCalled();
   });
+
+  it('exports history with environment snapshots as JSON', () => {
+    const createObjectURL = vi.fn().mockReturnValue('blob:history');
+    const revokeObjectURL = vi.fn();
+    Object.assign(URL, { createObjectURL, revokeObjectURL });
+    const click = vi.spyOn(HTMLAnchorElement.prototype, 'click').mockImplementation(() => {});
+    mockLoadHistory.mockReturnValue([
+      {
+        id: '1',
+        filename: 'foo',
+        timestamp: 0,
+        size: 0,
+        status: 'success',
+        direction: 'sent',
+        protocol: 'TCP',
+        environment: {
+          app_version: '0.1.0',
+          os: 'Linux',
+          python: '3.13.0',
+          protocol: 'tcp',
+          local: '10.0.0.1:50000',
+          remote: '10.0.0.2:8080',
+          peer_version: '0.1.0',
+          settings: { chunk_size: 4096 },
+          retries: 0,
+        },
+      },
+    ]);
+
+    render(<HistoryModal onClose={mockOnClose} />);
+
+    fireEvent.click(screen.getByTitle('Export History'));
+    expect(createObjectURL).toHaveBeenCalledWith(expect.any(Blob));
+    expect(click).toHaveBeenCalled();
+    expect(revokeObjectURL).toHaveBeenCalledWith('blob:history');
+    click.mockRestore();
+  });
 });
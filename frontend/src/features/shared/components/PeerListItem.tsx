@@ -7,6 +7,8 @@ import type React from 'react';
  * @property ip - IP address of the peer.
  * @property port - Optional port number.
  * @property hostname - Hostname if available.
+ * @property details - Optional capacity summary shown under the address.
+ * @property disabledReason - If set, the peer cannot be selected and this is shown instead.
  * @property onSelect - Callback when the peer is selected.
  * @property className - Optional additional CSS classes.
  */
@@ -14,6 +16,8 @@ interface PeerListItemProps {
   ip: string;
   port?: number;
   hostname?: string;
+  details?: string;
+  disabledReason?: string;
   onSelect?: () => void;
   className?: string;
 }
@@ -21,12 +25,20 @@ interface PeerListItemProps {
 /**
  * A list item representing a discovered network peer.
  */
-const PeerListItem: React.FC<PeerListItemProps> = ({ ip, port, hostname, onSelect }) => {
+const PeerListItem: React.FC<PeerListItemProps> = ({
+  ip,
+  port,
+  hostname,
+  details,
+  disabledReason,
+  onSelect,
+}) => {
   return (
     <button
       type="button"
-      className="w-full group bg-gray-800/40 backdrop-blur-sm border border-white/5 p-3 rounded-lg flex items-center justify-between hover:bg-gray-800/70 hover:border-white/10 transition-all outline-none focus:ring-2 focus:ring-blue-500/50 cursor-pointer text-left"
+      className="w-full group bg-gray-800/40 backdrop-blur-sm border border-white/5 p-3 rounded-lg flex items-center justify-between hover:bg-gray-800/70 hover:border-white/10 transition-all outline-none focus:ring-2 focus:ring-blue-500/50 cursor-pointer text-left disabled:opacity-50 disabled:cursor-not-allowed"
       onClick={onSelect}
+      disabled={Boolean(disabledReason)}
     >
       <div className="flex items-center gap-3">
         <div className="p-2 rounded-lg bg-blue-500/10 text-blue-400 shrink-0 group-hover:bg-blue-500/20 transition-colors">
@@ -45,11 +57,16 @@ const PeerListItem: React.FC<PeerListItemProps> = ({ ip, port, hostname, onSelec
               </>
             )}
           </div>
+          {details && <p className="text-xs text-gray-500">{details}</p>}
         </div>
       </div>
-      <div className="text-blue-500 opacity-0 group-hover:opacity-100 transition-opacity transform translate-x-2 group-hover:translate-x-0 font-bold text-xs uppercase">
-        Connect &rarr;
-      </div>
+      {disabledReason ? (
+        <div className="text-gray-400 font-bold text-xs uppercase">{disabledReason}</div>
+      ) : (
+        <div className="text-blue-500 opacity-0 group-hover:opacity-100 transition-opacity transform translate-x-2 group-hover:translate-x-0 font-bold text-xs uppercase">
+          Connect &rarr;
+        </div>
+      )}
     </button>
   );
 };
@@ -1,4 +1,5 @@
 import type React from 'react';
+import { useEffect, useState } from 'react';
 import BinarySwitch from './BinarySwitch';
 
 /**
@@ -18,6 +19,7 @@ interface ProtocolToggleProps {
 
 /**
  * A toggle switch specifically for selecting between TCP and UDP protocols.
+ * Locked to TCP when the deployment disables UDP (`TPI_REDES_DISABLE_UDP`).
  */
 const ProtocolToggle: React.FC<ProtocolToggleProps> = ({
   protocol,
@@ -25,12 +27,24 @@ const ProtocolToggle: React.FC<ProtocolToggleProps> = ({
   disabled = false,
   className,
 }) => {
+  const [udpEnabled, setUdpEnabled] = useState(true);
+
+  useEffect(() => {
+    window.api.getProtocols?.().then((protocols) => setUdpEnabled(protocols.includes('udp')));
+  }, []);
+
+  useEffect(() => {
+    if (!udpEnabled && protocol === 'udp') {
+      onChange('tcp');
+    }
+  }, [udpEnabled, protocol, onChange]);
+
   return (
     <BinarySwitch
       value={protocol}
       options={['tcp', 'udp']}
       onChange={onChange}
-      disabled={disabled}
+      disabled={disabled || !udpEnabled}
       className={className}
       activeColor={(p) =>
         p === 'tcp'
@@ -1,6 +1,7 @@
 import { Loader2, Radio, Wifi } from 'lucide-react';
 import type React from 'react';
 import type { Peer } from '../types';
+import { Formatters } from '../utils/formatters';
 import BaseModal from './BaseModal';
 import EmptyState from './EmptyState';
 import PeerListItem from './PeerListItem';
@@ -10,10 +11,11 @@ import PeerListItem from './PeerListItem';
  *
  * @property isOpen - Whether the modal is open.
  * @property onClose - Callback to close the modal.
- * @property onSelect - Callback when a peer is selected.
+ * @property onSelect - Callback when a peer is selected, with the port for `protocol`.
  * @property scanning - Whether a scan is currently in progress.
  * @property peers - List of discovered peers.
  * @property error - Optional error message.
+ * @property protocol - Protocol the transfer will use; peers not accepting it are disabled.
  */
 interface ScanModalProps {
   isOpen: boolean;
@@ -22,8 +24,53 @@ interface ScanModalProps {
   scanning: boolean;
   peers: Peer[];
   error?: string;
+  protocol?: 'tcp' | 'udp';
 }
 
+/**
+ * Explain why a peer cannot take a transfer, based on its advertised capacity.
+ *
+ * @param peer - The discovered peer.
+ * @param protocol - Protocol the transfer will use.
+ * @returns A short reason, or undefined if the peer can be selected.
+ */
+const unavailableReason = (peer: Peer, protocol?: 'tcp' | 'udp') => {
+  const accepted = peer.listeners ? Object.keys(peer.listeners) : peer.protocols;
+  if (protocol && accepted && !accepted.includes(protocol)) {
+    return `No ${protocol.toUpperCase()}`;
+  }
+  if (peer.free_bytes === 0) return 'Disk full';
+  return undefined;
+};
+
+/**
+ * Point a peer at the port it listens on for the transfer's protocol.
+ *
+ * @param peer - The discovered peer.
+ * @param protocol - Protocol the transfer will use.
+ * @returns The peer, with `port` taken from its advertised listeners when known.
+ */
+const withListenerPort = (peer: Peer, protocol?: 'tcp' | 'udp'): Peer => {
+  const port = protocol && peer.listeners?.[protocol];
+  return port ? { ...peer, port } : peer;
+};
+
+/**
+ * Summarize a peer's advertised capacity for display.
+ *
+ * @param peer - The discovered peer.
+ * @returns Details joined by bullets, or undefined if nothing was advertised.
+ */
+const capacityDetails = (peer: Peer) => {
+  const details = [
+    peer.free_bytes != null && `${Formatters.bytes(peer.free_bytes)} free`,
+    peer.auth_required && 'Auth required',
+    peer.audit && 'Audit only',
+    Boolean(peer.active_transfers) && 'Busy',
+  ].filter(Boolean);
+  return details.length > 0 ? details.join(' • ') : undefined;
+};
+
 /**
  * A modal that displays the status and results of a peer discovery scan.
  */
@@ -34,6 +81,7 @@ const ScanModal: React.FC<ScanModalProps> = ({
   scanning,
   peers,
   error,
+  protocol,
 }) => {
   return (
     <BaseModal
@@ -72,9 +120,11 @@ const ScanModal: React.FC<ScanModalProps> = ({
             <PeerListItem
               key={`${peer.ip}-${idx}`}
               ip={peer.ip}
-              port={peer.port}
+              port={withListenerPort(peer, protocol).port}
               hostname={peer.hostname}
-              onSelect={() => onSelect(peer)}
+              details={capacityDetails(peer)}
+              disabledReason={unavailableReason(peer, protocol)}
+              onSelect={() => onSelect(withListenerPort(peer, protocol))}
             />
           ))}
         </div>
@@ -8,6 +8,22 @@ export interface AppStats {
   bytesReceived: number;
 }
 
+/**
+ * Conditions a sent file was transferred under, as reported by the backend.
+ * Kept with the history entry so bug reports can include it.
+ */
+export interface TransferEnvironment {
+  app_version: string;
+  os: string;
+  python: string;
+  protocol: string;
+  local: string;
+  remote: string;
+  peer_version: string | null;
+  settings: Record<string, unknown>;
+  retries: number;
+}
+
 /**
  * Record of a completed file transfer.
  */
@@ -19,6 +35,8 @@ export interface TransferHistoryItem {
   direction: 'sent' | 'received';
   status: 'success' | 'failed' | 'cancelled';
   protocol: string;
+  slaMissed?: boolean;
+  environment?: TransferEnvironment;
 }
 
 const STATS_KEY = 'tpi_redes_stats';
@@ -4,9 +4,21 @@
  * @property ip - IP address of the peer.
  * @property port - Optional port number.
  * @property hostname - Hostname if available.
+ * @property protocols - Protocols the receiver accepts, if advertised.
+ * @property listeners - Port the receiver listens on for each protocol, if advertised.
+ * @property free_bytes - Free disk space in the receiver's save directory.
+ * @property auth_required - Whether senders must knock before sending.
+ * @property active_transfers - Transfers the receiver is currently handling.
+ * @property audit - Whether the receiver only verifies files without storing them.
  */
 export interface Peer {
   ip: string;
   port?: number;
   hostname?: string;
+  protocols?: ('tcp' | 'udp')[];
+  listeners?: Partial<Record<'tcp' | 'udp', number>>;
+  free_bytes?: number | null;
+  auth_required?: boolean;
+  active_transfers?: number;
+  audit?: boolean;
 }
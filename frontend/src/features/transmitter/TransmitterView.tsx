@@ -79,6 +79,7 @@ const TransmitterView: React.FC<TransmitterViewProps> = ({
         scanning={discovery.scanning}
         peers={discovery.peers}
         error={discovery.error}
+        protocol={state.protocol}
       />
 
       {state.status === 'idle' && (
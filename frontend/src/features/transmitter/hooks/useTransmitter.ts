@@ -1,5 +1,6 @@
 import { useEffect, useRef, useState } from 'react';
 import { CHUNK_SIZE, DEFAULT_SERVER_PORT } from '../../../config/constants';
+import type { TransferEnvironment } from '../../shared/services/StorageService';
 import { StorageService } from '../../shared/services/StorageService';
 import type { SessionItem, TransferStats, TransferStatus } from '../types';
 
@@ -74,6 +75,10 @@ export const useTransmitter = ({ setBusy, addToast }: UseTransmitterProps) => {
             current?: number;
             filename?: string;
             message?: string;
+            sla_missed?: boolean;
+            environment?: TransferEnvironment;
+            elapsed?: number;
+            deadline?: number;
           };
 
           if (event.type === 'TRANSFER_UPDATE') {
@@ -113,6 +118,8 @@ export const useTransmitter = ({ setBusy, addToast }: UseTransmitterProps) => {
                 direction: 'sent',
                 status: 'success',
                 protocol: protocol.toUpperCase(),
+                slaMissed: event.sla_missed,
+                environment: event.environment,
               });
 
               setSessionHistory((prev) => [
@@ -150,6 +157,12 @@ export const useTransmitter = ({ setBusy, addToast }: UseTransmitterProps) => {
               } else {
               }
             }
+          } else if (event.type === 'SLA_WARNING') {
+            addToast(
+              'error',
+              event.status === 'overrun' ? 'SLA Exceeded' : 'SLA At Risk',
+              `${event.elapsed}s elapsed of ${event.deadline}s expected.`,
+            );
           } else if (event.type === 'ERROR') {
             setStatus('idle');
             setIsBatchActive(false);
@@ -1,5 +1,19 @@
 /// <reference types="vite/client" />
 
+interface TransferPreset {
+  name: string;
+  ip: string;
+  port: number;
+  protocol: 'tcp' | 'udp';
+  chunk_size?: number | null;
+  delay?: number | null;
+  pacing_rate?: number | null;
+  no_pacing?: boolean;
+  emit_manifest?: boolean;
+  sla?: number | null;
+  knock?: boolean;
+}
+
 interface Window {
   api: {
     startServer: (args: {
@@ -18,8 +32,40 @@ interface Window {
       delay: number;
       chunkSize: number;
       interface: string | null;
+      sla?: number;
+      preset?: string;
     }) => Promise<void>;
-    scanNetwork: () => Promise<{ ip: string; port?: number; hostname?: string }[]>;
+    scanNetwork: () => Promise<
+      {
+        ip: string;
+        port?: number;
+        hostname?: string;
+        protocols?: ('tcp' | 'udp')[];
+        listeners?: Partial<Record<'tcp' | 'udp', number>>;
+        free_bytes?: number | null;
+        auth_required?: boolean;
+        active_transfers?: number;
+        audit?: boolean;
+      }[]
+    >;
+    pingPeer: (args: { ip: string; port: number; protocol: 'tcp' | 'udp' }) => Promise<{
+      ip: string;
+      port: number;
+      protocol: 'tcp' | 'udp';
+      reachable: boolean;
+      rtt_ms?: number | null;
+      version?: string | null;
+      error?: string | null;
+    }>;
+    getPairingCode: (args: { port: number; protocol: 'tcp' | 'udp' }) => Promise<{
+      payload?: string;
+      addresses?: string[];
+      port?: number;
+      protocol?: 'tcp' | 'udp';
+      hostname?: string;
+      knock?: boolean;
+      error?: string;
+    }>;
     stopProcess: () => Promise<boolean>;
     getFilePath: (file: File) => string;
     onLog: (callback: (log: string) => void) => () => void;
@@ -37,7 +83,29 @@ interface Window {
     onSnifferError: (callback: (data: unknown) => void) => () => void;
     onProcessExit: (callback: (data: { code: number; signal: string }) => void) => () => void;
     getLocalIp: () => Promise<string>;
+    getProtocols: () => Promise<('tcp' | 'udp')[]>;
+    inspectFile: (path: string) => Promise<{
+      path: string;
+      name?: string;
+      size?: number;
+      mime?: string;
+      width?: number | null;
+      height?: number | null;
+      duration?: number | null;
+      encoding?: string | null;
+      error?: string;
+    }>;
+    listPresets: () => Promise<TransferPreset[]>;
+    savePreset: (preset: TransferPreset) => Promise<boolean>;
+    deletePreset: (name: string) => Promise<boolean>;
     getDownloadsDir: () => Promise<string>;
+    setOutputDirectory: (path: string) => Promise<{
+      path: string;
+      ok: boolean;
+      free_bytes: number | null;
+      errors: { code: string; message: string }[];
+      saved?: boolean;
+    }>;
     listFiles: (
       path: string,
     ) => Promise<{ name: string; size: number; mtime: number; path: string }[]>;